@@ -14,6 +14,7 @@ use zksvm::Releases;
 /// - "macosx-amd64"
 /// - "macosx-aarch64"
 /// - "windows-amd64"
+/// - "windows-aarch64"
 pub const ZKSVM_TARGET_PLATFORM: &str = "ZKSVM_TARGET_PLATFORM";
 
 /// The path to the releases JSON file, that was pre-fetched manually. If this
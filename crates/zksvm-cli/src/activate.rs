@@ -0,0 +1,138 @@
+use clap::{Parser, ValueEnum};
+use semver::Version;
+use std::path::Path;
+
+/// Print a script selecting a zksolc version in the current shell session only.
+///
+/// Evaluate the output, e.g. `eval "$(zksvm activate 1.3.17)"`, to set `$ZKSOLC` and
+/// `$ZKSVM_ZKSOLC_VERSION` without changing the global version. Projects pinning a version still
+/// take precedence in the `zksolc` shim.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct ActivateCmd {
+    /// The installed zksolc version to activate.
+    #[clap(add = clap_complete::ArgValueCandidates::new(crate::completions::installed_versions))]
+    pub version: Version,
+
+    /// The shell to print the script for.
+    #[clap(long, value_enum, default_value_t = ActivateShell::Bash)]
+    pub shell: ActivateShell,
+}
+
+/// Shells `activate` can print scripts for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ActivateShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl ActivateCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let binary = zksvm::version_binary(&self.version.to_string());
+        if !binary.exists() {
+            anyhow::bail!(
+                "zksolc {} is not installed; run `zksvm install {0}` first",
+                self.version
+            );
+        }
+        let bin_dir = zksvm::bin_dir();
+        let shim_dir = bin_dir.is_dir().then_some(bin_dir.as_path());
+        if shim_dir.is_none() {
+            eprintln!("zksvm: run `zksvm init` to also put a `zksolc` shim on PATH");
+        }
+        print!("{}", script(self.shell, &self.version, &binary, shim_dir));
+        Ok(())
+    }
+}
+
+/// Renders the script exporting `version` and its `binary`, prepending `shim_dir` to `PATH`.
+fn script(
+    shell: ActivateShell,
+    version: &Version,
+    binary: &Path,
+    shim_dir: Option<&Path>,
+) -> String {
+    let version = version.to_string();
+    let binary = binary.to_string_lossy();
+    let vars = [
+        (zksvm::ZKSVM_ZKSOLC_VERSION, version.as_str()),
+        ("ZKSOLC", &binary),
+    ];
+    let mut script = String::new();
+    for (name, value) in vars {
+        script += &export(shell, name, value);
+    }
+    if let Some(dir) = shim_dir {
+        script += &prepend_path(shell, dir);
+    }
+    script
+}
+
+/// Renders the line exporting `name` as `value` in `shell`.
+pub(crate) fn export(shell: ActivateShell, name: &str, value: &str) -> String {
+    match shell {
+        ActivateShell::Bash | ActivateShell::Zsh => {
+            format!("export {name}={}\n", posix_quote(value))
+        }
+        ActivateShell::Fish => format!("set -gx {name} {}\n", fish_quote(value)),
+        ActivateShell::Powershell => format!("$env:{name} = {}\n", powershell_quote(value)),
+    }
+}
+
+/// Renders the line prepending `dir` to `PATH` in `shell`.
+pub(crate) fn prepend_path(shell: ActivateShell, dir: &Path) -> String {
+    let dir = dir.to_string_lossy();
+    match shell {
+        ActivateShell::Bash | ActivateShell::Zsh => {
+            format!("export PATH={}:\"$PATH\"\n", posix_quote(&dir))
+        }
+        ActivateShell::Fish => format!("set -gx PATH {} $PATH\n", fish_quote(&dir)),
+        ActivateShell::Powershell => format!(
+            "$env:Path = {} + [IO.Path]::PathSeparator + $env:Path\n",
+            powershell_quote(&dir)
+        ),
+    }
+}
+
+fn posix_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn powershell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_scripts() {
+        let version = Version::new(1, 3, 17);
+        let binary = Path::new("/home/o'neil/.zksvm/1.3.17/zksolc-1.3.17");
+        let bin_dir = Path::new("/home/o'neil/.zksvm/bin");
+
+        assert_eq!(
+            script(ActivateShell::Bash, &version, binary, Some(bin_dir)),
+            "export ZKSVM_ZKSOLC_VERSION='1.3.17'\n\
+             export ZKSOLC='/home/o'\\''neil/.zksvm/1.3.17/zksolc-1.3.17'\n\
+             export PATH='/home/o'\\''neil/.zksvm/bin':\"$PATH\"\n"
+        );
+        assert_eq!(
+            script(ActivateShell::Fish, &version, binary, None),
+            "set -gx ZKSVM_ZKSOLC_VERSION '1.3.17'\n\
+             set -gx ZKSOLC '/home/o\\'neil/.zksvm/1.3.17/zksolc-1.3.17'\n"
+        );
+        assert_eq!(
+            script(ActivateShell::Powershell, &version, binary, Some(bin_dir)),
+            "$env:ZKSVM_ZKSOLC_VERSION = '1.3.17'\n\
+             $env:ZKSOLC = '/home/o''neil/.zksvm/1.3.17/zksolc-1.3.17'\n\
+             $env:Path = '/home/o''neil/.zksvm/bin' + [IO.Path]::PathSeparator + $env:Path\n"
+        );
+    }
+}
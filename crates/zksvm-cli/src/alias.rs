@@ -0,0 +1,67 @@
+use crate::utils;
+use clap::Parser;
+
+/// Name zksolc versions, e.g. `zksvm alias stable 1.3.17`, to use the name wherever a version is
+/// accepted. Lists all aliases without arguments.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct AliasCmd {
+    /// Name of the alias, a word like "stable" or "ci-pinned".
+    pub name: Option<String>,
+
+    /// Version the alias stands for, "latest" for the newest installed version or another alias.
+    /// Prints the version of the alias if left out.
+    pub version: Option<String>,
+
+    /// Remove the alias instead.
+    #[clap(long, requires = "name", conflicts_with = "version")]
+    pub remove: bool,
+}
+
+impl AliasCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let Some(name) = self.name else {
+            for (name, version) in zksvm::list_aliases()? {
+                println!("{name:<20} {version}");
+            }
+            return Ok(());
+        };
+        if self.remove {
+            match zksvm::remove_alias(&name)? {
+                Some(version) => println!("Removed alias {name} of zksolc {version}"),
+                None => anyhow::bail!("there is no alias named {name}"),
+            }
+            return Ok(());
+        }
+        let Some(version) = self.version else {
+            match zksvm::resolve_alias(&name) {
+                Some(version) => println!("{version}"),
+                None => anyhow::bail!("there is no alias named {name}"),
+            }
+            return Ok(());
+        };
+
+        let version = utils::parse_version(&version, &zksvm::installed_versions()?)?;
+        match zksvm::set_alias(&name, &version)? {
+            Some(previous) if previous != version => {
+                println!("{name} is now an alias of zksolc {version}, instead of {previous}")
+            }
+            _ => println!("{name} is an alias of zksolc {version}"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_alias() {
+        let args = AliasCmd::parse_from(["zksvm", "stable", "1.3.17"]);
+        assert_eq!(args.name.as_deref(), Some("stable"));
+        assert_eq!(args.version.as_deref(), Some("1.3.17"));
+        assert!(AliasCmd::parse_from(["zksvm", "--remove", "stable"]).remove);
+        assert!(AliasCmd::try_parse_from(["zksvm", "--remove"]).is_err());
+        assert!(AliasCmd::try_parse_from(["zksvm", "--remove", "stable", "1.3.17"]).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use clap::Parser;
+use console::style;
+
+/// Cross-check the release lists of all platforms for versions missing on some of them, missing
+/// checksums and malformed artifact names.
+///
+/// Helps maintainers of release sources, and explains "unknown version" errors on a single
+/// platform. The exit code is non-zero if anything was found.
+#[derive(Clone, Debug, Parser)]
+pub struct AuditCmd {}
+
+impl AuditCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let report = zksvm::audit_releases().await?;
+        for (platform, err) in &report.failed {
+            println!(
+                "{platform}: {}",
+                style(format!("release list unavailable: {err}")).red()
+            );
+        }
+        for finding in &report.findings {
+            println!("{}", style(finding).yellow());
+        }
+        let platforms = report
+            .platforms
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        println!(
+            "Audited the release lists of {}: {} findings",
+            platforms.join(", "),
+            report.findings.len()
+        );
+        if !report.is_clean() {
+            anyhow::bail!("the release lists are inconsistent");
+        }
+        Ok(())
+    }
+}
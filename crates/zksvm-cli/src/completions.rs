@@ -0,0 +1,90 @@
+use clap::{CommandFactory, Parser};
+use clap_complete::{env::Shells, CompletionCandidate, Shell};
+use std::io::{self, Write};
+
+/// Print a shell completion script.
+///
+/// The script calls back into zksvm to complete installed versions, so source it on shell startup
+/// instead of saving it, e.g. `source <(zksvm completions bash)` in `~/.bashrc`.
+#[derive(Clone, Debug, Parser)]
+pub struct CompletionsCmd {
+    /// The shell to complete in.
+    pub shell: Shell,
+}
+
+impl CompletionsCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let completer = std::env::current_exe()?;
+        let mut buf = vec![];
+        write_registration(self.shell, &completer.to_string_lossy(), &mut buf)?;
+        io::stdout().write_all(&buf)?;
+        Ok(())
+    }
+}
+
+/// Writes the script registering `completer` as the completer of zksvm in `shell`.
+fn write_registration(shell: Shell, completer: &str, buf: &mut dyn Write) -> anyhow::Result<()> {
+    let name = shell.to_string();
+    let shells = Shells::builtins();
+    let Some(env_completer) = shells.completer(&name) else {
+        anyhow::bail!("completions are not supported for {name}");
+    };
+    let cmd = crate::Zksvm::command();
+    env_completer.write_registration(
+        crate::COMPLETE_VAR,
+        cmd.get_name(),
+        cmd.get_name(),
+        completer,
+        buf,
+    )?;
+    Ok(())
+}
+
+/// Completes the installed zksolc versions.
+pub fn installed_versions() -> Vec<CompletionCandidate> {
+    zksvm::installed_versions()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|version| CompletionCandidate::new(version.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete(args: &[&str]) -> Vec<String> {
+        let args = args.iter().map(Into::into).collect::<Vec<_>>();
+        let index = args.len() - 1;
+        clap_complete::engine::complete(&mut crate::Zksvm::command(), args, index, None)
+            .unwrap()
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn writes_registration_scripts() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut buf = vec![];
+            write_registration(shell, "/usr/local/bin/zksvm", &mut buf).unwrap();
+            let script = String::from_utf8(buf).unwrap();
+            assert!(script.contains("/usr/local/bin/zksvm"), "{shell}: {script}");
+        }
+    }
+
+    #[test]
+    fn completes_installed_versions() {
+        let version = semver::Version::new(0, 0, 47);
+        zksvm::setup_data_dir().unwrap();
+        std::fs::create_dir_all(zksvm::version_path(&version.to_string())).unwrap();
+
+        assert!(complete(&["zksvm", "use", ""]).contains(&version.to_string()));
+        let remove = complete(&["zksvm", "remove", ""]);
+        assert!(remove.contains(&version.to_string()));
+        assert!(remove.contains(&"all".to_string()));
+        assert!(complete(&["zksvm", "ver"]).contains(&"verify".to_string()));
+
+        std::fs::remove_dir_all(zksvm::version_path(&version.to_string())).unwrap();
+    }
+}
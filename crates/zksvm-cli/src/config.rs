@@ -0,0 +1,88 @@
+use clap::{Parser, Subcommand};
+
+/// Read and change settings of the global config file, `~/.config/zksvm/config.toml` on Linux
+/// unless `$ZKSVM_CONFIG` points elsewhere.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct ConfigCmd {
+    #[clap(subcommand)]
+    pub cmd: ConfigSubcommand,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Subcommand)]
+pub enum ConfigSubcommand {
+    /// Print the value of a setting, e.g. `install.attempts`.
+    Get {
+        /// The setting to print.
+        key: String,
+    },
+    /// Change a setting, e.g. `zksvm config set releases-ttl 10m`.
+    Set {
+        /// The setting to change.
+        key: String,
+        /// Its new value.
+        value: String,
+    },
+    /// Remove a setting, restoring its default.
+    Unset {
+        /// The setting to remove.
+        key: String,
+    },
+    /// List all settings, and the path of the config file.
+    List,
+}
+
+impl ConfigCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let Some(path) = zksvm::config_path() else {
+            anyhow::bail!("could not detect user config directory, set $ZKSVM_CONFIG instead");
+        };
+        let mut config = zksvm::Config::read(&path)?;
+        match self.cmd {
+            ConfigSubcommand::Get { key } => match config.get(&key)? {
+                Some(value) => println!("{value}"),
+                None => anyhow::bail!("{key} is not set"),
+            },
+            ConfigSubcommand::Set { key, value } => {
+                config.set(&key, Some(&value))?;
+                config.write(&path)?;
+            }
+            ConfigSubcommand::Unset { key } => {
+                config.set(&key, None)?;
+                config.write(&path)?;
+            }
+            ConfigSubcommand::List => {
+                println!("# {}", path.display());
+                for key in zksvm::CONFIG_KEYS {
+                    match config.get(key)? {
+                        Some(value) => println!("{key:<24} {value}"),
+                        None => println!("{key:<24} -"),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let args = ConfigCmd::parse_from(["zksvm", "set", "install.attempts", "5"]);
+        assert_eq!(
+            args.cmd,
+            ConfigSubcommand::Set {
+                key: "install.attempts".into(),
+                value: "5".into()
+            }
+        );
+        assert_eq!(
+            ConfigCmd::parse_from(["zksvm", "list"]).cmd,
+            ConfigSubcommand::List
+        );
+        assert!(ConfigCmd::try_parse_from(["zksvm", "set", "install.attempts"]).is_err());
+        assert!(ConfigCmd::try_parse_from(["zksvm"]).is_err());
+    }
+}
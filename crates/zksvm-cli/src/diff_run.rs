@@ -0,0 +1,301 @@
+use crate::utils;
+use anyhow::Context;
+use clap::Parser;
+use console::style;
+use semver::Version;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Placeholder in the zksolc arguments that is replaced by each version's output directory.
+const OUT_DIR_PLACEHOLDER: &str = "{out}";
+
+/// Run two installed zksolc versions on the same input and compare the results.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct DiffRunCmd {
+    /// The baseline zksolc version.
+    pub a: String,
+
+    /// The zksolc version to compare against the baseline.
+    pub b: String,
+
+    /// Directory to capture outputs in. Defaults to a new temporary directory.
+    #[clap(long, value_name = "DIR")]
+    pub out_dir: Option<PathBuf>,
+
+    /// Arguments passed to both compilers. `{out}` is replaced by each version's output directory.
+    #[clap(last = true)]
+    pub args: Vec<String>,
+}
+
+impl DiffRunCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let a = utils::parse_exact_version(&self.a)?;
+        let b = utils::parse_exact_version(&self.b)?;
+        anyhow::ensure!(
+            a != b,
+            "cannot compare zksolc {a} with itself; pass two different versions"
+        );
+        let out_dir = match self.out_dir {
+            Some(dir) => dir,
+            None => tempfile::Builder::new()
+                .prefix("zksvm-diff-run-")
+                .tempdir()?
+                .keep(),
+        };
+
+        let run_a = RunOutput::capture(&a, &out_dir, &self.args)?;
+        let run_b = RunOutput::capture(&b, &out_dir, &self.args)?;
+
+        println!("Outputs captured in {}", out_dir.display());
+        print!("{}", summary(&run_a, &run_b));
+        Ok(())
+    }
+}
+
+/// The captured result of running a single zksolc version.
+struct RunOutput {
+    version: Version,
+    exit_code: Option<i32>,
+    warnings: usize,
+    /// Sizes of the produced files, keyed by their path relative to the output directory.
+    files: BTreeMap<PathBuf, u64>,
+}
+
+impl RunOutput {
+    fn capture(version: &Version, out_dir: &Path, args: &[String]) -> anyhow::Result<Self> {
+        let bin = zksvm::version_binary(&version.to_string());
+        if !bin.exists() {
+            anyhow::bail!("zksolc version {version} is not installed");
+        }
+
+        let dir = out_dir.join(version.to_string());
+        let artifacts = dir.join("artifacts");
+        fs::create_dir_all(&artifacts)?;
+        let artifacts_str = artifacts.to_string_lossy();
+        let args = args
+            .iter()
+            .map(|arg| arg.replace(OUT_DIR_PLACEHOLDER, &artifacts_str));
+
+        let output = Command::new(&bin)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .with_context(|| format!("failed to run {}", bin.display()))?;
+        fs::write(dir.join("stdout"), &output.stdout)?;
+        fs::write(dir.join("stderr"), &output.stderr)?;
+
+        let mut files = BTreeMap::new();
+        collect_file_sizes(&artifacts, &artifacts, &mut files)?;
+        files.insert(PathBuf::from("<stdout>"), output.stdout.len() as u64);
+
+        Ok(Self {
+            version: version.clone(),
+            exit_code: output.status.code(),
+            warnings: count_warnings(&output.stdout) + count_warnings(&output.stderr),
+            files,
+        })
+    }
+}
+
+/// Counts the warnings reported in the compiler output, either as plain text diagnostics or as
+/// standard JSON `errors` entries with a `warning` severity.
+fn count_warnings(output: &[u8]) -> usize {
+    let output = String::from_utf8_lossy(output);
+    let text = output
+        .lines()
+        .filter(|line| line.trim_start().starts_with("Warning"))
+        .count();
+    let json = output.matches(r#""severity":"warning""#).count()
+        + output.matches(r#""severity": "warning""#).count();
+    text.max(json)
+}
+
+/// Records the sizes of the files below `dir`, keyed by their path relative to `root`.
+fn collect_file_sizes(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<PathBuf, u64>,
+) -> anyhow::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_file_sizes(root, &path, files)?;
+        } else {
+            let relative = path.strip_prefix(root)?.to_path_buf();
+            files.insert(relative, entry.metadata()?.len());
+        }
+    }
+    Ok(())
+}
+
+/// Returns a table comparing the exit codes, warnings and file sizes of both runs.
+///
+/// Files produced by only one of the versions are marked as added or removed.
+fn summary(a: &RunOutput, b: &RunOutput) -> String {
+    let code = |c: Option<i32>| c.map_or_else(|| "signal".to_string(), |c| c.to_string());
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "\n{:<24}{:>16}{:>16}",
+        style("").bold(),
+        style(&a.version).bold(),
+        style(&b.version).bold()
+    );
+    let _ = writeln!(
+        out,
+        "{:<24}{:>16}{:>16}",
+        "exit code",
+        code(a.exit_code),
+        code(b.exit_code)
+    );
+    let _ = writeln!(
+        out,
+        "{:<24}{:>16}{:>16}",
+        "warnings", a.warnings, b.warnings
+    );
+
+    let paths = a
+        .files
+        .keys()
+        .chain(b.files.keys())
+        .collect::<std::collections::BTreeSet<_>>();
+    for path in paths {
+        let size_a = a.files.get(path);
+        let size_b = b.files.get(path);
+        let fmt = |s: Option<&u64>| s.map_or_else(|| "-".to_string(), |s| s.to_string());
+        let delta = match (size_a, size_b) {
+            (Some(x), Some(y)) if x == y => style("=".to_string()).dim(),
+            (Some(x), Some(y)) => {
+                let delta = *y as i64 - *x as i64;
+                if delta > 0 {
+                    style(format!("+{delta}")).red()
+                } else {
+                    style(delta.to_string()).green()
+                }
+            }
+            (None, _) => style("added".to_string()).yellow(),
+            (_, None) => style("removed".to_string()).yellow(),
+        };
+        let _ = writeln!(
+            out,
+            "{:<24}{:>16}{:>16}  {delta}",
+            path.display(),
+            fmt(size_a),
+            fmt(size_b)
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_diff_run() {
+        let args = DiffRunCmd::parse_from([
+            "zksvm",
+            "1.3.17",
+            "1.4.1",
+            "--",
+            "--bin",
+            "-o",
+            "{out}",
+            "Contract.sol",
+        ]);
+        assert_eq!(
+            args,
+            DiffRunCmd {
+                a: "1.3.17".into(),
+                b: "1.4.1".into(),
+                out_dir: None,
+                args: vec![
+                    "--bin".into(),
+                    "-o".into(),
+                    "{out}".into(),
+                    "Contract.sol".into()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn counts_warnings() {
+        let text = b"Warning: unused variable\nError: nope\n  Warning: shadowing\n";
+        assert_eq!(count_warnings(text), 2);
+        let json = br#"{"errors":[{"severity":"warning"},{"severity":"error"}]}"#;
+        assert_eq!(count_warnings(json), 1);
+    }
+
+    #[test]
+    fn collects_file_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("contracts")).unwrap();
+        fs::write(dir.path().join("combined.json"), "{}").unwrap();
+        fs::write(dir.path().join("contracts").join("A.zbin"), "0123").unwrap();
+
+        let mut files = BTreeMap::new();
+        collect_file_sizes(dir.path(), dir.path(), &mut files).unwrap();
+        assert_eq!(
+            files,
+            BTreeMap::from([
+                (PathBuf::from("combined.json"), 2),
+                (Path::new("contracts").join("A.zbin"), 4),
+            ])
+        );
+    }
+
+    #[test]
+    fn summarizes_runs() {
+        let run = |version: Version, files: &[(&str, u64)]| RunOutput {
+            version,
+            exit_code: Some(0),
+            warnings: 1,
+            files: files
+                .iter()
+                .map(|(path, size)| (PathBuf::from(path), *size))
+                .collect(),
+        };
+        let a = run(
+            Version::new(1, 3, 17),
+            &[
+                ("<stdout>", 10),
+                ("A.zbin", 4),
+                ("B.zbin", 8),
+                ("Old.zbin", 1),
+            ],
+        );
+        let b = run(
+            Version::new(1, 4, 1),
+            &[
+                ("<stdout>", 10),
+                ("A.zbin", 6),
+                ("B.zbin", 5),
+                ("New.zbin", 2),
+            ],
+        );
+
+        let summary = console::strip_ansi_codes(&summary(&a, &b)).into_owned();
+        let delta = |path: &str| {
+            summary
+                .lines()
+                .find(|line| line.starts_with(path))
+                .and_then(|line| line.split_whitespace().last())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(delta("<stdout>"), "=");
+        assert_eq!(delta("A.zbin"), "+2");
+        assert_eq!(delta("B.zbin"), "-3");
+        assert_eq!(delta("New.zbin"), "added");
+        assert_eq!(delta("Old.zbin"), "removed");
+        assert!(summary.contains("1.3.17"));
+        assert!(summary.contains("exit code"));
+    }
+}
@@ -0,0 +1,53 @@
+use clap::Parser;
+use console::style;
+
+/// Check the zksvm installation for problems and suggest how to fix them.
+///
+/// Checks the data directory, lock files, symlinks, installed binaries, the release list and
+/// `PATH`. The exit code is non-zero if anything is broken.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct DoctorCmd {
+    /// Print the report as JSON.
+    #[clap(long)]
+    pub json: bool,
+}
+
+impl DoctorCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let report = zksvm::diagnose().await;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report);
+        }
+        if !report.healthy {
+            anyhow::bail!("zksvm doctor found problems");
+        }
+        Ok(())
+    }
+}
+
+fn print_report(report: &zksvm::DiagnosticReport) {
+    for finding in &report.findings {
+        let status = match finding.severity {
+            zksvm::Severity::Ok => style("ok").green(),
+            zksvm::Severity::Warning => style("warning").yellow(),
+            zksvm::Severity::Error => style("error").red(),
+        };
+        println!("{status:>7} {}: {}", finding.check, finding.message);
+        if let Some(fix) = &finding.fix {
+            println!("        fix: {fix}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_doctor() {
+        assert_eq!(DoctorCmd::parse_from(["zksvm"]), DoctorCmd { json: false });
+        assert!(DoctorCmd::parse_from(["zksvm", "--json"]).json);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::activate::{self, ActivateShell};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Print the directories zksvm uses and how to put its shim on PATH, for setup scripts and
+/// editors.
+///
+/// The shell format can be evaluated, e.g. `eval "$(zksvm env)"`.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct EnvCmd {
+    /// Format of the output.
+    #[clap(long, value_enum, default_value_t = EnvFormat::Shell)]
+    pub format: EnvFormat,
+
+    /// The shell to print the script for, with `--format shell`.
+    #[clap(long, value_enum, default_value_t = ActivateShell::Bash)]
+    pub shell: ActivateShell,
+}
+
+/// Formats of the output of `env`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EnvFormat {
+    /// A script exporting the variables, see `--shell`.
+    Shell,
+    /// A JSON object, with the PATH lines of all shells.
+    Json,
+    /// `NAME="value"` lines, as read by dotenv libraries.
+    Dotenv,
+}
+
+/// The environment of zksvm, as printed by `env`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct Env {
+    data_dir: PathBuf,
+    global_version_file: PathBuf,
+    bin_dir: PathBuf,
+    cache_dir: PathBuf,
+    /// The line putting `bin_dir` on PATH, by shell.
+    path_exports: BTreeMap<String, String>,
+}
+
+impl EnvCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let env = Env::new(
+            zksvm::data_dir(),
+            zksvm::global_version_path(),
+            zksvm::bin_dir(),
+            zksvm::cache_dir(),
+        );
+        match self.format {
+            EnvFormat::Shell => print!("{}", env.script(self.shell)),
+            EnvFormat::Json => println!("{}", serde_json::to_string_pretty(&env)?),
+            EnvFormat::Dotenv => print!("{}", env.dotenv()),
+        }
+        Ok(())
+    }
+}
+
+impl Env {
+    fn new(
+        data_dir: PathBuf,
+        global_version_file: PathBuf,
+        bin_dir: PathBuf,
+        cache_dir: PathBuf,
+    ) -> Self {
+        let path_exports = ActivateShell::value_variants()
+            .iter()
+            .filter_map(|shell| {
+                let name = shell.to_possible_value()?.get_name().to_string();
+                Some((
+                    name,
+                    activate::prepend_path(*shell, &bin_dir).trim_end().into(),
+                ))
+            })
+            .collect();
+        Self {
+            data_dir,
+            global_version_file,
+            bin_dir,
+            cache_dir,
+            path_exports,
+        }
+    }
+
+    /// Returns the variables describing the environment, without PATH.
+    fn vars(&self) -> [(&'static str, String); 4] {
+        [
+            (zksvm::ZKSVM_HOME, &self.data_dir),
+            ("ZKSVM_GLOBAL_VERSION_FILE", &self.global_version_file),
+            ("ZKSVM_BIN_DIR", &self.bin_dir),
+            ("ZKSVM_CACHE_DIR", &self.cache_dir),
+        ]
+        .map(|(name, path)| (name, path.to_string_lossy().into_owned()))
+    }
+
+    fn script(&self, shell: ActivateShell) -> String {
+        let mut script = String::new();
+        for (name, value) in self.vars() {
+            script += &activate::export(shell, name, &value);
+        }
+        script + &activate::prepend_path(shell, &self.bin_dir)
+    }
+
+    fn dotenv(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', r"\\").replace('"', "\\\""));
+        let mut dotenv = String::new();
+        for (name, value) in self.vars() {
+            dotenv += &format!("{name}={}\n", quote(&value));
+        }
+        let separator = if cfg!(windows) { ';' } else { ':' };
+        let bin_dir = self.bin_dir.to_string_lossy();
+        dotenv
+            + &format!(
+                "PATH={}\n",
+                quote(&format!("{bin_dir}{separator}${{PATH}}"))
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env() -> Env {
+        Env::new(
+            "/home/dev/.zksvm".into(),
+            "/home/dev/.zksvm/.global-version".into(),
+            "/home/dev/.zksvm/bin".into(),
+            "/home/dev/.zksvm/.cache".into(),
+        )
+    }
+
+    #[test]
+    fn parse_env() {
+        let args = EnvCmd::parse_from(["zksvm", "--format", "json"]);
+        assert_eq!(args.format, EnvFormat::Json);
+        assert_eq!(args.shell, ActivateShell::Bash);
+        assert!(EnvCmd::try_parse_from(["zksvm", "--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn renders_env() {
+        let env = env();
+        assert_eq!(
+            env.script(ActivateShell::Fish),
+            "set -gx ZKSVM_HOME '/home/dev/.zksvm'\n\
+             set -gx ZKSVM_GLOBAL_VERSION_FILE '/home/dev/.zksvm/.global-version'\n\
+             set -gx ZKSVM_BIN_DIR '/home/dev/.zksvm/bin'\n\
+             set -gx ZKSVM_CACHE_DIR '/home/dev/.zksvm/.cache'\n\
+             set -gx PATH '/home/dev/.zksvm/bin' $PATH\n"
+        );
+        assert!(env.dotenv().ends_with(
+            "ZKSVM_CACHE_DIR=\"/home/dev/.zksvm/.cache\"\nPATH=\"/home/dev/.zksvm/bin:${PATH}\"\n"
+        ));
+
+        let json = serde_json::to_value(&env).unwrap();
+        assert_eq!(json["bin_dir"], "/home/dev/.zksvm/bin");
+        assert_eq!(
+            json["path_exports"]["bash"],
+            "export PATH='/home/dev/.zksvm/bin':\"$PATH\""
+        );
+        assert_eq!(json["path_exports"].as_object().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,20 @@
+//! Executing zksolc binaries on behalf of the user.
+
+use std::{ffi::OsString, path::Path, process::Command};
+
+/// Runs the zksolc binary at `bin` with `args`, forwarding stdio.
+///
+/// On Unix the current process is replaced, elsewhere the exit code of zksolc is returned.
+pub fn exec(bin: &Path, args: impl IntoIterator<Item = OsString>) -> anyhow::Result<i32> {
+    let mut cmd = Command::new(bin);
+    cmd.args(args);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(cmd.exec().into())
+    }
+    #[cfg(not(unix))]
+    {
+        Ok(cmd.status()?.code().unwrap_or(-1))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::utils;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Bundle installed zksolc versions into a tar archive, to install them on machines without
+/// network access with `install --bundle`.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct ExportCmd {
+    /// Installed versions to bundle, or requirements such as "^1.3". Defaults to all installed
+    /// versions.
+    #[clap(add = clap_complete::ArgValueCandidates::new(crate::completions::installed_versions))]
+    pub versions: Vec<String>,
+
+    /// Path to write the bundle to.
+    #[clap(long, short, value_name = "FILE")]
+    pub output: PathBuf,
+}
+
+impl ExportCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let installed = zksvm::installed_versions()?;
+        let versions = if self.versions.is_empty() {
+            installed
+        } else {
+            self.versions
+                .iter()
+                .map(|version| utils::parse_version(version, &installed))
+                .collect::<anyhow::Result<Vec<_>>>()?
+        };
+        anyhow::ensure!(!versions.is_empty(), "no zksolc versions are installed");
+
+        zksvm::export_bundle(&versions, zksvm::artifact_platform()?, &self.output)?;
+        for version in &versions {
+            println!("Bundled zksolc {version}");
+        }
+        println!("Wrote {}", self.output.display());
+        Ok(())
+    }
+}
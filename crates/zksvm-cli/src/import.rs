@@ -0,0 +1,26 @@
+use clap::Parser;
+use semver::Version;
+use std::path::PathBuf;
+
+/// Install a local zksolc binary, e.g. a custom build, so that it can be selected with `use`.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct ImportCmd {
+    /// Path to the zksolc binary.
+    pub path: PathBuf,
+
+    /// Version to install the binary as. Defaults to the version it reports with `--version`.
+    #[clap(long)]
+    pub version: Option<Version>,
+}
+
+impl ImportCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let imported = zksvm::import_binary(&self.path, self.version.as_ref())?;
+        println!(
+            "Imported zksolc {} (sha256 {})",
+            imported.version, imported.sha256
+        );
+        println!("Run `zksvm use {}` to select it", imported.version);
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::utils;
+use clap::{Parser, ValueEnum};
+use url::Url;
+
+/// Show version information reported by an installed zksolc binary.
+#[derive(Clone, Debug, Parser)]
+pub struct InfoCmd {
+    /// zksolc version to inspect. Defaults to the global version.
+    pub version: Option<String>,
+
+    /// Print the information as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Print a script downloading and verifying the release instead, for machines zksvm cannot
+    /// run on. The version does not have to be installed.
+    #[clap(long = "as", value_name = "FORMAT", conflicts_with = "json")]
+    pub script: Option<ScriptFormat>,
+
+    /// Download the artifact of this platform instead of the current one, with `--as`.
+    #[clap(long, value_name = "PLATFORM", requires = "script")]
+    pub platform: Option<zksvm::Platform>,
+}
+
+/// Formats of the download script printed by `info --as`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ScriptFormat {
+    /// `curl` followed by `sha256sum -c`.
+    Curl,
+}
+
+impl InfoCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        if let Some(format) = self.script {
+            return self.print_script(format).await;
+        }
+
+        let version = match self.version {
+            Some(version) => utils::parse_exact_version(&version)?,
+            None => zksvm::get_global_version()?.ok_or(zksvm::SvmError::GlobalVersionNotSet)?,
+        };
+        let info = zksvm::compiler_info(&version)?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&info)?);
+            return Ok(());
+        }
+        println!("version:       {}", info.version);
+        println!(
+            "llvm revision: {}",
+            info.llvm_revision.as_deref().unwrap_or("-")
+        );
+        println!("commit:        {}", info.commit.as_deref().unwrap_or("-"));
+        println!(
+            "path:          {}",
+            zksvm::version_binary(&version.to_string()).display()
+        );
+        Ok(())
+    }
+
+    async fn print_script(self, format: ScriptFormat) -> anyhow::Result<()> {
+        let platform = match self.platform {
+            Some(platform) => platform,
+            None => zksvm::artifact_platform()?,
+        };
+        let source = zksvm::ReleaseSource::from_env()?;
+        let releases = zksvm::all_releases_from(&source, platform).await?;
+        let version = match self.version {
+            Some(version) => {
+                let versions = releases.releases.keys().cloned().collect::<Vec<_>>();
+                utils::parse_version(&version, &versions)?
+            }
+            None => zksvm::get_global_version()?.ok_or(zksvm::SvmError::GlobalVersionNotSet)?,
+        };
+        let checksum = releases
+            .get_checksum(&version)
+            .ok_or(zksvm::SvmError::UnknownVersion)?;
+
+        let url = source.version_url(platform, &version, &releases)?;
+        match format {
+            ScriptFormat::Curl => println!("{}", curl_script(&url, &hex::encode(checksum))),
+        }
+        Ok(())
+    }
+}
+
+/// Returns a shell snippet downloading `url` to `./zksolc` and verifying its checksum.
+fn curl_script(url: &Url, sha256: &str) -> String {
+    format!("curl -fL {url} -o zksolc && echo \"{sha256}  zksolc\" | sha256sum -c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curl_script_verifies_checksum() {
+        let url =
+            Url::parse("https://example.com/linux-amd64/zksolc-linux-amd64-musl-v1.4.1").unwrap();
+        assert_eq!(
+            curl_script(&url, "deadbeef"),
+            "curl -fL https://example.com/linux-amd64/zksolc-linux-amd64-musl-v1.4.1 -o zksolc \
+             && echo \"deadbeef  zksolc\" | sha256sum -c"
+        );
+    }
+
+    #[test]
+    fn parse_script_format() {
+        let args = InfoCmd::parse_from(["zksvm", "1.4.1", "--as", "curl"]);
+        assert_eq!(args.script, Some(ScriptFormat::Curl));
+        assert!(InfoCmd::try_parse_from(["zksvm", "--as", "wget"]).is_err());
+        assert!(InfoCmd::try_parse_from(["zksvm", "--platform", "linux-amd64"]).is_err());
+    }
+}
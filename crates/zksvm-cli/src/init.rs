@@ -0,0 +1,81 @@
+use crate::shim;
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+/// Install a `zksolc` shim running the active zksolc version of wherever it is invoked.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct InitCmd {
+    /// Directory to install the shim in. Defaults to the `bin` directory in the zksvm data
+    /// directory.
+    #[clap(long, value_name = "DIR")]
+    pub bin_dir: Option<PathBuf>,
+
+    /// Print how to set up PATH in PowerShell and cmd.exe without editing the registry, and how
+    /// to use the global version where symlinks are not available. Implied on Windows.
+    #[clap(long)]
+    pub windows: bool,
+}
+
+impl InitCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let bin_dir = self.bin_dir.unwrap_or_else(zksvm::bin_dir);
+        let shim = shim::install(&bin_dir)?;
+        println!("Installed the zksolc shim at {}", shim.display());
+
+        if self.windows || cfg!(windows) {
+            let current = zksvm::current_binary();
+            let current_dir = current.parent().expect("the binary is in a directory");
+            print!("{}", windows_guidance(&bin_dir, current_dir));
+            return Ok(());
+        }
+        let on_path = std::env::var_os("PATH")
+            .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir == bin_dir));
+        if !on_path {
+            println!(
+                "Add {} to your PATH to use it, e.g. in your shell profile:",
+                bin_dir.display()
+            );
+            println!("  export PATH=\"{}:$PATH\"", bin_dir.display());
+        }
+        Ok(())
+    }
+}
+
+/// Renders how to put `bin_dir` on PATH in Windows shells, and how to use the binary of the
+/// global version in `current_dir` instead of the shim.
+fn windows_guidance(bin_dir: &Path, current_dir: &Path) -> String {
+    let (bin_dir, current_dir) = (bin_dir.display(), current_dir.display());
+    format!(
+        "Add {bin_dir} to your PATH to use it. In PowerShell, add this line to your profile, \
+         e.g. with `notepad $PROFILE`:\n  \
+         $env:Path = \"{bin_dir};$env:Path\"\n\
+         In cmd.exe, run this in each session or batch script:\n  \
+         set \"PATH={bin_dir};%PATH%\"\n\
+         To always run the global version instead, e.g. in IDEs, add {current_dir} instead. \
+         Without permission to create symlinks, run `zksvm config set global-binary copy` or \
+         `zksvm config set global-binary cmd` before `zksvm use`.\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_init() {
+        let args = InitCmd::parse_from(["zksvm", "--windows"]);
+        assert!(args.windows);
+        assert_eq!(args.bin_dir, None);
+    }
+
+    #[test]
+    fn renders_windows_guidance() {
+        let guidance = windows_guidance(
+            Path::new(r"C:\Users\dev\.zksvm\bin"),
+            Path::new(r"C:\Users\dev\.zksvm\current"),
+        );
+        assert!(guidance.contains(r#"$env:Path = "C:\Users\dev\.zksvm\bin;$env:Path""#));
+        assert!(guidance.contains(r#"set "PATH=C:\Users\dev\.zksvm\bin;%PATH%""#));
+        assert!(guidance.contains(r"add C:\Users\dev\.zksvm\current instead"));
+    }
+}
@@ -0,0 +1,432 @@
+use crate::{print, utils};
+use clap::{Args, Parser};
+use indicatif::MultiProgress;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+/// Install zksolc versions.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct InstallCmd {
+    /// zksolc versions to install, requirements such as "^1.3", "latest" for the newest
+    /// matching release or "nightly" for the newest pre-release.
+    pub versions: Vec<String>,
+
+    /// The compiler to install, `zksolc` or `zkvyper`. Versions of other compilers than zksolc
+    /// are kept apart and never become the global version.
+    #[clap(long, value_name = "TOOL", default_value_t, conflicts_with = "bundle")]
+    pub tool: zksvm::Tool,
+
+    /// Install plain solc versions from binaries.soliditylang.org, short for `--tool solc`.
+    #[cfg(feature = "solc")]
+    #[clap(long, conflicts_with_all = ["tool", "bundle"])]
+    pub solc: bool,
+
+    /// Also consider pre-releases when resolving "latest" and requirements.
+    #[clap(long)]
+    pub prerelease: bool,
+
+    /// Install the versions in a bundle written by `export` instead, without network access.
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["versions", "native_only"])]
+    pub bundle: Option<PathBuf>,
+
+    /// Only install versions that are missing or fail verification, and stay silent about the
+    /// others. Meant for scripts that may run more than once.
+    #[clap(long, conflicts_with = "bundle")]
+    pub if_missing: bool,
+
+    /// Install the binaries built for this platform, e.g. `linux-amd64`, instead of the ones for
+    /// this machine. They are kept apart from the installed versions, to pre-populate caches for
+    /// other targets.
+    #[clap(long, value_name = "PLATFORM", conflicts_with = "bundle")]
+    pub platform: Option<zksvm::Platform>,
+
+    /// Install the Linux binaries linked against this C library, `glibc` or `musl`, for versions
+    /// published for both. Defaults to the one of this machine.
+    #[clap(long, value_name = "LIBC", conflicts_with = "bundle")]
+    pub libc: Option<zksvm::Libc>,
+
+    #[clap(flatten)]
+    pub install: InstallArgs,
+
+    /// How many versions to download at the same time. Defaults to `install.jobs` of the config
+    /// file, or 4.
+    #[clap(long, short, value_name = "N")]
+    pub jobs: Option<usize>,
+}
+
+/// The flags of every command that installs zksolc versions, on top of the `[install]` settings of
+/// the config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Args)]
+pub struct InstallArgs {
+    /// Fail for versions without a build for this machine, instead of installing builds that run
+    /// under emulation, e.g. x64 builds under Rosetta on Apple Silicon.
+    #[clap(long)]
+    pub native_only: bool,
+
+    /// Directory to stage downloads in. Defaults to `$ZKSVM_TMPDIR` or the system temp directory.
+    #[clap(long, value_name = "DIR")]
+    pub tmp_dir: Option<PathBuf>,
+
+    /// Upper bound on the total time of each install, e.g. `300s` or `5m`.
+    #[clap(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub deadline: Option<Duration>,
+
+    /// How long to wait for another install of the same version to finish, e.g. `30s`.
+    #[clap(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub lock_timeout: Option<Duration>,
+
+    /// How many times to try each download before giving up on transient network errors.
+    /// Defaults to `install.attempts` of the config file, or 3.
+    #[clap(long, value_name = "N")]
+    pub attempts: Option<u32>,
+
+    /// Also verify downloads against the `.sha256` file published next to the artifact, if any.
+    #[clap(long)]
+    pub checksum_file: bool,
+
+    /// Install versions no checksum is published for, with a warning, instead of refusing to.
+    #[clap(long)]
+    pub allow_unchecked: bool,
+
+    /// Skip running `zksolc --version` on installed binaries to check that they report the
+    /// requested version, for platforms where they cannot be run.
+    #[clap(long)]
+    pub no_verify: bool,
+
+    /// Verify downloads against this minisign public key, or the `.pub` file at this path.
+    #[clap(long, value_name = "KEY")]
+    pub signature_key: Option<String>,
+
+    /// Refuse to install downloads without a valid signature, checked with `gpg` unless
+    /// `--signature-key` is set.
+    #[clap(long)]
+    pub require_signature: bool,
+
+    /// Fail instead of warning when the release list declares that a version requires a newer
+    /// zksvm.
+    #[clap(long)]
+    pub refuse_outdated: bool,
+}
+
+impl InstallArgs {
+    /// Returns the install options of the flags and the `defaults` of the config file.
+    ///
+    /// Flags can only turn settings of the config file on, or verification off.
+    pub fn options(
+        &self,
+        defaults: &zksvm::InstallDefaults,
+    ) -> anyhow::Result<zksvm::InstallOptions> {
+        let opts = zksvm::InstallOptions {
+            tmp_dir: self.tmp_dir.clone(),
+            deadline: self.deadline,
+            lock_timeout: self.lock_timeout,
+            verify_version: true,
+            verifier: utils::signature_verifier(
+                self.signature_key.as_deref(),
+                self.require_signature,
+            )?,
+            require_signature: self.require_signature,
+            outdated_manager: if self.refuse_outdated {
+                zksvm::ManagerVersionPolicy::Refuse
+            } else {
+                zksvm::ManagerVersionPolicy::Warn
+            },
+            ..Default::default()
+        };
+        let mut opts = defaults.apply(opts);
+        opts.sibling_checksum |= self.checksum_file;
+        opts.allow_unchecked |= self.allow_unchecked;
+        opts.verify_version &= !self.no_verify;
+        opts.native_only |= self.native_only;
+        if let Some(attempts) = self.attempts {
+            opts.retry = Some(zksvm::RetryPolicy {
+                attempts,
+                ..opts.retry.unwrap_or_default()
+            });
+        }
+        Ok(opts)
+    }
+}
+
+impl InstallCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        if let Some(bundle) = &self.bundle {
+            return install_bundle(bundle);
+        }
+        let channel = if self.prerelease
+            || self
+                .versions
+                .iter()
+                .any(|version| utils::channel_of(version) == zksvm::Channel::Prerelease)
+        {
+            zksvm::Channel::Prerelease
+        } else {
+            zksvm::Channel::Stable
+        };
+        #[cfg(feature = "solc")]
+        let tool = if self.solc {
+            zksvm::Tool::Solc
+        } else {
+            self.tool
+        };
+        #[cfg(not(feature = "solc"))]
+        let tool = self.tool;
+        let releases = match (tool, self.platform) {
+            (zksvm::Tool::Zksolc, Some(platform)) => {
+                zksvm::all_releases_in(platform.artifacts()?, channel).await?
+            }
+            (zksvm::Tool::Zksolc, None) => utils::releases_in(channel).await?,
+            (tool, platform) => {
+                let platform = platform
+                    .map_or_else(zksvm::artifact_platform, |platform| platform.artifacts())?;
+                zksvm::all_releases_from(&tool.source()?.with_channel(channel), platform).await?
+            }
+        };
+        let all_versions = releases.clone().into_versions();
+        let defaults = zksvm::Config::load()?.install;
+        let opts = zksvm::InstallOptions {
+            platform: self.platform,
+            libc: self.libc,
+            tool,
+            ..self.install.options(&defaults)?
+        };
+
+        let mut to_install = vec![];
+        for version in self.versions {
+            let version = utils::parse_version(&version, &all_versions)?;
+            let status = match tool {
+                zksvm::Tool::Zksolc => zksvm::LocalState::read()?.install_status(&version),
+                tool => zksvm::installed_tool_versions(tool)?
+                    .contains(&version)
+                    .then_some(zksvm::InstallOutcome::AlreadyInstalled),
+            }
+            // binaries of other platforms are not installed for use here
+            .filter(|_| self.platform.is_none());
+
+            if self.if_missing && status.is_some() {
+                // broken installs are downloaded again, intact ones left alone without a word
+                if tool == zksvm::Tool::Zksolc
+                    && zksvm::verify_installed(&version).await?.needs_repair()
+                    && !to_install.contains(&version)
+                {
+                    to_install.push(version);
+                }
+            } else if status == Some(zksvm::InstallOutcome::AlreadyActive) {
+                print::already_active(&version);
+            } else if status.is_some() {
+                println!("{tool} {version} is already installed");
+                if tool == zksvm::Tool::Zksolc
+                    && utils::confirm("Would you like to set it as the global version?")?
+                {
+                    zksvm::set_global_version(&version)?;
+                    print::set_global_version(&version);
+                }
+            } else if !all_versions.contains(&version) {
+                print::unknown_version(tool, &version);
+            } else if !releases.is_supported(&version) {
+                print::unsupported_version_range(&version, &releases.supported_versions());
+            } else if !to_install.contains(&version) {
+                to_install.push(version);
+            }
+        }
+        if to_install.is_empty() {
+            return Ok(());
+        }
+
+        let multi = MultiProgress::new();
+        let bars = to_install
+            .iter()
+            .map(|version| {
+                (
+                    version.clone(),
+                    multi.add(print::installing_version(tool, version)),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let jobs = self
+            .jobs
+            .or(defaults.jobs)
+            .unwrap_or(zksvm::DEFAULT_INSTALL_CONCURRENCY);
+        let results = {
+            let bars = bars.clone();
+            zksvm::install_many_with_events(&to_install, &opts, jobs, move |version, event| {
+                print::install_event(&bars[version], &event)
+            })
+            .await
+        };
+
+        let mut failed = 0;
+        for (version, res) in to_install.iter().zip(results) {
+            let bar = &bars[version];
+            match res {
+                Ok(_) => bar.finish_with_message(format!("Downloaded {tool}: {version}")),
+                Err(err) => {
+                    failed += 1;
+                    bar.abandon_with_message(format!("Failed to install {tool} {version}: {err}"));
+                }
+            }
+        }
+        let installed = to_install.len() - failed;
+        if installed > 0
+            && tool == zksvm::Tool::Zksolc
+            && self.platform.is_none()
+            && zksvm::get_global_version()?.is_none()
+        {
+            // the first requested version that installed successfully becomes the default
+            let local = zksvm::LocalState::read()?;
+            if let Some(version) = to_install.iter().find(|v| local.is_installed(v)) {
+                zksvm::set_global_version(version)?;
+                print::set_global_version(version);
+            }
+        }
+        if failed > 0 {
+            anyhow::bail!(
+                "failed to install {failed} of {} versions",
+                to_install.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Installs the versions in `bundle`, making the first of them the global version if none is set.
+fn install_bundle(bundle: &std::path::Path) -> anyhow::Result<()> {
+    let outcomes = zksvm::install_bundle(bundle)?;
+    for (version, outcome) in &outcomes {
+        match outcome {
+            zksvm::InstallOutcome::Installed => println!("Installed zksolc {version} from bundle"),
+            zksvm::InstallOutcome::AlreadyActive => print::already_active(version),
+            zksvm::InstallOutcome::AlreadyInstalled => {
+                println!("zksolc {version} is already installed")
+            }
+        }
+    }
+    if zksvm::get_global_version()?.is_none() {
+        if let Some((version, _)) = outcomes.first() {
+            zksvm::set_global_version(version)?;
+            print::set_global_version(version);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_install() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "1.3.17", "1.3.16"]);
+        assert_eq!(
+            args,
+            InstallCmd {
+                versions: vec!["1.3.17".into(), "1.3.16".into()],
+                tool: zksvm::Tool::Zksolc,
+                #[cfg(feature = "solc")]
+                solc: false,
+                prerelease: false,
+                bundle: None,
+                if_missing: false,
+                platform: None,
+                libc: None,
+                install: InstallArgs::default(),
+                jobs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn install_args_override_config() {
+        let mut defaults = zksvm::Config::default();
+        defaults.set("install.attempts", Some("5")).unwrap();
+        defaults.set("install.checksum-file", Some("true")).unwrap();
+        let defaults = defaults.install;
+
+        let opts = InstallArgs::default().options(&defaults).unwrap();
+        assert_eq!(opts.retry.unwrap().attempts, 5);
+        assert!(opts.sibling_checksum && opts.verify_version);
+
+        let args = InstallCmd::parse_from(["zksvm", "--attempts", "1", "--no-verify", "1.5.7"]);
+        let opts = args.install.options(&defaults).unwrap();
+        assert_eq!(opts.retry.unwrap().attempts, 1);
+        assert!(opts.sibling_checksum && !opts.verify_version);
+    }
+
+    #[test]
+    fn parse_install_bundle() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--bundle", "zksolc.tar"]);
+        assert_eq!(args.bundle, Some(PathBuf::from("zksolc.tar")));
+        assert!(InstallCmd::try_parse_from(["zksvm", "--bundle", "zksolc.tar", "1.3.17"]).is_err());
+    }
+
+    #[test]
+    fn parse_install_if_missing() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--if-missing", "1.3.17"]);
+        assert!(args.if_missing);
+        assert!(
+            InstallCmd::try_parse_from(["zksvm", "--if-missing", "--bundle", "zksolc.tar"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_install_tmp_dir() {
+        let args: InstallCmd =
+            InstallCmd::parse_from(["zksvm", "--tmp-dir", "/data/tmp", "1.3.17"]);
+        assert_eq!(args.install.tmp_dir, Some(PathBuf::from("/data/tmp")));
+    }
+
+    #[test]
+    fn parse_install_deadline() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--deadline", "5m", "1.3.17"]);
+        assert_eq!(args.install.deadline, Some(Duration::from_secs(300)));
+        assert!(InstallCmd::try_parse_from(["zksvm", "--deadline", "soon", "1.3.17"]).is_err());
+    }
+
+    #[test]
+    fn parse_install_platform() {
+        let args: InstallCmd =
+            InstallCmd::parse_from(["zksvm", "--platform", "linux-amd64", "1.5.7"]);
+        assert_eq!(args.platform, Some(zksvm::Platform::LinuxAmd64));
+        assert!(InstallCmd::try_parse_from(["zksvm", "--platform", "plan9", "1.5.7"]).is_err());
+    }
+
+    #[test]
+    fn parse_install_libc() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--libc", "musl", "1.5.7"]);
+        assert_eq!(args.libc, Some(zksvm::Libc::Musl));
+        assert!(InstallCmd::try_parse_from(["zksvm", "--libc", "bionic", "1.5.7"]).is_err());
+    }
+
+    #[test]
+    fn parse_install_native_only() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--native-only", "1.5.7"]);
+        assert!(args.install.native_only);
+        assert!(
+            InstallCmd::try_parse_from(["zksvm", "--native-only", "--bundle", "zksolc.tar"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_install_tool() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--tool", "zkvyper", "1.3.11"]);
+        assert_eq!(args.tool, zksvm::Tool::Zkvyper);
+        assert_eq!(args.versions, vec!["1.3.11".to_string()]);
+        assert!(InstallCmd::try_parse_from(["zksvm", "--tool", "zkllvm", "0.8.20"]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "solc")]
+    fn parse_install_solc() {
+        let args: InstallCmd = InstallCmd::parse_from(["zksvm", "--solc", "0.8.24"]);
+        assert!(args.solc);
+        assert_eq!(
+            InstallCmd::parse_from(["zksvm", "--tool", "solc", "0.8.24"]).tool,
+            zksvm::Tool::Solc
+        );
+        assert!(
+            InstallCmd::try_parse_from(["zksvm", "--solc", "--tool", "zkvyper", "0.8.24"]).is_err()
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// How long an interrupted command gets to clean up after itself before zksvm exits anyway, e.g.
+/// because it is blocked waiting for the lock of the data directory.
+pub const GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// A signal asking zksvm to stop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal {
+    /// `SIGINT`, e.g. Ctrl-C.
+    Interrupt,
+    /// `SIGTERM`.
+    Terminate,
+}
+
+impl Signal {
+    /// The conventional exit code of a process killed by the signal, `128 + signal number`.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Interrupt => 130,
+            Self::Terminate => 143,
+        }
+    }
+}
+
+/// Waits for an interrupt or termination signal.
+///
+/// Once called, the signals no longer kill the process, so that commands can be cancelled at
+/// their next await point, dropping in-flight downloads, staged temp files and lock files.
+pub async fn wait() -> Signal {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            return tokio::select! {
+                _ = tokio::signal::ctrl_c() => Signal::Interrupt,
+                _ = terminate.recv() => Signal::Terminate,
+            };
+        }
+    }
+    match tokio::signal::ctrl_c().await {
+        Ok(()) => Signal::Interrupt,
+        // without a handler the default behavior of the signal still applies
+        Err(_) => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes() {
+        assert_eq!(Signal::Interrupt.exit_code(), 130);
+        assert_eq!(Signal::Terminate.exit_code(), 143);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use crate::print;
+use clap::Parser;
+use semver::{Version, VersionReq};
+
+/// List all zksolc versions.
+#[derive(Debug, Parser)]
+pub struct ListCmd {
+    /// Only list the versions matching this requirement, e.g. `^1.4`.
+    pub req: Option<VersionReq>,
+
+    /// Only list installed versions, without fetching the release list.
+    #[clap(long, conflicts_with_all = ["available", "all"])]
+    pub installed: bool,
+
+    /// Only list versions available to install.
+    #[clap(long, visible_alias = "remote-only", conflicts_with = "all")]
+    pub available: bool,
+
+    /// List both installed versions and versions available to install. This is the default.
+    #[clap(long)]
+    pub all: bool,
+
+    /// Only list the newest N installed and available versions.
+    #[clap(long, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Show the size, install date, checksum status and path of installed versions, and the
+    /// artifact, checksum and size of releases.
+    #[clap(long)]
+    pub detailed: bool,
+}
+
+impl ListCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let filter = self.filter();
+        if !self.available {
+            let installed_versions = zksvm::LocalState::read()?.installed;
+            let global = zksvm::global_version_state()?;
+            print::global_version(&global);
+            if self.detailed {
+                let kept: HashSet<Version> =
+                    filter.apply(&installed_versions).into_iter().collect();
+                let mut installed = zksvm::list_installed().await?;
+                installed.retain(|v| kept.contains(&v.version));
+                print::installed_table(&installed);
+            } else {
+                print::installed_versions(filter.apply(&installed_versions));
+            }
+            if self.installed {
+                return Ok(());
+            }
+        }
+
+        let (releases, warnings) = zksvm::current_releases(&zksvm::StalenessThresholds::from_env())
+            .await?
+            .into_parts();
+        let installed_versions = zksvm::LocalState::read()?.installed;
+
+        let a: HashSet<Version> = releases.releases.keys().cloned().collect();
+        let b: HashSet<Version> = installed_versions.iter().cloned().collect();
+        let c = &a - &b;
+
+        if self.detailed {
+            // unlike the plain list, the table marks installed releases instead of leaving them out
+            let (title, versions) = if self.available {
+                ("Available to Install", filter.apply(&c))
+            } else {
+                ("Releases", filter.apply(&a))
+            };
+            print::releases_table(title, &zksvm::release_details(&releases, &versions).await?);
+        } else {
+            print::available_versions(filter.apply(&c));
+        }
+        warnings
+            .iter()
+            .filter(|w| !matches!(w, zksvm::Warning::Emulated { .. }))
+            .for_each(print::warning);
+
+        Ok(())
+    }
+
+    fn filter(&self) -> zksvm::VersionFilter {
+        zksvm::VersionFilter {
+            req: self.req.clone(),
+            limit: self.limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_detailed() {
+        assert!(ListCmd::parse_from(["zksvm", "--detailed"]).detailed);
+        assert!(!ListCmd::parse_from(["zksvm"]).detailed);
+    }
+
+    #[test]
+    fn parse_list_filters() {
+        let args = ListCmd::parse_from(["zksvm", "^1.4", "--installed", "--limit", "3"]);
+        assert_eq!(args.req, Some(VersionReq::parse("^1.4").unwrap()));
+        assert!(args.installed);
+        assert_eq!(args.filter().limit, Some(3));
+        assert!(ListCmd::parse_from(["zksvm", "--remote-only"]).available);
+        assert!(ListCmd::try_parse_from(["zksvm", "--installed", "--available"]).is_err());
+        assert!(ListCmd::try_parse_from(["zksvm", "--available", "--all"]).is_err());
+        assert!(ListCmd::try_parse_from(["zksvm", "1.4.x.y"]).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use clap::Parser;
+
+/// Show the zksolc version pinned by the project in the current directory.
+#[derive(Clone, Debug, Parser)]
+pub struct LocalCmd {
+    /// List all project files with pins in the current repository.
+    #[clap(long)]
+    pub list: bool,
+}
+
+impl LocalCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let dir = std::env::current_dir()?;
+
+        if self.list {
+            let root = zksvm::repository_root(&dir);
+            for file in zksvm::find_project_files(&root)? {
+                let path = file.path.strip_prefix(&root).unwrap_or(&file.path);
+                let zksolc = file.zksolc.map_or("-".to_string(), |v| v.to_string());
+                let solc = file.solc.map_or("-".to_string(), |v| v.to_string());
+                println!("{:<50} zksolc {zksolc:<8} solc {solc}", path.display());
+            }
+            return Ok(());
+        }
+
+        let workspace = zksvm::Workspace::load(&dir)?;
+        let pin = workspace
+            .files
+            .iter()
+            .find(|file| file.zksolc.is_some())
+            .and_then(|file| Some((file.zksolc.as_ref()?, &file.path)));
+        match pin {
+            Some((version, path)) => println!("{version} (set by {})", path.display()),
+            None => println!("No local zksolc version is pinned"),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable with the filter of the log, e.g. `zksvm_core=debug`, taking precedence
+/// over `-v`.
+const ZKSVM_LOG: &str = "ZKSVM_LOG";
+
+/// Logs what zksvm does to stderr, as much as `verbosity` asks for: nothing by default, zksvm's
+/// own debug events with `-v` and those of the HTTP client as well with `-vv`.
+pub fn init(verbosity: u8) {
+    let filter = match std::env::var(ZKSVM_LOG) {
+        Ok(filter) if !filter.is_empty() => EnvFilter::new(filter),
+        _ => EnvFilter::new(default_filter(verbosity)),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(console::colors_enabled_stderr())
+        .init();
+}
+
+fn default_filter(verbosity: u8) -> &'static str {
+    match verbosity {
+        0 => "off",
+        1 => "zksvm_core=debug",
+        _ => "zksvm_core=trace,reqwest=debug,hyper_util=debug",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_verbosity() {
+        for verbosity in 0..4 {
+            assert!(default_filter(verbosity).parse::<EnvFilter>().is_ok());
+        }
+        assert_eq!(default_filter(0), "off");
+        assert_eq!(default_filter(5), default_filter(2));
+    }
+}
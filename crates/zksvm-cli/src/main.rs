@@ -0,0 +1,348 @@
+//! Main svm-rs binary entry point.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/favicon.ico"
+)]
+#![warn(rustdoc::all)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::{path::PathBuf, process::ExitCode};
+
+mod activate;
+mod alias;
+mod audit;
+mod completions;
+mod config;
+mod diff_run;
+mod doctor;
+mod env;
+mod exec;
+mod export;
+mod import;
+mod info;
+mod init;
+mod install;
+mod interrupt;
+mod list;
+mod local;
+mod log;
+mod monitor;
+mod platform;
+mod print;
+mod prune;
+mod remove;
+mod run;
+mod search;
+#[cfg(feature = "self-update")]
+mod self_update;
+mod shim;
+mod stats;
+mod update;
+mod r#use;
+mod utils;
+mod verify;
+mod which;
+
+const VERSION_MESSAGE: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("VERGEN_GIT_SHA"),
+    " ",
+    env!("VERGEN_BUILD_DATE"),
+    ")"
+);
+
+/// The environment variable the completion scripts set to have zksvm complete its arguments.
+const COMPLETE_VAR: &str = "COMPLETE";
+
+/// zksolc version manager.
+#[derive(Debug, Parser)]
+#[clap(
+    name = "zksvm",
+    version = VERSION_MESSAGE,
+    next_display_order = None,
+)]
+struct Zksvm {
+    /// Print build information as JSON and exit.
+    #[clap(long, exclusive = true)]
+    build_info: bool,
+
+    /// Fetch release lists again instead of using the cached ones.
+    #[clap(long, global = true)]
+    refresh: bool,
+
+    /// Keep installs and state in this directory instead of `$ZKSVM_HOME` or `~/.zksvm`.
+    #[clap(long, global = true, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Send requests through this proxy, e.g. `http://proxy.internal:3128`, instead of the ones in
+    /// `$HTTPS_PROXY` and `$HTTP_PROXY`. Hosts in `$NO_PROXY` are still reached directly.
+    #[clap(long, global = true, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Log what zksvm does to stderr, e.g. requests and lock waits. Repeat for more detail.
+    /// `$ZKSVM_LOG` sets the filter of the log instead, e.g. `zksvm_core=debug`.
+    #[clap(long, short, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Answer all prompts with yes. Without it, prompts are answered with no when stdin is not a
+    /// terminal or `$ZKSVM_NONINTERACTIVE` is set.
+    #[clap(long, short, global = true)]
+    yes: bool,
+
+    #[clap(subcommand)]
+    cmd: Option<Subcommands>,
+}
+
+impl Zksvm {
+    /// Checks constraints clap cannot express, as `--refresh` is global but `--build-info` may
+    /// not be combined with a subcommand.
+    fn validate(self) -> Result<Self, clap::Error> {
+        if self.build_info && self.cmd.is_some() {
+            return Err(Self::command().error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "the argument '--build-info' cannot be used with a subcommand",
+            ));
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    List(list::ListCmd),
+    Install(install::InstallCmd),
+    Import(import::ImportCmd),
+    Export(export::ExportCmd),
+    Update(update::UpdateCmd),
+    Use(r#use::UseCmd),
+    Local(local::LocalCmd),
+    Alias(alias::AliasCmd),
+    Activate(activate::ActivateCmd),
+    Which(which::WhichCmd),
+    Run(run::RunCmd),
+    Remove(remove::RemoveCmd),
+    Prune(prune::PruneCmd),
+    Verify(verify::VerifyCmd),
+    Info(info::InfoCmd),
+    Search(search::SearchCmd),
+    DiffRun(diff_run::DiffRunCmd),
+    Platform(platform::PlatformCmd),
+    Init(init::InitCmd),
+    Monitor(monitor::MonitorCmd),
+    Audit(audit::AuditCmd),
+    Doctor(doctor::DoctorCmd),
+    Env(env::EnvCmd),
+    Stats(stats::StatsCmd),
+    #[cfg(feature = "self-update")]
+    #[clap(name = "self")]
+    Self_(self_update::SelfCmd),
+    Config(config::ConfigCmd),
+    Completions(completions::CompletionsCmd),
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<ExitCode> {
+    let mut args = std::env::args_os();
+    if args
+        .next()
+        .is_some_and(|arg0| shim::invoked_as_zksolc(&arg0))
+    {
+        let code = match shim::run(args.collect()).await {
+            Ok(code) => code,
+            Err(err) => {
+                eprintln!("zksvm: error: {err:#}");
+                1
+            }
+        };
+        std::process::exit(code);
+    }
+
+    clap_complete::CompleteEnv::with_factory(Zksvm::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    let matches = Zksvm::command().get_matches();
+    let opt = Zksvm::from_arg_matches(&matches)
+        .and_then(Zksvm::validate)
+        .unwrap_or_else(|err| err.exit());
+
+    if opt.build_info {
+        let mut info = zksvm::build_info();
+        info.git_sha = Some(env!("VERGEN_GIT_SHA"));
+        info.build_date = Some(env!("VERGEN_BUILD_DATE"));
+        println!("{}", info.to_json());
+        return Ok(ExitCode::SUCCESS);
+    }
+    let Some(cmd) = opt.cmd else {
+        Zksvm::command().print_help()?;
+        return Ok(ExitCode::SUCCESS);
+    };
+    log::init(opt.verbose);
+
+    if let Some(data_dir) = opt.data_dir {
+        zksvm::set_data_dir(data_dir);
+    }
+    if let Some(proxy) = &opt.proxy {
+        zksvm::set_client_config(zksvm::ClientConfig::new().proxy(proxy)?);
+    }
+    if opt.refresh {
+        zksvm::clear_releases_cache()?;
+    }
+    utils::assume_yes(opt.yes);
+    if let Some(name) = matches.subcommand_name() {
+        // stats are best effort and must never fail the command
+        let _ = zksvm::record_command(name);
+    }
+
+    // doctor reports corrupted state files instead of changing anything
+    if !matches!(cmd, Subcommands::Doctor(_)) {
+        zksvm::recover_state_files()?
+            .iter()
+            .for_each(print::warning);
+    }
+
+    // the settings of an invalid config file would otherwise be ignored without a word; a torn
+    // write of it was recovered above
+    if !matches!(cmd, Subcommands::Config(_)) {
+        match zksvm::Config::load() {
+            Ok(config) => config.warnings().iter().for_each(print::warning),
+            Err(err) => {
+                print::error(&err.into());
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+
+    if matches!(
+        cmd,
+        Subcommands::List(_)
+            | Subcommands::Install(_)
+            | Subcommands::Update(_)
+            | Subcommands::Use(_)
+    ) {
+        print::emulation_notice();
+    }
+
+    // the command runs as a task of its own, so that signals are still handled while it blocks
+    // waiting for the lock of the data directory; aborting it drops it, cleaning up downloads,
+    // temp files and locks
+    let mut task = tokio::spawn(cmd.run());
+    let signal = tokio::select! {
+        joined = &mut task => {
+            if let Err(err) = joined? {
+                print::error(&err);
+                return Ok(ExitCode::FAILURE);
+            }
+            return Ok(ExitCode::SUCCESS);
+        }
+        signal = interrupt::wait() => signal,
+    };
+    task.abort();
+    // a blocked command is only dropped once it gets its lock, which may never happen
+    let cleaned_up = tokio::time::timeout(interrupt::GRACE_PERIOD, task)
+        .await
+        .is_ok();
+    print::interrupted(cleaned_up);
+    // shutting down the runtime would wait for a blocked command as well; a global version switch
+    // cut short here has not replaced anything yet, as it stages the new files first
+    std::process::exit(signal.exit_code())
+}
+
+impl Subcommands {
+    async fn run(self) -> anyhow::Result<()> {
+        match self {
+            Subcommands::List(cmd) => cmd.run().await,
+            Subcommands::Install(cmd) => cmd.run().await,
+            Subcommands::Import(cmd) => cmd.run().await,
+            Subcommands::Export(cmd) => cmd.run().await,
+            Subcommands::Update(cmd) => cmd.run().await,
+            Subcommands::Use(cmd) => cmd.run().await,
+            Subcommands::Local(cmd) => cmd.run().await,
+            Subcommands::Alias(cmd) => cmd.run().await,
+            Subcommands::Activate(cmd) => cmd.run().await,
+            Subcommands::Which(cmd) => cmd.run().await,
+            Subcommands::Run(cmd) => cmd.run().await,
+            Subcommands::Remove(cmd) => cmd.run().await,
+            Subcommands::Prune(cmd) => cmd.run().await,
+            Subcommands::Verify(cmd) => cmd.run().await,
+            Subcommands::Info(cmd) => cmd.run().await,
+            Subcommands::Search(cmd) => cmd.run().await,
+            Subcommands::DiffRun(cmd) => cmd.run().await,
+            Subcommands::Platform(cmd) => cmd.run().await,
+            Subcommands::Init(cmd) => cmd.run().await,
+            Subcommands::Monitor(cmd) => cmd.run().await,
+            Subcommands::Audit(cmd) => cmd.run().await,
+            Subcommands::Doctor(cmd) => cmd.run().await,
+            Subcommands::Env(cmd) => cmd.run().await,
+            Subcommands::Stats(cmd) => cmd.run().await,
+            #[cfg(feature = "self-update")]
+            Subcommands::Self_(cmd) => cmd.run().await,
+            Subcommands::Config(cmd) => cmd.run().await,
+            Subcommands::Completions(cmd) => cmd.run().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_cli() {
+        Zksvm::command().debug_assert();
+    }
+
+    #[test]
+    fn parse_build_info() {
+        let opt = Zksvm::parse_from(["zksvm", "--build-info"]);
+        assert!(opt.build_info);
+        assert!(opt.cmd.is_none());
+        let opt = Zksvm::parse_from(["zksvm", "--build-info", "list"]);
+        assert!(opt.validate().is_err());
+    }
+
+    #[test]
+    fn parse_refresh() {
+        let opt = Zksvm::parse_from(["zksvm", "list", "--refresh"]);
+        assert!(opt.refresh);
+        assert!(matches!(opt.cmd, Some(Subcommands::List(_))));
+        let opt = Zksvm::parse_from(["zksvm", "--refresh", "list"]);
+        assert!(opt.refresh);
+    }
+
+    #[test]
+    fn parse_verbose() {
+        assert_eq!(Zksvm::parse_from(["zksvm", "list"]).verbose, 0);
+        assert_eq!(Zksvm::parse_from(["zksvm", "-v", "list"]).verbose, 1);
+        assert_eq!(
+            Zksvm::parse_from(["zksvm", "install", "-vv", "1.3.17"]).verbose,
+            2
+        );
+    }
+
+    #[test]
+    fn parse_proxy() {
+        let opt = Zksvm::parse_from(["zksvm", "install", "1.3.17", "--proxy", "http://proxy:3128"]);
+        assert_eq!(opt.proxy.as_deref(), Some("http://proxy:3128"));
+        assert!(Zksvm::parse_from(["zksvm", "list"]).proxy.is_none());
+    }
+
+    #[test]
+    fn parse_data_dir() {
+        let opt = Zksvm::parse_from(["zksvm", "list", "--data-dir", "/ci/zksvm"]);
+        assert_eq!(opt.data_dir, Some(PathBuf::from("/ci/zksvm")));
+        assert!(Zksvm::parse_from(["zksvm", "list"]).data_dir.is_none());
+    }
+
+    #[test]
+    fn parse_yes() {
+        let opt = Zksvm::parse_from(["zksvm", "remove", "1.3.17", "-y"]);
+        assert!(opt.yes);
+        assert!(matches!(opt.cmd, Some(Subcommands::Remove(_))));
+        assert!(!Zksvm::parse_from(["zksvm", "remove", "1.3.17"]).yes);
+    }
+}
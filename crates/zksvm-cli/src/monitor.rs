@@ -0,0 +1,108 @@
+use clap::{Parser, ValueEnum};
+use console::style;
+
+/// Check that every advertised zksolc artifact is downloadable and has a checksum.
+///
+/// Meant to run from cron or a monitoring system: the exit code is non-zero if anything is broken.
+#[derive(Clone, Debug, Parser)]
+pub struct MonitorCmd {
+    /// Platform whose artifacts are checked, or `all`. Defaults to the current platform.
+    #[clap(long, value_name = "PLATFORM", value_parser = parse_platforms)]
+    pub platform: Option<Platforms>,
+
+    /// Format of the health report.
+    #[clap(long, value_enum, default_value_t = ReportFormat::Text)]
+    pub format: ReportFormat,
+}
+
+/// Platforms selected with `monitor --platform`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Platforms {
+    All,
+    One(zksvm::Platform),
+}
+
+/// Formats of the report printed by `monitor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// One line per broken artifact.
+    Text,
+    /// The full report as JSON.
+    Json,
+}
+
+fn parse_platforms(s: &str) -> Result<Platforms, String> {
+    if s == "all" {
+        return Ok(Platforms::All);
+    }
+    s.parse().map(Platforms::One)
+}
+
+impl MonitorCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let platforms = match self.platform {
+            Some(Platforms::All) => zksvm::Platform::WITH_ARTIFACTS.to_vec(),
+            Some(Platforms::One(platform)) => vec![platform],
+            None => vec![zksvm::artifact_platform()?],
+        };
+        let report = zksvm::check_availability(&platforms).await?;
+
+        match self.format {
+            ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            ReportFormat::Text => print_report(&report),
+        }
+        if !report.healthy {
+            anyhow::bail!("some zksolc artifacts are unavailable");
+        }
+        Ok(())
+    }
+}
+
+fn print_report(report: &zksvm::HealthReport) {
+    for platform in &report.platforms {
+        if let Some(error) = &platform.error {
+            println!(
+                "{}: {}",
+                platform.platform,
+                style(format!("release list unavailable: {error}")).red()
+            );
+            continue;
+        }
+        let broken = platform
+            .artifacts
+            .iter()
+            .filter(|artifact| !artifact.healthy);
+        let mut count = 0;
+        for artifact in broken {
+            count += 1;
+            println!(
+                "{} zksolc {}: {} ({})",
+                platform.platform,
+                artifact.version,
+                style(artifact.error.as_deref().unwrap_or("unavailable")).red(),
+                artifact.url
+            );
+        }
+        println!(
+            "{}: {}/{} artifacts available",
+            platform.platform,
+            platform.artifacts.len() - count,
+            platform.artifacts.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_platforms() {
+        assert_eq!(parse_platforms("all"), Ok(Platforms::All));
+        assert_eq!(
+            parse_platforms("linux-amd64"),
+            Ok(Platforms::One(zksvm::Platform::LinuxAmd64))
+        );
+        assert!(parse_platforms("plan9").is_err());
+    }
+}
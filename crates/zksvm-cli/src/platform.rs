@@ -0,0 +1,19 @@
+use clap::Parser;
+
+/// Print the platform of this machine.
+#[derive(Clone, Debug, Parser)]
+pub struct PlatformCmd;
+
+impl PlatformCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let platform = zksvm::platform();
+        match zksvm::artifact_platform() {
+            Ok(artifacts) if artifacts == platform => println!("{platform}"),
+            Ok(artifacts) => {
+                println!("{platform} (using {artifacts} builds under emulation)")
+            }
+            Err(err) => println!("{platform} ({err})"),
+        }
+        Ok(())
+    }
+}
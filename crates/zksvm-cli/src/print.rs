@@ -0,0 +1,334 @@
+use console::style;
+use indicatif::{ProgressBar, ProgressStyle};
+use itertools::Itertools;
+use semver::Version;
+use std::{path::Path, time::Duration};
+
+pub fn global_version(state: &zksvm::GlobalVersionState) {
+    match state {
+        zksvm::GlobalVersionState::Active(v, _) => {
+            println!("{} (current)", style(v.to_string().as_str()).green());
+        }
+        zksvm::GlobalVersionState::Unset => {
+            println!("Global version not set");
+        }
+        zksvm::GlobalVersionState::Broken { version, reason } => {
+            println!(
+                "{}",
+                style(format!(
+                    "Global version {version} is {reason}; run `zksvm use <version>` to fix it"
+                ))
+                .red()
+            );
+        }
+    }
+}
+
+pub fn installed_versions(versions: Vec<Version>) {
+    println!("\n{}", style("Installed Versions").bold());
+    versions.iter().for_each(|v| {
+        println!("{}", style(v.to_string().as_str()).yellow());
+    });
+}
+
+pub fn installed_table(installed: &[zksvm::InstalledVersion]) {
+    println!("\n{}", style("Installed Versions").bold());
+    println!(
+        "{:<16}{:>12}  {:<22}{:<14}PATH",
+        "VERSION", "SIZE", "INSTALLED", "CHECKSUM"
+    );
+    for v in installed {
+        let installed_at = v
+            .installed_at
+            .map(|at| humantime::format_rfc3339_seconds(at).to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let checksum = match &v.checksum {
+            Some(zksvm::VerificationResult::Verified) => style("ok").green(),
+            Some(zksvm::VerificationResult::Mismatch { .. }) => style("mismatch").red(),
+            Some(zksvm::VerificationResult::Missing) => style("missing").red(),
+            Some(zksvm::VerificationResult::Unverifiable) => style("unverifiable").yellow(),
+            None => style("unknown").dim(),
+        };
+        println!(
+            "{:<16}{:>12}  {installed_at:<22}{:<14}{}",
+            style(v.version.to_string()).yellow(),
+            indicatif::HumanBytes(v.size).to_string(),
+            checksum,
+            v.path.display()
+        );
+    }
+}
+
+pub fn releases_table(title: &str, releases: &[zksvm::ReleaseDetails]) {
+    println!("\n{}", style(title).bold());
+    println!(
+        "{:<16}{:>12}  {:<11}{:<66}ARTIFACT",
+        "VERSION", "SIZE", "INSTALLED", "SHA256"
+    );
+    for release in releases {
+        let size = release
+            .size
+            .map(|size| indicatif::HumanBytes(size).to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let installed = if release.installed {
+            style("yes").green()
+        } else {
+            style("no").dim()
+        };
+        let sha256 = match &release.sha256 {
+            Some(sha256) => style(hex::encode(sha256)),
+            None => style("missing".to_string()).red(),
+        };
+        println!(
+            "{:<16}{size:>12}  {installed:<11}{sha256:<66}{}",
+            style(release.version.to_string()).yellow(),
+            release.artifact
+        );
+    }
+}
+
+pub fn available_versions(versions: Vec<Version>) {
+    println!("\n{}", style("Available to Install").bold());
+    let groups = versions
+        .iter()
+        .group_by(|v| v.minor)
+        .into_iter()
+        .map(|(_, g)| g.cloned().collect())
+        .collect::<Vec<Vec<Version>>>();
+    for group in groups {
+        println!(
+            "{:?}",
+            group.iter().map(|v| v.to_string()).collect::<Vec<String>>()
+        );
+    }
+}
+
+pub fn installing_version(tool: zksvm::Tool, version: &Version) -> ProgressBar {
+    spinner(format!("Downloading {tool} {version}"))
+}
+
+pub fn spinner(message: String) -> ProgressBar {
+    let spinner = ProgressBar::new_spinner();
+    spinner.enable_steady_tick(Duration::from_millis(120));
+    spinner.set_message(message);
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .tick_strings(&[
+                "☀️ ", "☀️ ", "☀️ ", "🌤 ", "⛅️ ", "🌥 ", "☁️ ", "🌧 ", "🌨 ", "🌧 ", "🌨 ", "🌧 ", "🌨 ",
+                "⛈ ", "🌨 ", "🌧 ", "🌨 ", "☁️ ", "🌥 ", "⛅️ ", "🌤 ", "☀️ ", "☀️ ",
+            ])
+            .template("{spinner:.green} {msg}")
+            .unwrap(),
+    );
+    spinner
+}
+
+pub fn install_event(spinner: &ProgressBar, event: &zksvm::InstallEvent) {
+    match event {
+        zksvm::InstallEvent::WaitingForLock {
+            version,
+            pid,
+            held_for,
+            ..
+        } => {
+            // both are recorded by the holder of the lock
+            let holder = match (pid, held_for) {
+                (Some(pid), Some(held_for)) => {
+                    format!(
+                        " (pid {pid}, for {})",
+                        humantime::format_duration(*held_for)
+                    )
+                }
+                _ => String::new(),
+            };
+            spinner.set_message(format!(
+                "Waiting for another install of zksolc {version}{holder}"
+            ))
+        }
+        zksvm::InstallEvent::LockAcquired { version, .. } => {
+            spinner.set_message(format!("Installing zksolc {version}"))
+        }
+        zksvm::InstallEvent::Downloading {
+            downloaded,
+            total: Some(total),
+        } if *total > 0 => {
+            if spinner.length() != Some(*total) {
+                spinner.set_length(*total);
+                spinner.set_style(
+                    ProgressStyle::default_bar()
+                        .template(
+                            "{msg} [{bar:30.green}] {percent}% ({bytes}/{total_bytes}, {eta})",
+                        )
+                        .unwrap()
+                        .progress_chars("=> "),
+                );
+            }
+            spinner.set_position(*downloaded);
+        }
+        zksvm::InstallEvent::Retrying {
+            attempt,
+            delay,
+            error,
+        } => spinner.println(format!(
+            "{}",
+            style(format!(
+                "Download attempt {attempt} failed: {error}; retrying in {}",
+                humantime::format_duration(Duration::from_millis(delay.as_millis() as u64))
+            ))
+            .yellow()
+        )),
+        zksvm::InstallEvent::Warning(warning) => {
+            spinner.println(format!("{}", style(format!("Warning: {warning}")).yellow()))
+        }
+        _ => {}
+    }
+}
+
+pub fn unknown_version(tool: zksvm::Tool, version: &Version) {
+    println!(
+        "{}",
+        style(format!(
+            "{tool} {version} is not a known release; run `zksvm list` to see the available versions"
+        ))
+        .red()
+    );
+}
+
+pub fn unsupported_version_range(version: &Version, supported: &semver::VersionReq) {
+    println!(
+        "{}",
+        style(format!(
+            "zksolc {version} is released, but zksvm can only install versions {supported}; pick a version in that range"
+        ))
+        .red()
+    );
+}
+
+pub fn set_global_version(version: &Version) {
+    ProgressBar::new_spinner().finish_with_message(format!("Global version set: {version}"));
+}
+
+pub fn set_project_version(version: &Version, path: &Path) {
+    ProgressBar::new_spinner().finish_with_message(format!(
+        "Project version set: {version} ({})",
+        path.display()
+    ));
+}
+
+pub fn removed_version(version: &Version, report: &zksvm::RemovalReport) {
+    println!("Removed zksolc {version} ({} items)", report.removed.len());
+    for lock in &report.kept_locks {
+        println!(
+            "{}",
+            style(format!(
+                "Kept {}: an install of zksolc {version} is in progress",
+                lock.display()
+            ))
+            .yellow()
+        );
+    }
+}
+
+pub fn pruned(report: &zksvm::PruneReport) {
+    println!(
+        "{}",
+        style(format!(
+            "Removed {} versions, reclaiming {}",
+            report.removed.len(),
+            indicatif::HumanBytes(report.reclaimed)
+        ))
+        .green()
+    );
+}
+
+pub fn cache_cleaned(report: &zksvm::CacheCleanReport) {
+    println!(
+        "{}",
+        style(format!(
+            "Removed {} cached downloads, reclaiming {}",
+            report.removed.len(),
+            indicatif::HumanBytes(report.reclaimed)
+        ))
+        .green()
+    );
+}
+
+pub fn verification(version: &Version, result: &zksvm::VerificationResult) {
+    match result {
+        zksvm::VerificationResult::Verified => {
+            println!("zksolc {version}: {}", style("ok").green())
+        }
+        zksvm::VerificationResult::Mismatch { expected, actual } => println!(
+            "zksolc {version}: {}",
+            style(format!(
+                "checksum mismatch, expected {expected} but the binary hashes to {actual}"
+            ))
+            .red()
+        ),
+        zksvm::VerificationResult::Missing => {
+            println!("zksolc {version}: {}", style("binary is missing").red())
+        }
+        zksvm::VerificationResult::Unverifiable => println!(
+            "zksolc {version}: {}",
+            style("no published checksum to compare against").yellow()
+        ),
+    }
+}
+
+pub fn interrupted(cleaned_up: bool) {
+    let message = if cleaned_up {
+        "Interrupted, partial downloads and locks were cleaned up"
+    } else {
+        "Interrupted while waiting for a lock held by another zksvm process"
+    };
+    eprintln!("{}", style(message).yellow());
+}
+
+/// Prints the error that failed a command, followed by a hint at how to resolve it if zksvm has
+/// one.
+pub fn error(err: &anyhow::Error) {
+    eprintln!("Error: {err:?}");
+    let hint = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<zksvm::SvmError>())
+        .and_then(zksvm::SvmError::hint);
+    if let Some(hint) = hint {
+        eprintln!("{}", style(format!("hint: {hint}")).cyan());
+    }
+}
+
+pub fn already_active(version: &Version) {
+    println!("zksolc {version} is already installed and set as the global version");
+}
+
+pub fn version_not_found(version: &Version) {
+    println!("{}", style(format!("Version: {version} not found")).red());
+}
+
+pub fn warning(warning: &zksvm::Warning) {
+    println!("{}", style(format!("Warning: {warning}")).yellow());
+}
+
+/// Warns once per data directory that zksolc builds of another platform are used.
+pub fn emulation_notice() {
+    let Ok(artifacts) = zksvm::artifact_platform() else {
+        return;
+    };
+    let Some(emulated) = zksvm::emulation_warning(artifacts) else {
+        return;
+    };
+    let marker = zksvm::data_dir().join(".emulation-notice");
+    if marker.exists() {
+        return;
+    }
+    println!(
+        "{}",
+        style(format!(
+            "Warning: {emulated}. Set ZKSVM_NO_EMULATION=1 to disable this."
+        ))
+        .yellow()
+    );
+    if zksvm::data_dir().is_dir() {
+        let _ = std::fs::write(marker, "");
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{print, utils};
+use clap::Parser;
+use semver::VersionReq;
+use std::time::{Duration, SystemTime};
+
+/// Remove all installed zksolc versions except the global one, to free up disk space.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct PruneCmd {
+    /// Also keep the installed versions matching this requirement, e.g. "^1.5".
+    #[clap(long, value_name = "RANGE")]
+    pub keep: Option<VersionReq>,
+
+    /// Only remove the versions not run for this long, e.g. "90d", as recorded once enabled with
+    /// `zksvm config set telemetry true`. Versions not run since then count as used when they
+    /// were installed.
+    #[clap(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub unused_for: Option<Duration>,
+
+    /// Also remove the cached downloads that no installed version uses anymore.
+    #[clap(long)]
+    pub cache: bool,
+}
+
+impl PruneCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let used_since = match self.unused_for {
+            Some(_) if !zksvm::telemetry_enabled() => anyhow::bail!(
+                "`--unused-for` needs usage stats; run `zksvm config set telemetry true` to record them"
+            ),
+            Some(unused_for) => Some((zksvm::usage_stats()?, SystemTime::now() - unused_for)),
+            None => None,
+        };
+        let keep = |version: &semver::Version| {
+            self.keep.as_ref().is_some_and(|keep| keep.matches(version))
+                || used_since
+                    .as_ref()
+                    .is_some_and(|(stats, since)| stats.used_since(version, *since))
+        };
+        let zksvm::LocalState { installed, global } = zksvm::LocalState::read()?;
+        let to_remove = installed
+            .iter()
+            .filter(|v| global.as_ref() != Some(*v) && !keep(v))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if to_remove.is_empty() {
+            println!("Nothing to prune");
+        } else {
+            println!("Versions to remove: {}", to_remove.join(", "));
+            if !utils::confirm("Are you sure?")? {
+                return Ok(());
+            }
+            let report = zksvm::prune(keep)?;
+            for (version, removal) in &report.removed {
+                print::removed_version(version, removal);
+            }
+            print::pruned(&report);
+        }
+        if self.cache {
+            print::cache_cleaned(&zksvm::clean_download_cache()?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_prune() {
+        assert_eq!(
+            PruneCmd::parse_from(["zksvm"]),
+            PruneCmd {
+                keep: None,
+                unused_for: None,
+                cache: false,
+            }
+        );
+        assert!(PruneCmd::parse_from(["zksvm", "--cache"]).cache);
+        let args = PruneCmd::parse_from(["zksvm", "--keep", "^1.5"]);
+        assert_eq!(args.keep, Some(VersionReq::parse("^1.5").unwrap()));
+        assert!(PruneCmd::try_parse_from(["zksvm", "--keep", "one"]).is_err());
+        let args = PruneCmd::parse_from(["zksvm", "--unused-for", "90d"]);
+        assert_eq!(
+            args.unused_for,
+            Some(Duration::from_secs(90 * 24 * 60 * 60))
+        );
+    }
+}
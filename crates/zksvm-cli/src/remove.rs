@@ -1,36 +1,36 @@
-use crate::print;
+use crate::{print, utils};
 use clap::Parser;
-use dialoguer::Input;
-use semver::Version;
 
 /// Remove a zksolc version, or "all" to remove all versions.
 #[derive(Clone, Debug, Parser)]
 pub struct RemoveCmd {
     /// zksolc version to remove, or "all" to remove all versions.
+    #[clap(add = clap_complete::ArgValueCandidates::new(|| {
+        let mut candidates = crate::completions::installed_versions();
+        candidates.push(clap_complete::CompletionCandidate::new("all"));
+        candidates
+    }))]
     pub version: String,
 }
 
 impl RemoveCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        if self.version.to_ascii_lowercase() == "all" {
-            for v in zksvm::installed_versions().unwrap_or_default() {
-                zksvm::remove_version(&v)?;
+        if self.version.eq_ignore_ascii_case("all") {
+            for v in zksvm::LocalState::read()?.installed {
+                print::removed_version(&v, &zksvm::remove_version(&v)?);
             }
             zksvm::unset_global_version()?;
             return Ok(());
         } else {
-            let mut installed_versions = zksvm::installed_versions().unwrap_or_default();
-            let current_version = zksvm::get_global_version()?;
-            let version = Version::parse(&self.version)?;
+            let zksvm::LocalState {
+                installed: mut installed_versions,
+                global: current_version,
+            } = zksvm::LocalState::read()?;
+            let version = utils::parse_exact_version(&self.version)?;
 
             if installed_versions.contains(&version) {
-                let input: String = Input::new()
-                    .with_prompt("Are you sure?")
-                    .with_initial_text("Y")
-                    .default("N".into())
-                    .interact_text()?;
-                if matches!(input.as_str(), "y" | "Y" | "yes" | "Yes") {
-                    zksvm::remove_version(&version)?;
+                if utils::confirm("Are you sure?")? {
+                    print::removed_version(&version, &zksvm::remove_version(&version)?);
                     if let Some(v) = current_version {
                         if version == v {
                             if let Some(i) = installed_versions.iter().position(|x| *x == v) {
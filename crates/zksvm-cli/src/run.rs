@@ -0,0 +1,52 @@
+use crate::{exec, utils};
+use clap::Parser;
+use semver::Version;
+use std::ffi::OsString;
+
+/// Run a zksolc version, installing it first if needed.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct RunCmd {
+    /// zksolc version to run, a requirement such as "^1.3", "latest" for the newest matching
+    /// release or "nightly" for the newest pre-release.
+    pub version: String,
+
+    /// Arguments passed to zksolc.
+    #[clap(last = true)]
+    pub args: Vec<OsString>,
+}
+
+impl RunCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let exact = Version::parse(&self.version)
+            .ok()
+            .or_else(|| zksvm::resolve_alias(&self.version));
+        let version = match exact {
+            Some(version) => version,
+            None => {
+                let all_versions = utils::versions_in(utils::channel_of(&self.version)).await?;
+                utils::parse_version(&self.version, &all_versions)?
+            }
+        };
+        if zksvm::install_status(&version)?.is_none() {
+            // stdout belongs to zksolc, e.g. for `--standard-json`
+            eprintln!("zksvm: installing zksolc {version}");
+        }
+        zksvm::ensure_installed(&version, &utils::install_options()?).await?;
+        let _ = zksvm::record_version_use(&version);
+
+        let code = exec::exec(&zksvm::version_binary(&version.to_string()), self.args)?;
+        std::process::exit(code);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwards_arguments() {
+        let cmd = RunCmd::parse_from(["run", "1.3.17", "--", "--standard-json", "-O3"]);
+        assert_eq!(cmd.version, "1.3.17");
+        assert_eq!(cmd.args, ["--standard-json", "-O3"]);
+    }
+}
@@ -0,0 +1,173 @@
+use clap::Parser;
+use semver::Version;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Search versions and artifact names by glob or regex.
+#[derive(Clone, Debug, Parser)]
+pub struct SearchCmd {
+    /// Glob pattern matched against version strings and artifact names, e.g. '1.3.*musl*'.
+    pub pattern: String,
+
+    /// Interpret the pattern as a regular expression instead of a glob.
+    #[clap(long)]
+    pub regex: bool,
+
+    /// Search the artifacts of this platform instead of the current one.
+    #[clap(long, value_name = "PLATFORM")]
+    pub platform: Option<zksvm::Platform>,
+
+    /// Print the matches as JSON.
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// A version matching a search.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SearchMatch {
+    pub version: Version,
+    /// The artifact name in the release index, if the version is listed there.
+    pub artifact: Option<String>,
+    pub installed: bool,
+}
+
+enum Matcher {
+    Glob(glob::Pattern),
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    fn new(pattern: &str, regex: bool) -> anyhow::Result<Self> {
+        Ok(if regex {
+            Self::Regex(regex::Regex::new(pattern)?)
+        } else {
+            Self::Glob(glob::Pattern::new(pattern)?)
+        })
+    }
+
+    fn is_match(&self, haystack: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => pattern.matches(haystack),
+            Self::Regex(regex) => regex.is_match(haystack),
+        }
+    }
+}
+
+impl SearchCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let matcher = Matcher::new(&self.pattern, self.regex)?;
+        let platform = match self.platform {
+            Some(platform) => platform,
+            None => zksvm::artifact_platform()?,
+        };
+        let releases = zksvm::all_releases(platform).await?;
+        let installed = zksvm::LocalState::read()?.installed;
+
+        let matches = search(&matcher, &releases.releases, &installed);
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&matches)?);
+            return Ok(());
+        }
+        if matches.is_empty() {
+            println!("No versions match {:?}", self.pattern);
+        }
+        for m in matches {
+            let artifact = m.artifact.as_deref().unwrap_or("-");
+            let installed = if m.installed { " (installed)" } else { "" };
+            println!("{:<10} {artifact}{installed}", m.version.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Matches the pattern against the indexed versions and artifacts and the installed versions.
+fn search(
+    matcher: &Matcher,
+    releases: &BTreeMap<Version, String>,
+    installed: &[Version],
+) -> Vec<SearchMatch> {
+    let mut matches = BTreeMap::new();
+    for (version, artifact) in releases {
+        if matcher.is_match(&version.to_string()) || matcher.is_match(artifact) {
+            matches.insert(version.clone(), Some(artifact.clone()));
+        }
+    }
+    for version in installed {
+        if matcher.is_match(&version.to_string()) {
+            matches
+                .entry(version.clone())
+                .or_insert_with(|| releases.get(version).cloned());
+        }
+    }
+    matches
+        .into_iter()
+        .map(|(version, artifact)| SearchMatch {
+            installed: installed.contains(&version),
+            version,
+            artifact,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn releases() -> BTreeMap<Version, String> {
+        BTreeMap::from([
+            (
+                Version::new(1, 3, 16),
+                "zksolc-linux-amd64-musl-v1.3.16".to_string(),
+            ),
+            (
+                Version::new(1, 3, 17),
+                "zksolc-linux-amd64-musl-v1.3.17".to_string(),
+            ),
+            (
+                Version::new(1, 4, 0),
+                "zksolc-linux-amd64-musl-v1.4.0".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn glob_matches_versions_and_artifacts() {
+        let matcher = Matcher::new("*v1.3.*musl*", false).unwrap();
+        assert!(search(&matcher, &releases(), &[]).is_empty());
+
+        let matcher = Matcher::new("*musl-v1.3.*", false).unwrap();
+        let matches = search(&matcher, &releases(), &[Version::new(1, 3, 17)]);
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.version.to_string(), m.installed))
+                .collect::<Vec<_>>(),
+            [("1.3.16".to_string(), false), ("1.3.17".to_string(), true)]
+        );
+
+        let matcher = Matcher::new("1.4.*", false).unwrap();
+        assert_eq!(search(&matcher, &releases(), &[]).len(), 1);
+    }
+
+    #[test]
+    fn regex_includes_installed_only_versions() {
+        let matcher = Matcher::new(r"^1\.2\.", true).unwrap();
+        let matches = search(&matcher, &releases(), &[Version::new(1, 2, 0)]);
+        assert_eq!(
+            matches,
+            [SearchMatch {
+                version: Version::new(1, 2, 0),
+                artifact: None,
+                installed: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_search() {
+        let args = SearchCmd::parse_from(["search", "1.3.*", "--regex", "--json"]);
+        assert_eq!(args.pattern, "1.3.*");
+        assert!(args.regex && args.json);
+        assert!(args.platform.is_none());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{print, utils};
+use clap::{Parser, Subcommand};
+use semver::Version;
+
+/// Manage zksvm itself.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct SelfCmd {
+    #[clap(subcommand)]
+    pub cmd: SelfSubcommand,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Subcommand)]
+pub enum SelfSubcommand {
+    /// Replace this zksvm binary with the latest release, after verifying its checksum.
+    Update {
+        /// Only print whether a newer release is available.
+        #[clap(long)]
+        check: bool,
+    },
+}
+
+impl SelfCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let SelfSubcommand::Update { check } = self.cmd;
+        let exe = std::env::current_exe()?;
+        zksvm::remove_replaced_exe(&exe);
+
+        let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+        let Some(update) = zksvm::check_self_update(&current).await? else {
+            println!("zksvm {current} is the latest release");
+            return Ok(());
+        };
+        if check {
+            println!("zksvm {} is available, you have {current}", update.version);
+            return Ok(());
+        }
+        if !utils::confirm(&format!(
+            "Replace {} with zksvm {}?",
+            exe.display(),
+            update.version
+        ))? {
+            return Ok(());
+        }
+
+        let spinner = print::spinner(format!("Downloading {}", update.asset));
+        zksvm::self_update(&update, &exe, &utils::install_options()?).await?;
+        spinner.finish_with_message(format!(
+            "Updated zksvm from {current} to {}",
+            update.version
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_self_update() {
+        let args = SelfCmd::parse_from(["zksvm", "update", "--check"]);
+        assert_eq!(args.cmd, SelfSubcommand::Update { check: true });
+        assert!(SelfCmd::try_parse_from(["zksvm"]).is_err());
+    }
+}
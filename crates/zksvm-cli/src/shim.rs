@@ -0,0 +1,96 @@
+//! Running zksvm as a `zksolc` front-end, when invoked through a link named `zksolc`.
+
+use crate::{exec, utils};
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Returns whether the binary was invoked under the name `zksolc`, e.g. through a symlink.
+pub fn invoked_as_zksolc(arg0: &OsString) -> bool {
+    Path::new(arg0)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem.eq_ignore_ascii_case("zksolc"))
+}
+
+/// Resolves the active zksolc version, installing it if the project policy allows, and runs it
+/// with `args`.
+pub async fn run(args: Vec<OsString>) -> anyhow::Result<i32> {
+    let workspace = zksvm::Workspace::load(std::env::current_dir()?)?;
+    let Some(active) = workspace.active_version()? else {
+        anyhow::bail!(
+            "no zksolc version is selected; pin one in {}, set ${} or run `zksvm use <version>`",
+            zksvm::PROJECT_VERSION_FILE,
+            zksvm::ZKSVM_ZKSOLC_VERSION
+        );
+    };
+
+    let version = active.version;
+    let bin = zksvm::version_binary(&version.to_string());
+    if !bin.exists() {
+        if !workspace.policy.auto_install {
+            return Err(zksvm::SvmError::VersionNotInstalled(version.to_string()).into());
+        }
+        eprintln!("zksvm: installing zksolc {version}");
+        zksvm::install_with_options(&version, &utils::install_options()?).await?;
+    }
+
+    let _ = zksvm::record_version_use(&version);
+    exec::exec(&bin, args)
+}
+
+/// Installs a `zksolc` shim into `bin_dir`, linking to the running zksvm binary, and returns its
+/// path.
+///
+/// An existing shim is replaced. On Windows, where symlinks need extra privileges, the shim is a
+/// hard link or, across file systems, a copy.
+pub fn install(bin_dir: &Path) -> anyhow::Result<PathBuf> {
+    let zksvm = std::env::current_exe()?;
+    fs::create_dir_all(bin_dir)?;
+    let shim = bin_dir.join(format!("zksolc{}", std::env::consts::EXE_SUFFIX));
+    match fs::remove_file(&shim) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+        _ => {}
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&zksvm, &shim)?;
+    #[cfg(not(unix))]
+    if fs::hard_link(&zksvm, &shim).is_err() {
+        fs::copy(&zksvm, &shim)?;
+    }
+    Ok(shim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_zksolc_name() {
+        assert!(invoked_as_zksolc(&"zksolc".into()));
+        assert!(invoked_as_zksolc(&"/usr/local/bin/zksolc".into()));
+        #[cfg(windows)]
+        assert!(invoked_as_zksolc(&"C:\\bin\\zksolc.exe".into()));
+        assert!(!invoked_as_zksolc(&"zksvm".into()));
+        assert!(!invoked_as_zksolc(&"zksolc-1.3.17".into()));
+    }
+
+    #[test]
+    fn installs_shim() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin_dir = dir.path().join("bin");
+        let shim = install(&bin_dir).unwrap();
+        assert_eq!(shim.parent(), Some(bin_dir.as_path()));
+        assert!(invoked_as_zksolc(&shim.clone().into_os_string()));
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(&shim).unwrap(),
+            std::env::current_exe().unwrap()
+        );
+
+        // installing again replaces the shim
+        assert_eq!(install(&bin_dir).unwrap(), shim);
+    }
+}
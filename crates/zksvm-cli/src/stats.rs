@@ -0,0 +1,106 @@
+use clap::Parser;
+use console::style;
+use itertools::Itertools;
+
+/// Show which zksolc versions and commands were used most, as recorded locally once enabled with
+/// `zksvm config set telemetry true`. Nothing is sent anywhere.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct StatsCmd {
+    /// Print the stats as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Remove the recorded stats.
+    #[clap(long, conflicts_with = "json")]
+    pub clear: bool,
+}
+
+impl StatsCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        if self.clear {
+            zksvm::clear_usage_stats()?;
+            println!("Cleared the usage stats");
+            return Ok(());
+        }
+        let stats = zksvm::usage_stats()?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+            return Ok(());
+        }
+        if !zksvm::telemetry_enabled() {
+            println!(
+                "Usage stats are not recorded; run `zksvm config set telemetry true` to start"
+            );
+        }
+        print!("{}", render_stats(&stats));
+        Ok(())
+    }
+}
+
+/// Renders the versions and commands of `stats`, most used first.
+fn render_stats(stats: &zksvm::UsageStats) -> String {
+    let mut out = format!(
+        "\n{}\n{:<16}{:>8}  LAST USED\n",
+        style("Versions").bold(),
+        "VERSION",
+        "RUNS"
+    );
+    let versions = stats
+        .versions
+        .iter()
+        .sorted_by(|(a, a_usage), (b, b_usage)| b_usage.runs.cmp(&a_usage.runs).then(b.cmp(a)));
+    for (version, usage) in versions {
+        out += &format!(
+            "{:<16}{:>8}  {}\n",
+            style(version.to_string()).yellow(),
+            usage.runs,
+            humantime::format_rfc3339_seconds(usage.last_used)
+        );
+    }
+    out += &format!(
+        "\n{}\n{:<16}{:>8}\n",
+        style("Commands").bold(),
+        "COMMAND",
+        "RUNS"
+    );
+    let commands = stats
+        .commands
+        .iter()
+        .sorted_by(|(a, a_runs), (b, b_runs)| b_runs.cmp(a_runs).then(a.cmp(b)));
+    for (command, runs) in commands {
+        out += &format!("{command:<16}{runs:>8}\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn parse_stats() {
+        assert!(StatsCmd::parse_from(["zksvm", "--clear"]).clear);
+        assert!(StatsCmd::try_parse_from(["zksvm", "--clear", "--json"]).is_err());
+    }
+
+    #[test]
+    fn renders_most_used_first() {
+        let used = |runs| zksvm::VersionUsage {
+            runs,
+            last_used: SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_564_800),
+        };
+        let stats = zksvm::UsageStats {
+            commands: [("list".to_string(), 1), ("use".to_string(), 3)].into(),
+            versions: [
+                (Version::new(1, 5, 6), used(2)),
+                (Version::new(1, 5, 7), used(5)),
+            ]
+            .into(),
+        };
+        let rendered = console::strip_ansi_codes(&render_stats(&stats)).into_owned();
+        assert!(rendered.contains("1.5.7                  5  2024-05-01T12:00:00Z\n1.5.6"));
+        assert!(rendered.contains("use                    3\nlist                   1\n"));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{print, utils};
+use clap::Parser;
+use semver::Version;
+
+/// Install the newest zksolc release, if it is not installed yet.
+#[derive(Clone, Debug, Parser)]
+pub struct UpdateCmd {
+    /// Also set the newest release as the global default.
+    #[clap(long = "use")]
+    pub use_: bool,
+}
+
+impl UpdateCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let releases = zksvm::all_releases(zksvm::artifact_platform()?).await?;
+        let Some(newest) = newest_installable(&releases) else {
+            anyhow::bail!(
+                "no release in the supported range {} is available",
+                releases.supported_versions()
+            );
+        };
+        let local = zksvm::LocalState::read()?;
+
+        if local.is_installed(&newest) {
+            println!("zksolc {newest} is the newest release and already installed");
+        } else {
+            if let Some(installed) = local.installed.iter().max() {
+                println!("Updating from zksolc {installed} to {newest}");
+            }
+            let spinner = print::installing_version(zksvm::Tool::Zksolc, &newest);
+            zksvm::install_with_events(&newest, &utils::install_options()?, |event| {
+                print::install_event(&spinner, &event)
+            })
+            .await?;
+            spinner.finish_with_message(format!("Downloaded zksolc: {newest}"));
+        }
+
+        if local.global.as_ref() != Some(&newest) && (self.use_ || local.global.is_none()) {
+            zksvm::set_global_version(&newest)?;
+            print::set_global_version(&newest);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the newest stable release of `releases` that can be installed from them.
+fn newest_installable(releases: &zksvm::Releases) -> Option<Version> {
+    let all_versions = releases.clone().into_versions();
+    zksvm::best_matching_version(&releases.supported_versions(), &all_versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newest_installable_respects_supported_range() {
+        let mut releases = zksvm::Releases::default();
+        releases.releases = [
+            Version::new(1, 3, 5),
+            Version::new(1, 4, 1),
+            Version::new(9, 0, 0),
+            Version::parse("9.0.1-beta.1").unwrap(),
+        ]
+        .into_iter()
+        .map(|version| (version, String::new()))
+        .collect();
+        // releases newer than this version of zksvm are installable as soon as they are listed
+        assert_eq!(newest_installable(&releases), Some(Version::new(9, 0, 0)));
+
+        releases.min_version = Some(Version::new(10, 0, 0));
+        assert_eq!(newest_installable(&releases), None);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{install::InstallArgs, print, utils};
+use clap::Parser;
+
+/// Set a zksolc version as the global default, or pin it for the current project.
+#[derive(Clone, Debug, Parser)]
+pub struct UseCmd {
+    /// zksolc version to use, a requirement such as "^1.3", "latest" for the newest matching
+    /// release or "nightly" for the newest pre-release.
+    #[clap(add = clap_complete::ArgValueCandidates::new(crate::completions::installed_versions))]
+    pub version: String,
+
+    /// Pin the version for the project in the current directory by writing `.zksolc-version`,
+    /// instead of changing the global default.
+    #[clap(long)]
+    pub local: bool,
+
+    #[clap(flatten)]
+    pub install: InstallArgs,
+}
+
+impl UseCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let releases = utils::releases_in(utils::channel_of(&self.version)).await?;
+        let all_versions = releases.clone().into_versions();
+        let version = utils::parse_version(&self.version, &all_versions)?;
+        let local = zksvm::LocalState::read()?;
+
+        let installable = all_versions.contains(&version) && releases.is_supported(&version);
+        if self.local && (local.is_installed(&version) || installable) {
+            let path = zksvm::set_project_version(std::env::current_dir()?, &version)?;
+            print::set_project_version(&version, &path);
+        }
+
+        if local.is_installed(&version) {
+            if !self.local {
+                zksvm::set_global_version(&version)?;
+                print::set_global_version(&version);
+            }
+        } else if !all_versions.contains(&version) {
+            print::unknown_version(zksvm::Tool::Zksolc, &version);
+        } else if !releases.is_supported(&version) {
+            print::unsupported_version_range(&version, &releases.supported_versions());
+        } else {
+            println!("zksolc {version} is not installed");
+            if utils::confirm("Would you like to install it?")? {
+                let spinner = print::installing_version(zksvm::Tool::Zksolc, &version);
+                let opts = self.install.options(&zksvm::Config::load()?.install)?;
+                zksvm::install_with_events(&version, &opts, |event| {
+                    print::install_event(&spinner, &event)
+                })
+                .await?;
+                spinner.finish_with_message(format!("Downloaded zksolc: {version}"));
+                if local.global.is_none() && !self.local {
+                    zksvm::set_global_version(&version)?;
+                    print::set_global_version(&version);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use semver::{Version, VersionReq};
+use std::{
+    future::Future,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Disables prompts when set to anything but an empty string or `0`.
+#[cfg(feature = "interactive")]
+pub const ZKSVM_NONINTERACTIVE: &str = "ZKSVM_NONINTERACTIVE";
+
+/// Whether `--yes` was passed.
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Answers all prompts with yes, for `--yes`.
+pub fn assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+/// Asks a yes or no question. When there is no one to ask, because stdin is not a terminal or
+/// `$ZKSVM_NONINTERACTIVE` is set, it is answered like [`unattended`].
+#[cfg(feature = "interactive")]
+pub fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    use std::io::IsTerminal;
+
+    if !interactive(
+        ASSUME_YES.load(Ordering::Relaxed),
+        std::env::var_os(ZKSVM_NONINTERACTIVE),
+        std::io::stdin().is_terminal(),
+    ) {
+        return Ok(unattended(prompt));
+    }
+    let input: String = dialoguer::Input::new()
+        .with_prompt(prompt)
+        .with_initial_text("Y")
+        .default("N".into())
+        .interact_text()?;
+    Ok(matches!(input.as_str(), "y" | "Y" | "yes" | "Yes"))
+}
+
+/// Answers a yes or no question like [`unattended`], as prompts are compiled out.
+#[cfg(not(feature = "interactive"))]
+pub fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    Ok(unattended(prompt))
+}
+
+/// Answers a yes or no question without asking: yes with `--yes` and no otherwise, so nothing is
+/// changed without consent.
+fn unattended(prompt: &str) -> bool {
+    let yes = ASSUME_YES.load(Ordering::Relaxed);
+    if yes {
+        println!("{prompt} yes");
+    } else {
+        println!("{prompt} no (pass --yes to answer yes)");
+    }
+    yes
+}
+
+#[cfg(feature = "interactive")]
+fn interactive(
+    assume_yes: bool,
+    noninteractive: Option<std::ffi::OsString>,
+    terminal: bool,
+) -> bool {
+    terminal && !assume_yes && noninteractive.map_or(true, |var| var.is_empty() || var == "0")
+}
+
+/// Runs the `future` in a new [`tokio::runtime::Runtime`]
+#[allow(unused)]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let rt = tokio::runtime::Runtime::new().expect("could not start tokio rt");
+    rt.block_on(future)
+}
+
+/// Returns the channel a version argument refers to: pre-releases for `nightly` and pre-release
+/// versions such as `1.4.2-beta.1`, stable releases otherwise.
+pub fn channel_of(version: &str) -> zksvm::Channel {
+    if version.eq_ignore_ascii_case("nightly") {
+        return zksvm::Channel::Prerelease;
+    }
+    Version::parse(version).map_or(zksvm::Channel::Stable, |version| {
+        zksvm::Channel::of(&version)
+    })
+}
+
+/// Fetches the release list of `channel` for this machine.
+pub async fn releases_in(channel: zksvm::Channel) -> anyhow::Result<zksvm::Releases> {
+    Ok(zksvm::all_releases_in(zksvm::artifact_platform()?, channel).await?)
+}
+
+/// Fetches the sorted versions of `channel` available for this machine.
+pub async fn versions_in(channel: zksvm::Channel) -> anyhow::Result<Vec<Version>> {
+    Ok(releases_in(channel).await?.into_versions())
+}
+
+/// Parses a version argument, resolving aliases to their version, and `latest`, `nightly` and
+/// requirements such as `^1.3` to the newest matching version of `all_versions`.
+pub fn parse_version(version: &str, all_versions: &[Version]) -> anyhow::Result<Version> {
+    if let Some(aliased) = zksvm::resolve_alias(version) {
+        return Ok(aliased);
+    }
+    if version.eq_ignore_ascii_case("nightly") {
+        return all_versions
+            .iter()
+            .filter(|version| !version.pre.is_empty())
+            .max()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no zksolc pre-releases are available"));
+    }
+    if version.eq_ignore_ascii_case("latest") {
+        return all_versions
+            .iter()
+            .max()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no zksolc releases are available"));
+    }
+    if let Ok(version) = Version::parse(version) {
+        return Ok(version);
+    }
+    let req = VersionReq::parse(version)?;
+    Ok(zksvm::best_matching_version(&req, all_versions)
+        .ok_or(zksvm::SvmError::NoMatchingVersion(req.to_string()))?)
+}
+
+/// Parses a version argument naming exactly one version, or an alias of one.
+pub fn parse_exact_version(version: &str) -> anyhow::Result<Version> {
+    if let Some(aliased) = zksvm::resolve_alias(version) {
+        return Ok(aliased);
+    }
+    Ok(Version::parse(version)?)
+}
+
+/// Returns the install options of commands without install flags, with the `[install]` settings
+/// of the config file applied.
+pub fn install_options() -> anyhow::Result<zksvm::InstallOptions> {
+    Ok(zksvm::Config::load()?.install.apply(Default::default()))
+}
+
+/// Returns the verifier for `--signature-key`, a minisign public key or the path to a `.pub`
+/// file, falling back to `gpg` if signatures are required without a key.
+pub fn signature_verifier(
+    key: Option<&str>,
+    require_signature: bool,
+) -> anyhow::Result<Option<zksvm::Verifier>> {
+    let Some(key) = key else {
+        return Ok(require_signature.then(|| zksvm::Verifier::new(zksvm::CommandVerifier::gpg())));
+    };
+    let key = match std::fs::read_to_string(key) {
+        Ok(contents) => contents,
+        Err(_) => key.to_string(),
+    };
+    Ok(Some(zksvm::Verifier::new(
+        zksvm::MinisignVerifier::from_public_key(&key)?,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_latest() {
+        let all_versions = [
+            Version::new(1, 3, 17),
+            Version::new(1, 4, 1),
+            Version::new(1, 3, 9),
+        ];
+        assert_eq!(
+            parse_version("latest", &all_versions).unwrap(),
+            Version::new(1, 4, 1)
+        );
+        assert_eq!(
+            parse_version("1.3.9", &all_versions).unwrap(),
+            Version::new(1, 3, 9)
+        );
+        assert!(parse_version("latest", &[]).is_err());
+        assert!(parse_version("newest", &all_versions).is_err());
+        assert!(parse_version("nightly", &all_versions).is_err());
+    }
+
+    #[test]
+    fn parse_requirement() {
+        let all_versions = [
+            Version::new(1, 3, 17),
+            Version::new(1, 3, 22),
+            Version::new(1, 4, 1),
+        ];
+        assert_eq!(
+            parse_version("~1.3", &all_versions).unwrap(),
+            Version::new(1, 3, 22)
+        );
+        assert_eq!(
+            parse_version(">=1.3.17,<1.4", &all_versions).unwrap(),
+            Version::new(1, 3, 22)
+        );
+        assert!(parse_version("^2", &all_versions).is_err());
+    }
+
+    #[test]
+    fn parse_nightly() {
+        let beta = Version::parse("1.4.2-beta.1").unwrap();
+        let all_versions = [
+            Version::new(1, 4, 1),
+            Version::parse("1.4.2-beta.0").unwrap(),
+            beta.clone(),
+        ];
+        assert_eq!(parse_version("nightly", &all_versions).unwrap(), beta);
+        assert_eq!(channel_of("nightly"), zksvm::Channel::Prerelease);
+        assert_eq!(channel_of("1.4.2-beta.1"), zksvm::Channel::Prerelease);
+        assert_eq!(channel_of("1.4.1"), zksvm::Channel::Stable);
+        assert_eq!(channel_of("latest"), zksvm::Channel::Stable);
+    }
+
+    #[test]
+    fn signature_verifier_defaults_to_gpg() {
+        assert!(signature_verifier(None, false).unwrap().is_none());
+        let gpg = signature_verifier(None, true).unwrap().unwrap();
+        assert_eq!(gpg.extension(), ".asc");
+        assert!(signature_verifier(Some("not a key"), false).is_err());
+    }
+
+    #[test]
+    fn answers_no_unattended() {
+        assert!(!unattended("Are you sure?"));
+        assume_yes(true);
+        assert!(unattended("Are you sure?"));
+        assume_yes(false);
+    }
+
+    #[test]
+    #[cfg(feature = "interactive")]
+    fn prompts_only_on_terminals() {
+        assert!(interactive(false, None, true));
+        assert!(interactive(false, Some("".into()), true));
+        assert!(interactive(false, Some("0".into()), true));
+        assert!(!interactive(false, None, false));
+        assert!(!interactive(true, None, true));
+        assert!(!interactive(false, Some("1".into()), true));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{print, utils};
+use clap::Parser;
+use semver::Version;
+
+/// Check installed zksolc binaries against the published checksums.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyCmd {
+    /// zksolc versions to check. Defaults to all installed versions.
+    pub versions: Vec<Version>,
+
+    /// Download corrupted or missing binaries again.
+    #[clap(long)]
+    pub repair: bool,
+}
+
+impl VerifyCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let versions = if self.versions.is_empty() {
+            zksvm::LocalState::read()?.installed
+        } else {
+            self.versions
+        };
+        if versions.is_empty() {
+            println!("No zksolc versions are installed");
+            return Ok(());
+        }
+
+        let mut broken = vec![];
+        for version in versions {
+            let result = zksvm::verify_installed(&version).await?;
+            print::verification(&version, &result);
+            if !result.needs_repair() {
+                continue;
+            }
+            if !self.repair {
+                broken.push(version);
+                continue;
+            }
+            let spinner = print::installing_version(zksvm::Tool::Zksolc, &version);
+            zksvm::install_with_events(&version, &utils::install_options()?, |event| {
+                print::install_event(&spinner, &event)
+            })
+            .await?;
+            spinner.finish_with_message(format!("Repaired zksolc: {version}"));
+        }
+
+        if !broken.is_empty() {
+            anyhow::bail!(
+                "{} installed versions failed verification; run `zksvm verify --repair` to download them again",
+                broken.len()
+            );
+        }
+        Ok(())
+    }
+}
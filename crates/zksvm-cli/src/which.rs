@@ -0,0 +1,187 @@
+use clap::Parser;
+use std::path::PathBuf;
+use zksvm::{ActiveVersion, VersionSource, Workspace};
+
+/// Print the path of the zksolc binary used in the current directory.
+#[derive(Clone, Debug, Parser)]
+pub struct WhichCmd {
+    /// Show how the version was selected, including overridden pins.
+    #[clap(long)]
+    pub explain: bool,
+}
+
+impl WhichCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let workspace = Workspace::load(std::env::current_dir()?)?;
+        let active = workspace.active_version()?;
+
+        if self.explain {
+            let env = std::env::var(zksvm::ZKSVM_ZKSOLC_VERSION).ok();
+            let global = zksvm::global_version_state()?;
+            for line in explain(&workspace, active.as_ref(), env.as_deref(), &global) {
+                println!("{line}");
+            }
+            return Ok(());
+        }
+
+        let Some(active) = active else {
+            if let zksvm::GlobalVersionState::Broken { version, reason } =
+                zksvm::global_version_state()?
+            {
+                anyhow::bail!(
+                    "the global version {version} is {reason}; run `zksvm use <version>` to fix it"
+                );
+            }
+            anyhow::bail!(
+                "no zksolc version is selected; pin one in {}, set ${} or run `zksvm use <version>`",
+                zksvm::PROJECT_VERSION_FILE,
+                zksvm::ZKSVM_ZKSOLC_VERSION
+            );
+        };
+        println!(
+            "{}",
+            binary(&active, &zksvm::LocalState::read()?)?.display()
+        );
+        Ok(())
+    }
+}
+
+/// Returns the binary of the active version, failing if it is not installed so that scripts
+/// never receive a path that does not exist.
+fn binary(active: &ActiveVersion, local: &zksvm::LocalState) -> anyhow::Result<PathBuf> {
+    let version = &active.version;
+    if !local.is_installed(version) {
+        let source = match &active.source {
+            VersionSource::Project(root) => format!("the project at {}", root.display()),
+            VersionSource::Env => format!("${}", zksvm::ZKSVM_ZKSOLC_VERSION),
+            VersionSource::Global => "the global version".to_string(),
+        };
+        anyhow::bail!(
+            "zksolc {version} is selected by {source} but not installed; run `zksvm install {version}`"
+        );
+    }
+    Ok(zksvm::version_binary(&version.to_string()))
+}
+
+/// Describes every place a version could be selected from, nearest first, marking the one used.
+fn explain(
+    workspace: &Workspace,
+    active: Option<&ActiveVersion>,
+    env: Option<&str>,
+    global: &zksvm::GlobalVersionState,
+) -> Vec<String> {
+    let mut lines = vec![];
+    let mut selected = false;
+    let mut entry = |source: String, setting: String, selects: bool| {
+        let marker = if selects && !selected {
+            selected = true;
+            "*"
+        } else {
+            " "
+        };
+        lines.push(format!("{marker} {source:<50} {setting}"));
+    };
+
+    for file in &workspace.files {
+        let mut settings = vec![];
+        if let Some(zksolc) = &file.zksolc {
+            settings.push(format!("zksolc {zksolc}"));
+        }
+        if let Some(solc) = &file.solc {
+            settings.push(format!("solc {solc}"));
+        }
+        if settings.is_empty() {
+            settings.push("no pins".to_string());
+        }
+        entry(
+            file.path.display().to_string(),
+            settings.join(", "),
+            file.zksolc.is_some(),
+        );
+    }
+    entry(
+        format!("${}", zksvm::ZKSVM_ZKSOLC_VERSION),
+        env.unwrap_or("unset").to_string(),
+        matches!(active, Some(a) if a.source == VersionSource::Env),
+    );
+    let global = match global {
+        zksvm::GlobalVersionState::Unset => "unset".to_string(),
+        zksvm::GlobalVersionState::Active(version, _) => version.to_string(),
+        zksvm::GlobalVersionState::Broken { version, reason } => {
+            format!("{version} ({reason})")
+        }
+    };
+    entry(
+        "global".to_string(),
+        global,
+        matches!(active, Some(a) if a.source == VersionSource::Global),
+    );
+    match active {
+        Some(active) => lines.push(format!(
+            "\nUsing zksolc {} at {}",
+            active.version,
+            zksvm::version_binary(&active.version.to_string()).display()
+        )),
+        None => lines.push("\nNo zksolc version is selected".to_string()),
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+
+    #[test]
+    fn explain_marks_nearest_pin() {
+        let workspace = Workspace {
+            root: PathBuf::from("/repo/packages/token"),
+            zksolc: Some(Version::new(1, 4, 0)),
+            solc: None,
+            policy: Default::default(),
+            files: vec![
+                zksvm::ProjectFile {
+                    path: PathBuf::from("/repo/packages/token/zksvm.toml"),
+                    zksolc: Some(Version::new(1, 4, 0)),
+                    solc: None,
+                },
+                zksvm::ProjectFile {
+                    path: PathBuf::from("/repo/.zksolc-version"),
+                    zksolc: Some(Version::new(1, 3, 17)),
+                    solc: None,
+                },
+            ],
+        };
+        let active = workspace.active_version().unwrap();
+        let global = zksvm::GlobalVersionState::Broken {
+            version: "1.3.9".to_string(),
+            reason: zksvm::BrokenGlobalVersion::NotInstalled,
+        };
+        let lines = explain(&workspace, active.as_ref(), None, &global);
+        assert!(lines[0].starts_with("* /repo/packages/token/zksvm.toml"));
+        assert!(lines[0].ends_with("zksolc 1.4.0"));
+        assert!(lines[1].starts_with("  /repo/.zksolc-version"));
+        assert!(lines[3].starts_with("  global"));
+        assert!(lines[3].ends_with("1.3.9 (not installed)"));
+        assert!(lines[4].contains("Using zksolc 1.4.0"));
+    }
+
+    #[test]
+    fn binary_requires_install() {
+        let active = ActiveVersion {
+            version: Version::new(1, 3, 17),
+            source: VersionSource::Env,
+        };
+        let err = binary(&active, &zksvm::LocalState::default()).unwrap_err();
+        assert!(err.to_string().contains("$ZKSVM_ZKSOLC_VERSION"));
+
+        let local = zksvm::LocalState {
+            installed: vec![Version::new(1, 3, 17)],
+            global: None,
+        };
+        assert_eq!(
+            binary(&active, &local).unwrap(),
+            zksvm::version_binary("1.3.17")
+        );
+    }
+}
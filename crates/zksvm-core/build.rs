@@ -0,0 +1,7 @@
+fn main() {
+    // expose the target triple for `zksvm::build_info`
+    println!(
+        "cargo:rustc-env=ZKSVM_TARGET={}",
+        std::env::var("TARGET").unwrap()
+    );
+}
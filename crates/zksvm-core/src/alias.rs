@@ -0,0 +1,123 @@
+use crate::{aliases_path, data_dir, setup_data_dir, state, SvmError};
+use semver::{Version, VersionReq};
+use std::{collections::BTreeMap, fs, io};
+
+/// Names that already mean something wherever a version is accepted.
+const RESERVED_NAMES: &[&str] = &["latest", "nightly", "default", "system"];
+
+/// Returns the versions of all aliases, by name.
+///
+/// Aliases are kept in [`aliases_path`], a TOML table mapping each name to its version.
+pub fn list_aliases() -> Result<BTreeMap<String, Version>, SvmError> {
+    let _lock = state::lock_data_dir(false)?;
+    read_aliases()
+}
+
+/// Returns the version `name` is an alias of, if it is one.
+pub fn resolve_alias(name: &str) -> Option<Version> {
+    list_aliases().ok()?.remove(name)
+}
+
+/// Makes `name` an alias of `version`, returning the version it was an alias of before, if any.
+///
+/// Fails with [`SvmError::InvalidAlias`] unless `name` is a word that cannot be mistaken for a
+/// version or requirement, like `stable` or `ci-pinned`.
+pub fn set_alias(name: &str, version: &Version) -> Result<Option<Version>, SvmError> {
+    check_alias_name(name)?;
+    setup_data_dir()?;
+    let _lock = state::lock_data_dir(true)?;
+    let mut aliases = read_aliases()?;
+    let previous = aliases.insert(name.to_string(), version.clone());
+    write_aliases(&aliases)?;
+    Ok(previous)
+}
+
+/// Removes the alias `name`, returning the version it was an alias of, if it existed.
+pub fn remove_alias(name: &str) -> Result<Option<Version>, SvmError> {
+    if !data_dir().exists() {
+        return Ok(None);
+    }
+    let _lock = state::lock_data_dir(true)?;
+    let mut aliases = read_aliases()?;
+    let removed = aliases.remove(name);
+    if removed.is_some() {
+        write_aliases(&aliases)?;
+    }
+    Ok(removed)
+}
+
+fn read_aliases() -> Result<BTreeMap<String, Version>, SvmError> {
+    let path = aliases_path();
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(err.into()),
+    };
+    toml::from_str(&contents).map_err(|err| SvmError::InvalidAliases(path, err.to_string()))
+}
+
+fn write_aliases(aliases: &BTreeMap<String, Version>) -> Result<(), SvmError> {
+    let contents = toml::to_string(aliases)
+        .map_err(|err| SvmError::InvalidAliases(aliases_path(), err.to_string()))?;
+    state::write_state_file(&aliases_path(), &contents)
+}
+
+/// Checks that `name` can be told apart from versions and requirements wherever it is used.
+fn check_alias_name(name: &str) -> Result<(), SvmError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        && !RESERVED_NAMES.contains(&name.to_ascii_lowercase().as_str())
+        && VersionReq::parse(name).is_err();
+    if valid {
+        Ok(())
+    } else {
+        Err(SvmError::InvalidAlias(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[serial_test::serial]
+    fn alias_lifecycle() {
+        let (old, new) = (Version::new(1, 3, 16), Version::new(1, 3, 17));
+        assert_eq!(resolve_alias("stable"), None);
+
+        assert_eq!(set_alias("stable", &old).unwrap(), None);
+        assert_eq!(set_alias("stable", &new).unwrap(), Some(old));
+        set_alias("ci_pinned", &new).unwrap();
+        assert_eq!(resolve_alias("stable"), Some(new.clone()));
+        assert_eq!(
+            list_aliases().unwrap().keys().collect::<Vec<_>>(),
+            ["ci_pinned", "stable"]
+        );
+        assert_eq!(
+            state::verify_state_file(&aliases_path()).unwrap(),
+            state::Integrity::Valid
+        );
+
+        assert_eq!(remove_alias("stable").unwrap(), Some(new.clone()));
+        assert_eq!(remove_alias("stable").unwrap(), None);
+        assert_eq!(remove_alias("ci_pinned").unwrap(), Some(new));
+        assert!(list_aliases().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_ambiguous_names() {
+        for name in [
+            "", "1.3.17", "^1.3", "x", "*", "latest", "Nightly", "a b", "-rc", "../x",
+        ] {
+            assert!(
+                matches!(check_alias_name(name), Err(SvmError::InvalidAlias(_))),
+                "{name}"
+            );
+        }
+        for name in ["stable", "ci-pinned", "team_a", "v2"] {
+            assert!(check_alias_name(name).is_ok(), "{name}");
+        }
+    }
+}
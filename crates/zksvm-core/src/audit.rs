@@ -0,0 +1,205 @@
+use crate::{
+    releases::{all_platform_artifacts, is_valid_artifact_name, PlatformArtifacts},
+    Platform, SvmError,
+};
+use semver::Version;
+use std::{collections::BTreeMap, fmt};
+
+/// The problems found by [`audit_releases`] in the release lists of all platforms.
+#[derive(Debug, Default)]
+pub struct AuditReport {
+    /// The platforms whose release lists were audited.
+    pub platforms: Vec<Platform>,
+    /// The platforms whose release list could not be fetched, along with the error.
+    pub failed: Vec<(Platform, SvmError)>,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl AuditReport {
+    /// Returns whether every release list was fetched and nothing was found.
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty() && self.findings.is_empty()
+    }
+}
+
+/// A problem found in the release lists, see [`audit_releases`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditFinding {
+    /// `version` is listed for some platforms, but not for `missing`.
+    MissingOnPlatforms {
+        version: Version,
+        missing: Vec<Platform>,
+    },
+    /// `version` is listed for `platform` without a checksum.
+    MissingChecksum {
+        platform: Platform,
+        version: Version,
+    },
+    /// The artifact listed for `version` on `platform` is not a plain file name or does not
+    /// mention the version.
+    MalformedArtifact {
+        platform: Platform,
+        version: Version,
+        artifact: String,
+    },
+}
+
+impl fmt::Display for AuditFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingOnPlatforms { version, missing } => {
+                let missing = missing.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "zksolc {version} is not listed for {}",
+                    missing.join(", ")
+                )
+            }
+            Self::MissingChecksum { platform, version } => {
+                write!(f, "zksolc {version} has no checksum for {platform}")
+            }
+            Self::MalformedArtifact {
+                platform,
+                version,
+                artifact,
+            } => write!(
+                f,
+                "zksolc {version} has a malformed artifact name for {platform}: {artifact:?}"
+            ),
+        }
+    }
+}
+
+/// Fetches the release lists of all platforms and cross-checks them, reporting versions missing
+/// on some platforms, versions without a checksum and malformed artifact names.
+///
+/// Meant for maintainers of release sources, and for debugging
+/// [`SvmError::UnknownVersion`] on a single platform.
+pub async fn audit_releases() -> Result<AuditReport, SvmError> {
+    Ok(audit(all_platform_artifacts().await?))
+}
+
+/// Cross-checks the release lists in `artifacts`.
+fn audit(artifacts: PlatformArtifacts) -> AuditReport {
+    let platforms = artifacts
+        .releases
+        .iter()
+        .map(|(platform, _)| *platform)
+        .collect::<Vec<_>>();
+    let mut listed_for = BTreeMap::<&Version, Vec<Platform>>::new();
+    let mut findings = vec![];
+    for (platform, releases) in &artifacts.releases {
+        for (version, artifact) in &releases.releases {
+            listed_for.entry(version).or_default().push(*platform);
+            if releases.get_checksum(version).is_none() {
+                findings.push(AuditFinding::MissingChecksum {
+                    platform: *platform,
+                    version: version.clone(),
+                });
+            }
+            if !is_valid_artifact_name(artifact) || !artifact.contains(&version.to_string()) {
+                findings.push(AuditFinding::MalformedArtifact {
+                    platform: *platform,
+                    version: version.clone(),
+                    artifact: artifact.clone(),
+                });
+            }
+        }
+    }
+    // versions missing somewhere come first, as they cause "unknown version" errors
+    let mut missing = listed_for
+        .into_iter()
+        .filter_map(|(version, listed)| {
+            let missing = platforms
+                .iter()
+                .copied()
+                .filter(|platform| !listed.contains(platform))
+                .collect::<Vec<_>>();
+            (!missing.is_empty()).then(|| AuditFinding::MissingOnPlatforms {
+                version: version.clone(),
+                missing,
+            })
+        })
+        .collect::<Vec<_>>();
+    missing.extend(findings);
+    AuditReport {
+        platforms,
+        failed: artifacts.failed,
+        findings: missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildInfo, Releases};
+
+    #[test]
+    fn cross_checks_platforms() {
+        let list = |versions: &[(Version, &str, bool)]| Releases {
+            builds: versions
+                .iter()
+                .filter(|(.., checksum)| *checksum)
+                .map(|(version, ..)| BuildInfo::new(version.clone(), vec![0; 32]))
+                .collect(),
+            releases: versions
+                .iter()
+                .map(|(version, artifact, _)| (version.clone(), artifact.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+        let (v1, v2) = (Version::new(1, 5, 6), Version::new(1, 5, 7));
+        let report = audit(PlatformArtifacts {
+            releases: vec![
+                (
+                    Platform::LinuxAmd64,
+                    list(&[
+                        (v1.clone(), "zksolc-linux-amd64-musl-v1.5.6", true),
+                        (v2.clone(), "zksolc-linux-amd64-musl-v1.5.7", true),
+                    ]),
+                ),
+                (
+                    Platform::MacOsAmd64,
+                    list(&[
+                        (v1.clone(), "../zksolc-macosx-amd64-v1.5.6", true),
+                        (v2.clone(), "zksolc-macosx-amd64-v1.5.7", false),
+                    ]),
+                ),
+                (
+                    Platform::WindowsAmd64,
+                    list(&[(v1.clone(), "zksolc-windows-amd64-gnu-v1.5.5.exe", true)]),
+                ),
+            ],
+            failed: vec![],
+        });
+        assert_eq!(
+            report.findings,
+            [
+                AuditFinding::MissingOnPlatforms {
+                    version: v2.clone(),
+                    missing: vec![Platform::WindowsAmd64],
+                },
+                AuditFinding::MalformedArtifact {
+                    platform: Platform::MacOsAmd64,
+                    version: v1.clone(),
+                    artifact: "../zksolc-macosx-amd64-v1.5.6".to_string(),
+                },
+                AuditFinding::MissingChecksum {
+                    platform: Platform::MacOsAmd64,
+                    version: v2.clone(),
+                },
+                AuditFinding::MalformedArtifact {
+                    platform: Platform::WindowsAmd64,
+                    version: v1.clone(),
+                    artifact: "zksolc-windows-amd64-gnu-v1.5.5.exe".to_string(),
+                },
+            ]
+        );
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.findings[0].to_string(),
+            "zksolc 1.5.7 is not listed for windows-amd64"
+        );
+        assert!(audit(PlatformArtifacts::default()).is_clean());
+    }
+}
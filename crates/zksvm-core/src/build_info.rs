@@ -0,0 +1,81 @@
+use crate::{data_dir, platform, releases};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Information about how this version of zksvm was built and where it looks for things.
+///
+/// This is the first thing to include in bug reports.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BuildDetails {
+    /// The zksvm crate version.
+    pub version: &'static str,
+    /// The git commit zksvm was built from, if known. Only the CLI records it.
+    pub git_sha: Option<&'static str>,
+    /// The date zksvm was built, if known. Only the CLI records it.
+    pub build_date: Option<&'static str>,
+    /// The target triple zksvm was built for.
+    pub target: &'static str,
+    /// The zksolc platform detected for this machine.
+    pub platform: String,
+    /// The platform zksolc artifacts are downloaded for, which differs from `platform` when
+    /// running them under emulation.
+    pub artifact_platform: String,
+    /// The enabled cargo features.
+    pub features: Vec<&'static str>,
+    /// The data directory zksolc versions are installed in.
+    pub data_dir: PathBuf,
+    /// The URL the release list is fetched from.
+    pub releases_url: String,
+}
+
+impl BuildDetails {
+    /// Returns the build details as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("build details serialize to JSON")
+    }
+}
+
+/// Returns information about this build of zksvm.
+pub fn build_info() -> BuildDetails {
+    let platform = platform::platform();
+    let artifact_platform = platform::artifact_platform().unwrap_or(platform);
+    BuildDetails {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: None,
+        build_date: None,
+        target: env!("ZKSVM_TARGET"),
+        platform: platform.to_string(),
+        artifact_platform: artifact_platform.to_string(),
+        features: enabled_features(),
+        data_dir: data_dir().to_path_buf(),
+        releases_url: releases::ReleaseSource::from_env()
+            .map(|source| source.releases_url(artifact_platform).to_string())
+            .unwrap_or_else(|err| err.to_string()),
+    }
+}
+
+fn enabled_features() -> Vec<&'static str> {
+    [
+        ("blocking", cfg!(feature = "blocking")),
+        ("openssl", cfg!(feature = "openssl")),
+        ("rustls", cfg!(feature = "rustls")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_info_json() {
+        let info = build_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.target.is_empty());
+        let json: serde_json::Value = serde_json::from_str(&info.to_json()).unwrap();
+        assert_eq!(json["data_dir"], data_dir().to_str().unwrap());
+        assert!(json["features"].is_array());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    fsutil,
+    manifest::{self, Manifest},
+    platform::Platform,
+    state, version_binary, version_path, SvmError,
+};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// The name of the manifest, which is always the first entry of a bundle.
+const MANIFEST_NAME: &str = "zksvm-bundle.json";
+
+/// The version of the bundle format written by [`export_bundle`].
+const FORMAT_VERSION: u32 = 1;
+
+/// Describes the contents of a bundle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BundleManifest {
+    /// The version of the bundle format.
+    pub(crate) format: u32,
+    /// The platform the bundled binaries were installed for.
+    pub(crate) platform: String,
+    /// The bundled versions, in the order of their entries.
+    pub(crate) versions: Vec<BundledVersion>,
+}
+
+/// A zksolc version in a bundle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BundledVersion {
+    pub(crate) version: Version,
+    /// The path of the binary within the bundle.
+    pub(crate) path: String,
+    /// The hex encoded SHA-256 checksum of the binary.
+    pub(crate) sha256: String,
+    /// The manifest of the installed version.
+    #[serde(default)]
+    pub(crate) manifest: Manifest,
+}
+
+/// Writes the installed `versions` to a bundle at `path`, a tar archive that
+/// [`install_bundle`](crate::install_bundle) installs on machines without network access.
+///
+/// The bundle holds the binaries, their manifests and their checksums. Bundles are specific to
+/// `platform`, the platform the versions were installed for.
+pub fn export_bundle(
+    versions: &[Version],
+    platform: Platform,
+    path: &Path,
+) -> Result<(), SvmError> {
+    let _lock = state::lock_data_dir(false)?;
+    let mut bundled = Vec::with_capacity(versions.len());
+    for version in versions {
+        if !version_path(&version.to_string()).is_dir() {
+            return Err(SvmError::VersionNotInstalled(version.to_string()));
+        }
+        let binary = version_binary(&version.to_string());
+        let mut hasher = Sha256::new();
+        io::copy(
+            &mut fs::File::open(&binary).map_err(|err| fsutil::file_error(err, &binary))?,
+            &mut hasher,
+        )?;
+        let file_name = binary.file_name().expect("binary has a file name");
+        bundled.push(BundledVersion {
+            version: version.clone(),
+            path: format!("{version}/{}", file_name.to_string_lossy()),
+            sha256: hex::encode(hasher.finalize()),
+            manifest: manifest::read_manifest(version),
+        });
+    }
+    let manifest = BundleManifest {
+        format: FORMAT_VERSION,
+        platform: platform.to_string(),
+        versions: bundled,
+    };
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let staged = tempfile::Builder::new()
+        .prefix(".zksvm-bundle-")
+        .tempfile_in(dir)?;
+    let mut builder = tar::Builder::new(staged);
+    let json = serde_json::to_vec_pretty(&manifest).expect("bundle manifest is serializable");
+    append(
+        &mut builder,
+        MANIFEST_NAME,
+        0o644,
+        json.len() as u64,
+        &json[..],
+    )?;
+    for bundled in &manifest.versions {
+        let binary = version_binary(&bundled.version.to_string());
+        let file = fs::File::open(&binary).map_err(|err| fsutil::file_error(err, &binary))?;
+        let size = file.metadata()?.len();
+        append(&mut builder, &bundled.path, 0o755, size, file)?;
+    }
+    let staged = builder.into_inner()?;
+    fsutil::persist(staged, path).map_err(|err| fsutil::file_error(err.error, path))?;
+    Ok(())
+}
+
+/// Appends a file to the bundle, leaving out timestamps and owners so that bundles of the same
+/// versions are identical.
+fn append(
+    builder: &mut tar::Builder<impl io::Write>,
+    path: &str,
+    mode: u32,
+    size: u64,
+    data: impl Read,
+) -> Result<(), SvmError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(size);
+    header.set_mode(mode);
+    header.set_mtime(0);
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+/// Reads the bundle at `path`, calling `on_binary` with each bundled version and its binary.
+///
+/// Fails if the bundle is not in a supported format, was made for another platform than
+/// `platform` or does not hold exactly the binaries its manifest lists. The binaries are not
+/// checked against their checksums, which is up to `on_binary`.
+pub(crate) fn read_bundle(
+    path: &Path,
+    platform: Platform,
+    mut on_binary: impl FnMut(&BundledVersion, Vec<u8>) -> Result<(), SvmError>,
+) -> Result<BundleManifest, SvmError> {
+    let invalid = |reason: String| SvmError::InvalidBundle(path.to_path_buf(), reason);
+    let file = fs::File::open(path).map_err(|err| fsutil::file_error(err, path))?;
+    let mut archive = tar::Archive::new(file);
+    let mut entries = archive.entries()?;
+
+    let mut first = entries
+        .next()
+        .ok_or_else(|| invalid("the bundle is empty".to_string()))??;
+    if first.path()?.to_str() != Some(MANIFEST_NAME) {
+        return Err(invalid(format!("it does not start with {MANIFEST_NAME}")));
+    }
+    let mut json = vec![];
+    first.read_to_end(&mut json)?;
+    let manifest: BundleManifest =
+        serde_json::from_slice(&json).map_err(|err| invalid(err.to_string()))?;
+    if manifest.format != FORMAT_VERSION {
+        return Err(invalid(format!(
+            "unsupported format version {}; update zksvm to install it",
+            manifest.format
+        )));
+    }
+    if manifest.platform != platform.to_string() {
+        return Err(invalid(format!(
+            "it was made for {}, but this machine uses {platform}",
+            manifest.platform
+        )));
+    }
+
+    let mut seen = BTreeSet::new();
+    for entry in entries {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.to_string_lossy().into_owned();
+        let Some(bundled) = manifest.versions.iter().find(|v| v.path == entry_path) else {
+            return Err(invalid(format!("unexpected entry {entry_path}")));
+        };
+        if !seen.insert(entry_path.clone()) {
+            return Err(invalid(format!("duplicate entry {entry_path}")));
+        }
+        let mut binary = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut binary)?;
+        on_binary(bundled, binary)?;
+    }
+    if let Some(missing) = manifest.versions.iter().find(|v| !seen.contains(&v.path)) {
+        return Err(invalid(format!("missing entry {}", missing.path)));
+    }
+    Ok(manifest)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{
+        import_binary, install_bundle, platform::artifact_platform, remove_version, Releases,
+        VerificationResult,
+    };
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    #[serial_test::serial]
+    fn installs_exported_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zksolc");
+        fs::write(&path, "#!/bin/sh\necho 'zksolc v0.0.8 (LLVM build abc)'\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let version = import_binary(&path, None).unwrap().version;
+        let platform = artifact_platform().unwrap();
+
+        let bundle = dir.path().join("bundle.tar");
+        export_bundle(std::slice::from_ref(&version), platform, &bundle).unwrap();
+        remove_version(&version).unwrap();
+
+        let outcomes = install_bundle(&bundle).unwrap();
+        assert_eq!(
+            outcomes,
+            [(version.clone(), crate::InstallOutcome::Installed)]
+        );
+        assert_eq!(
+            fs::read(version_binary(&version.to_string())).unwrap(),
+            fs::read(&path).unwrap()
+        );
+        assert_eq!(
+            crate::verify::verify_binary(&version, &Releases::default()).unwrap(),
+            VerificationResult::Verified
+        );
+        assert_eq!(
+            install_bundle(&bundle).unwrap(),
+            [(version.clone(), crate::InstallOutcome::AlreadyInstalled)]
+        );
+        remove_version(&version).unwrap();
+
+        // another platform's bundle is refused before anything is installed
+        let other = match platform {
+            Platform::LinuxAmd64 => Platform::MacOsAarch64,
+            _ => Platform::LinuxAmd64,
+        };
+        assert!(matches!(
+            read_bundle(&bundle, other, |_, _| unreachable!()),
+            Err(SvmError::InvalidBundle(..))
+        ));
+
+        // a binary not matching its recorded checksum is not installed
+        let mut tampered = fs::read(&bundle).unwrap();
+        let at = tampered
+            .windows(6)
+            .rposition(|window| window == b"v0.0.8")
+            .unwrap();
+        tampered[at + 5] = b'9';
+        fs::write(&bundle, tampered).unwrap();
+        assert!(matches!(
+            install_bundle(&bundle),
+            Err(SvmError::ChecksumMismatch { .. })
+        ));
+        assert!(!version_path(&version.to_string()).exists());
+
+        assert!(install_bundle(&path).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{http, SvmError};
+use reqwest::StatusCode;
+use semver::Version;
+use std::time::Duration;
+use url::Url;
+
+/// Returns the URL of the `sha256sum` file some mirrors publish next to an artifact.
+pub(crate) fn sibling_url(artifact_url: &Url) -> Url {
+    let mut url = artifact_url.clone();
+    url.set_path(&format!("{}.sha256", artifact_url.path()));
+    url
+}
+
+/// Parses the checksum of `file_name` from a file in `sha256sum` format.
+///
+/// Lines are `<hex>  <name>`, or `<hex> *<name>` for binary mode. A file with a single bare
+/// hash is accepted as well, as some mirrors omit the name.
+pub(crate) fn parse_sha256sum(contents: &str, file_name: &str) -> Option<Vec<u8>> {
+    let mut lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let first = lines.clone().next()?;
+    if !first.contains(char::is_whitespace) && lines.clone().count() == 1 {
+        return decode(first);
+    }
+    lines.find_map(|line| {
+        let (hash, name) = line.split_once(char::is_whitespace)?;
+        let name = name.trim_start();
+        let name = name.strip_prefix('*').unwrap_or(name);
+        (name == file_name).then(|| decode(hash)).flatten()
+    })
+}
+
+fn decode(hash: &str) -> Option<Vec<u8>> {
+    hex::decode(hash).ok().filter(|bytes| bytes.len() == 32)
+}
+
+/// Fetches and parses the checksum file next to the artifact at `artifact_url`.
+///
+/// Returns `None` if the mirror does not publish one.
+pub(crate) async fn fetch_sibling_checksum(
+    artifact_url: &Url,
+    artifact: &str,
+    timeout: Duration,
+) -> Result<Option<Vec<u8>>, SvmError> {
+    let url = sibling_url(artifact_url);
+    let res = http::client()?
+        .timeout(timeout)
+        .build()?
+        .get(url.clone())
+        .send()
+        .await?;
+    if res.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !res.status().is_success() {
+        return Err(SvmError::UnsuccessfulResponse(url, res.status()));
+    }
+    let contents = res.text().await?;
+    parse_sha256sum(&contents, artifact)
+        .map(Some)
+        .ok_or(SvmError::InvalidChecksumFile(url))
+}
+
+/// Blocking version of [`fetch_sibling_checksum`].
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_fetch_sibling_checksum(
+    artifact_url: &Url,
+    artifact: &str,
+    timeout: Duration,
+) -> Result<Option<Vec<u8>>, SvmError> {
+    let url = sibling_url(artifact_url);
+    let res = http::blocking_client()?
+        .timeout(timeout)
+        .build()?
+        .get(url.clone())
+        .send()?;
+    if res.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !res.status().is_success() {
+        return Err(SvmError::UnsuccessfulResponse(url, res.status()));
+    }
+    let contents = res.text()?;
+    parse_sha256sum(&contents, artifact)
+        .map(Some)
+        .ok_or(SvmError::InvalidChecksumFile(url))
+}
+
+/// Combines the checksum of the release list with the one of the sibling checksum file, failing
+/// if they disagree.
+pub(crate) fn reconcile(
+    version: &Version,
+    index: Option<Vec<u8>>,
+    sibling: Option<Vec<u8>>,
+) -> Result<Option<Vec<u8>>, SvmError> {
+    match (index, sibling) {
+        (Some(index), Some(sibling)) if index != sibling => {
+            Err(SvmError::ChecksumSourcesDisagree {
+                version: version.to_string(),
+                index: hex::encode(index),
+                sibling: hex::encode(sibling),
+            })
+        }
+        (index, sibling) => Ok(index.or(sibling)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+
+    #[test]
+    fn sibling_url_appends_extension() {
+        let url = Url::parse("https://example.com/linux-amd64/zksolc-v1.4.1?raw=true").unwrap();
+        assert_eq!(
+            sibling_url(&url).as_str(),
+            "https://example.com/linux-amd64/zksolc-v1.4.1.sha256?raw=true"
+        );
+    }
+
+    #[test]
+    fn parses_sha256sum_format() {
+        let expected = hex::decode(HASH).ok();
+        assert_eq!(parse_sha256sum(&format!("{HASH}\n"), "zksolc"), expected);
+        assert_eq!(
+            parse_sha256sum(&format!("{HASH}  zksolc-v1.4.1\n"), "zksolc-v1.4.1"),
+            expected
+        );
+        assert_eq!(
+            parse_sha256sum(&format!("{HASH} *zksolc-v1.4.1"), "zksolc-v1.4.1"),
+            expected
+        );
+        let other = "0".repeat(64);
+        assert_eq!(
+            parse_sha256sum(
+                &format!("{other}  zksolc-v1.4.0\n{HASH}  zksolc-v1.4.1\n"),
+                "zksolc-v1.4.1"
+            ),
+            expected
+        );
+        assert_eq!(
+            parse_sha256sum(&format!("{HASH}  zksolc-v1.4.0"), "zksolc-v1.4.1"),
+            None
+        );
+        assert_eq!(parse_sha256sum("not a hash", "zksolc"), None);
+        assert_eq!(parse_sha256sum("", "zksolc"), None);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn parses_generated_sha256sum(
+            hash in proptest::array::uniform32(0u8..),
+            name in "[A-Za-z0-9._+-]{1,40}",
+            binary in proptest::bool::ANY,
+        ) {
+            let mode = if binary { " *" } else { "  " };
+            let contents = format!("{}{mode}{name}\n", hex::encode(hash));
+            proptest::prop_assert_eq!(parse_sha256sum(&contents, &name), Some(hash.to_vec()));
+        }
+
+        #[test]
+        fn parses_arbitrary_contents(contents in "\\PC*", name in "\\PC*") {
+            if let Some(hash) = parse_sha256sum(&contents, &name) {
+                proptest::prop_assert_eq!(hash.len(), 32);
+            }
+        }
+    }
+
+    #[test]
+    fn reconcile_cross_checks() {
+        let version = Version::new(1, 4, 1);
+        let a = vec![1; 32];
+        let b = vec![2; 32];
+        assert_eq!(
+            reconcile(&version, Some(a.clone()), None).unwrap(),
+            Some(a.clone())
+        );
+        assert_eq!(
+            reconcile(&version, None, Some(b.clone())).unwrap(),
+            Some(b.clone())
+        );
+        assert_eq!(
+            reconcile(&version, Some(a.clone()), Some(a.clone())).unwrap(),
+            Some(a.clone())
+        );
+        assert!(matches!(
+            reconcile(&version, Some(a), Some(b)),
+            Err(SvmError::ChecksumSourcesDisagree { .. })
+        ));
+        assert_eq!(reconcile(&version, None, None).unwrap(), None);
+    }
+}
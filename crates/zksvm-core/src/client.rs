@@ -0,0 +1,255 @@
+use crate::{
+    http, paths::DataDir, platform::Platform, ClientConfig, InstallEvent, InstallOptions,
+    InstallOutcome, ReleaseSource, Releases, RetryPolicy, Svm, SvmError,
+};
+use semver::{Version, VersionReq};
+use std::{future::Future, path::PathBuf, time::Duration};
+use url::Url;
+
+/// zksvm with its own network settings, for consumers that need to tune how it reaches the
+/// release server, e.g. with a shorter timeout or their own user agent.
+///
+/// Its methods mirror the free functions of this crate, running them with the settings of the
+/// client, and in its data directory if one was set. Settings left out keep the defaults of the
+/// free functions.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), zksvm_core::SvmError> {
+/// use std::time::Duration;
+///
+/// let client = zksvm_core::SvmClient::builder()
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("foundry-zksync")
+///     .retries(5)
+///     .build();
+/// client.install(&semver::Version::new(1, 5, 7)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SvmClient {
+    svm: Svm,
+    config: ClientConfig,
+    retry: Option<RetryPolicy>,
+    source: Option<ReleaseSource>,
+}
+
+/// Builder of an [`SvmClient`], created with [`SvmClient::builder`].
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct SvmClientBuilder {
+    data_dir: Option<DataDir>,
+    config: ClientConfig,
+    retry: Option<RetryPolicy>,
+    source: Option<ReleaseSource>,
+}
+
+impl SvmClientBuilder {
+    /// Keeps installs and state in `data_dir`, see [`Svm::new`].
+    pub fn data_dir(mut self, data_dir: impl Into<DataDir>) -> Self {
+        self.data_dir = Some(data_dir.into());
+        self
+    }
+
+    /// Starts from `config`, e.g. to set a proxy, instead of the default configuration. Set it
+    /// before the other network settings, which it replaces.
+    pub fn client_config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// See [`ClientConfig::timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config = self.config.timeout(timeout);
+        self
+    }
+
+    /// See [`ClientConfig::connect_timeout`].
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config = self.config.connect_timeout(timeout);
+        self
+    }
+
+    /// See [`ClientConfig::user_agent`].
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config = self.config.user_agent(user_agent);
+        self
+    }
+
+    /// Tries each download up to `attempts` times, with the default backoff of
+    /// [`RetryPolicy`].
+    pub fn retries(self, attempts: u32) -> Self {
+        self.retry(RetryPolicy {
+            attempts,
+            ..Default::default()
+        })
+    }
+
+    /// Retries downloads according to `policy`.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Downloads release lists and artifacts from the mirror at `base_url`, which is laid out
+    /// like the default source.
+    ///
+    /// Fails like [`ReleaseSource::new`] if `base_url` cannot have paths below it.
+    pub fn mirror(self, base_url: Url) -> Result<Self, SvmError> {
+        Ok(self.source(ReleaseSource::new(base_url)?))
+    }
+
+    /// Downloads release lists and artifacts from `source`.
+    pub fn source(mut self, source: ReleaseSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Creates the client.
+    pub fn build(self) -> SvmClient {
+        SvmClient {
+            svm: self.data_dir.map_or_else(Svm::default, Svm::new),
+            config: self.config,
+            retry: self.retry,
+            source: self.source,
+        }
+    }
+}
+
+impl SvmClient {
+    /// Returns a builder of a client, with the default settings.
+    pub fn builder() -> SvmClientBuilder {
+        SvmClientBuilder::default()
+    }
+
+    /// Returns the [`Svm`] of the data directory the client operates on.
+    pub fn svm(&self) -> &Svm {
+        &self.svm
+    }
+
+    /// Returns `opts` with the retry policy and source of the client filled in, unless they are
+    /// set already.
+    pub fn install_options(&self, opts: &InstallOptions) -> InstallOptions {
+        InstallOptions {
+            retry: opts.retry.or(self.retry),
+            source: opts.source.clone().or_else(|| self.source.clone()),
+            ..opts.clone()
+        }
+    }
+
+    /// Runs `fut` with the data directory and network settings of the client.
+    async fn scope<F: Future>(&self, fut: F) -> F::Output {
+        self.svm
+            .data_dir
+            .scope_async(http::scope_async(self.config.clone(), fut))
+            .await
+    }
+
+    /// See [`install`](crate::install()).
+    pub async fn install(&self, version: &Version) -> Result<PathBuf, SvmError> {
+        self.install_with_options(version, &InstallOptions::default())
+            .await
+    }
+
+    /// See [`install_with_options`](crate::install_with_options).
+    pub async fn install_with_options(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+    ) -> Result<PathBuf, SvmError> {
+        let opts = self.install_options(opts);
+        self.scope(crate::install_with_options(version, &opts))
+            .await
+    }
+
+    /// See [`install_with_events`](crate::install_with_events).
+    pub async fn install_with_events(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+        on_event: impl Fn(InstallEvent),
+    ) -> Result<PathBuf, SvmError> {
+        let opts = self.install_options(opts);
+        self.scope(crate::install_with_events(version, &opts, on_event))
+            .await
+    }
+
+    /// See [`ensure_installed`](crate::ensure_installed).
+    pub async fn ensure_installed(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+    ) -> Result<InstallOutcome, SvmError> {
+        let opts = self.install_options(opts);
+        self.scope(crate::ensure_installed(version, &opts)).await
+    }
+
+    /// See [`install_many`](crate::install_many).
+    pub async fn install_many(&self, versions: &[Version]) -> Vec<Result<PathBuf, SvmError>> {
+        let opts = self.install_options(&InstallOptions::default());
+        self.scope(crate::install_many_with_events(
+            versions,
+            &opts,
+            crate::DEFAULT_INSTALL_CONCURRENCY,
+            |_, _| {},
+        ))
+        .await
+    }
+
+    /// See [`all_releases`](crate::all_releases).
+    ///
+    /// Only lists the releases of `platform` itself if the client has a source, not those it can
+    /// run under emulation.
+    pub async fn all_releases(&self, platform: Platform) -> Result<Releases, SvmError> {
+        match &self.source {
+            Some(source) => self.scope(crate::all_releases_from(source, platform)).await,
+            None => self.scope(crate::all_releases(platform)).await,
+        }
+    }
+
+    /// See [`resolve_version`](crate::resolve_version).
+    pub async fn resolve_version(&self, req: &VersionReq) -> Result<Version, SvmError> {
+        let releases = self.all_releases(crate::artifact_platform()?).await?;
+        crate::best_matching_version(req, releases.releases.keys())
+            .ok_or_else(|| SvmError::NoMatchingVersion(req.to_string()))
+    }
+
+    /// See [`installed_versions`](crate::installed_versions).
+    pub fn installed_versions(&self) -> Result<Vec<Version>, SvmError> {
+        self.svm.installed_versions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_in_install_options() {
+        let mirror = Url::parse("https://mirror.example.com/zksolc").unwrap();
+        let client = SvmClient::builder()
+            .retries(5)
+            .mirror(mirror.clone())
+            .unwrap()
+            .build();
+        let opts = client.install_options(&InstallOptions::default());
+        assert_eq!(opts.retry.unwrap().attempts, 5);
+        assert_eq!(opts.source.unwrap().base_url(), &mirror);
+
+        let own = InstallOptions::default().retry(RetryPolicy {
+            attempts: 1,
+            ..Default::default()
+        });
+        assert_eq!(client.install_options(&own).retry.unwrap().attempts, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn applies_network_settings() {
+        let client = SvmClient::builder().timeout(Duration::from_secs(5)).build();
+        assert_eq!(http::request_timeout(), Duration::from_secs(120));
+        assert_eq!(
+            client.scope(async { http::request_timeout() }).await,
+            Duration::from_secs(5)
+        );
+    }
+}
@@ -0,0 +1,438 @@
+use crate::{GlobalBinary, InstallOptions, ReleaseSource, RetryPolicy, SvmError, Warning};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Environment variable overriding the path of the global config file.
+pub const ZKSVM_CONFIG: &str = "ZKSVM_CONFIG";
+
+/// Settings of the machine, read from the global config file, see [`config_path`].
+///
+/// Environment variables take precedence over the file: `$ZKSVM_RELEASES_SOURCE` and
+/// `$ZKSVM_RELEASES_URL` over [`releases_source`](Self::releases_source) and
+/// [`releases_url`](Self::releases_url), `$ZKSVM_RELEASES_TTL` over
+/// [`releases_ttl`](Self::releases_ttl), `$ZKSVM_HOME` over [`data_dir`](Self::data_dir), and the
+/// proxies of `$HTTPS_PROXY` and friends over [`proxy`](Self::proxy).
+///
+/// ```toml
+/// releases-url = "https://mirror.example.com/zksolc"
+/// releases-ttl = "10m"
+///
+/// [install]
+/// attempts = 5
+/// checksum-file = true
+/// ```
+///
+/// Keys this zksvm does not know, e.g. written by a newer one, are kept but otherwise ignored,
+/// see [`unknown_keys`](Self::unknown_keys).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// Where to list releases from, like `$ZKSVM_RELEASES_SOURCE`: `matter-labs`,
+    /// `github:<owner>/<repo>` or `default`, see [`ReleaseSource`](crate::ReleaseSource).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub releases_source: Option<String>,
+    /// Base URL to download release lists and artifacts from, or a comma separated list of base
+    /// URLs to try in order, like `$ZKSVM_RELEASES_URL`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub releases_url: Option<String>,
+    /// Proxy to send all requests through, e.g. `http://proxy.internal:3128`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// How long fetched release lists are cached, see
+    /// [`releases_cache_ttl`](crate::releases_cache_ttl).
+    #[serde(skip_serializing_if = "Option::is_none", with = "duration_opt")]
+    pub releases_ttl: Option<Duration>,
+    /// Directory to keep installs and state in, see
+    /// [`DataDir::from_env`](crate::DataDir::from_env).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<PathBuf>,
+    /// How the binary of the global version is provided, see
+    /// [`set_global_version`](crate::set_global_version).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_binary: Option<GlobalBinary>,
+    /// Whether to count command invocations and version runs in the data directory, see
+    /// [`usage_stats`](crate::usage_stats). The counts never leave the machine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<bool>,
+    /// Defaults of `zksvm install`.
+    #[serde(skip_serializing_if = "InstallDefaults::is_empty")]
+    pub install: InstallDefaults,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+/// Defaults of installs, the `[install]` table of the [`Config`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct InstallDefaults {
+    /// See [`RetryPolicy::attempts`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u32>,
+    /// How many versions to download at the same time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jobs: Option<usize>,
+    /// See [`InstallOptions::sibling_checksum`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_file: Option<bool>,
+    /// See [`InstallOptions::allow_unchecked`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_unchecked: Option<bool>,
+    /// See [`InstallOptions::verify_version`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify: Option<bool>,
+    /// See [`InstallOptions::native_only`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native_only: Option<bool>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+impl InstallDefaults {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Applies the defaults that are set to `opts`.
+    pub fn apply(&self, mut opts: InstallOptions) -> InstallOptions {
+        if let Some(attempts) = self.attempts {
+            opts.retry = Some(RetryPolicy {
+                attempts,
+                ..opts.retry.unwrap_or_default()
+            });
+        }
+        opts.sibling_checksum = self.checksum_file.unwrap_or(opts.sibling_checksum);
+        opts.allow_unchecked = self.allow_unchecked.unwrap_or(opts.allow_unchecked);
+        opts.verify_version = self.verify.unwrap_or(opts.verify_version);
+        opts.native_only = self.native_only.unwrap_or(opts.native_only);
+        opts
+    }
+}
+
+/// The keys [`Config::get`] and [`Config::set`] accept.
+pub const CONFIG_KEYS: &[&str] = &[
+    "releases-source",
+    "releases-url",
+    "proxy",
+    "releases-ttl",
+    "data-dir",
+    "global-binary",
+    "telemetry",
+    "install.attempts",
+    "install.jobs",
+    "install.checksum-file",
+    "install.allow-unchecked",
+    "install.verify",
+    "install.native-only",
+];
+
+impl Config {
+    /// Reads the global config file, or returns the default config if there is none.
+    pub fn load() -> Result<Self, SvmError> {
+        match config_path() {
+            Some(path) => Self::read(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Reads the config file at `path`, which is the default config if it does not exist.
+    pub fn read(path: &Path) -> Result<Self, SvmError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        toml::from_str(&contents)
+            .map_err(|err| SvmError::InvalidConfig(path.to_path_buf(), err.to_string()))
+    }
+
+    /// Returns the keys of the file this zksvm does not know, e.g. `install.mirror`, which are
+    /// ignored.
+    pub fn unknown_keys(&self) -> Vec<String> {
+        let install = self
+            .install
+            .unknown
+            .keys()
+            .map(|key| format!("install.{key}"));
+        self.unknown.keys().cloned().chain(install).collect()
+    }
+
+    /// Returns a [`Warning::UnknownConfigKey`] for each of the
+    /// [`unknown_keys`](Self::unknown_keys).
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.unknown_keys()
+            .into_iter()
+            .map(|key| Warning::UnknownConfigKey { key })
+            .collect()
+    }
+
+    /// Writes the config to the file at `path`, creating its directory if needed.
+    ///
+    /// Like the other state files, it is written along with a checksum and a backup of its
+    /// previous contents, see [`recover_state_files`](crate::recover_state_files).
+    pub fn write(&self, path: &Path) -> Result<(), SvmError> {
+        let contents = toml::to_string(self)
+            .map_err(|err| SvmError::InvalidConfig(path.to_path_buf(), err.to_string()))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        crate::state::write_state_file(path, &contents)
+    }
+
+    /// Returns the value of `key`, one of [`CONFIG_KEYS`], formatted like in the file, or `None`
+    /// if it is not set.
+    pub fn get(&self, key: &str) -> Result<Option<String>, SvmError> {
+        let install = &self.install;
+        Ok(match key {
+            "releases-source" => self.releases_source.clone(),
+            "releases-url" => self.releases_url.clone(),
+            "proxy" => self.proxy.clone(),
+            "releases-ttl" => self
+                .releases_ttl
+                .map(|ttl| humantime::format_duration(ttl).to_string()),
+            "data-dir" => self.data_dir.as_ref().map(|dir| dir.display().to_string()),
+            "global-binary" => self.global_binary.map(|v| v.to_string()),
+            "telemetry" => self.telemetry.map(|v| v.to_string()),
+            "install.attempts" => install.attempts.map(|v| v.to_string()),
+            "install.jobs" => install.jobs.map(|v| v.to_string()),
+            "install.checksum-file" => install.checksum_file.map(|v| v.to_string()),
+            "install.allow-unchecked" => install.allow_unchecked.map(|v| v.to_string()),
+            "install.verify" => install.verify.map(|v| v.to_string()),
+            "install.native-only" => install.native_only.map(|v| v.to_string()),
+            _ => return Err(SvmError::UnknownConfigKey(key.to_string())),
+        })
+    }
+
+    /// Sets `key`, one of [`CONFIG_KEYS`], to `value`, or unsets it if `value` is `None`.
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<(), SvmError> {
+        let invalid = |reason: String| SvmError::InvalidConfigValue {
+            key: key.to_string(),
+            reason,
+        };
+        let parse_bool = |value: &str| {
+            value
+                .parse::<bool>()
+                .map_err(|err| invalid(err.to_string()))
+        };
+        let install = &mut self.install;
+        match (key, value) {
+            ("releases-source", value) => {
+                if let Some(source) = value {
+                    ReleaseSource::named(source)?;
+                }
+                self.releases_source = value.map(str::to_string);
+            }
+            ("releases-url", value) => {
+                for url in value.into_iter().flat_map(|urls| urls.split(',')) {
+                    let url =
+                        url::Url::parse(url.trim()).map_err(|err| invalid(err.to_string()))?;
+                    crate::releases::check_base_url(&url)?;
+                }
+                self.releases_url = value.map(str::to_string);
+            }
+            ("proxy", value) => {
+                if let Some(proxy) = value {
+                    reqwest::Proxy::all(proxy).map_err(|err| invalid(err.to_string()))?;
+                }
+                self.proxy = value.map(str::to_string);
+            }
+            ("releases-ttl", value) => {
+                self.releases_ttl = value
+                    .map(|ttl| parse_duration(ttl).map_err(invalid))
+                    .transpose()?
+            }
+            ("data-dir", value) => self.data_dir = value.map(PathBuf::from),
+            ("global-binary", value) => {
+                self.global_binary = value.map(str::parse).transpose().map_err(invalid)?
+            }
+            ("telemetry", value) => self.telemetry = value.map(parse_bool).transpose()?,
+            ("install.attempts", value) => {
+                install.attempts = value
+                    .map(|v| v.parse().map_err(|err| invalid(format!("{err}"))))
+                    .transpose()?
+            }
+            ("install.jobs", value) => {
+                install.jobs = value
+                    .map(|v| v.parse().map_err(|err| invalid(format!("{err}"))))
+                    .transpose()?
+            }
+            ("install.checksum-file", value) => {
+                install.checksum_file = value.map(parse_bool).transpose()?
+            }
+            ("install.allow-unchecked", value) => {
+                install.allow_unchecked = value.map(parse_bool).transpose()?
+            }
+            ("install.verify", value) => install.verify = value.map(parse_bool).transpose()?,
+            ("install.native-only", value) => {
+                install.native_only = value.map(parse_bool).transpose()?
+            }
+            _ => return Err(SvmError::UnknownConfigKey(key.to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// Returns the path of the global config file: `$ZKSVM_CONFIG` if set, otherwise
+/// `zksvm/config.toml` in the user's config directory, e.g. `~/.config/zksvm/config.toml` on
+/// Linux.
+pub fn config_path() -> Option<PathBuf> {
+    match std::env::var_os(ZKSVM_CONFIG) {
+        Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+        // tests must not pick up the config of whoever runs them
+        _ if cfg!(test) => None,
+        _ => Some(dirs::config_dir()?.join("zksvm").join("config.toml")),
+    }
+}
+
+/// Returns the config file's settings, ignoring a config file that cannot be read, for settings
+/// that are looked up where errors cannot be reported.
+pub(crate) fn file_config() -> Config {
+    Config::load().unwrap_or_default()
+}
+
+/// Parses a duration like `10m`, or `0` for none.
+pub(crate) fn parse_duration(duration: &str) -> Result<Duration, String> {
+    match duration.trim() {
+        "0" => Ok(Duration::ZERO),
+        duration => humantime::parse_duration(duration).map_err(|err| err.to_string()),
+    }
+}
+
+/// Helper serde module to serialize and deserialize optional durations like `10m`.
+mod duration_opt {
+    use super::*;
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|duration| parse_duration(&duration).map_err(de::Error::custom))
+            .transpose()
+    }
+
+    pub fn serialize<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(duration) => {
+                serializer.serialize_str(&humantime::format_duration(*duration).to_string())
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zksvm").join("config.toml");
+        assert_eq!(Config::read(&path).unwrap(), Config::default());
+
+        let mut config = Config::default();
+        for (key, value) in [
+            ("releases-source", "github:matter-labs/zksolc-bin"),
+            ("releases-url", "https://mirror.example.com/zksolc"),
+            ("releases-ttl", "10m"),
+            ("global-binary", "cmd"),
+            ("telemetry", "true"),
+            ("install.attempts", "5"),
+            ("install.checksum-file", "true"),
+        ] {
+            config.set(key, Some(value)).unwrap();
+            assert_eq!(config.get(key).unwrap().as_deref(), Some(value));
+        }
+        config.write(&path).unwrap();
+        assert_eq!(Config::read(&path).unwrap(), config);
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("[install]\nattempts = 5"));
+
+        let opts = config.install.apply(InstallOptions::default());
+        assert_eq!(opts.retry.unwrap().attempts, 5);
+        assert!(opts.sibling_checksum);
+        assert!(!opts.allow_unchecked);
+
+        config.set("releases-ttl", None).unwrap();
+        assert_eq!(config.get("releases-ttl").unwrap(), None);
+        for key in CONFIG_KEYS {
+            assert!(config.get(key).is_ok(), "{key}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let mut config = Config::default();
+        assert!(matches!(
+            config.get("mirror"),
+            Err(SvmError::UnknownConfigKey(_))
+        ));
+        for (key, value) in [
+            ("install.attempts", "many"),
+            ("install.verify", "yes please"),
+            ("releases-ttl", "soon"),
+            ("releases-url", "not a url"),
+            ("global-binary", "junction"),
+        ] {
+            assert!(
+                matches!(
+                    config.set(key, Some(value)),
+                    Err(SvmError::InvalidConfigValue { .. })
+                ),
+                "{key}"
+            );
+        }
+
+        assert!(matches!(
+            config.set(
+                "releases-url",
+                Some("https://mirror.example.com,mailto:a@b")
+            ),
+            Err(SvmError::InvalidReleaseSource(_))
+        ));
+        assert!(matches!(
+            config.set("releases-source", Some("gitlab:matter-labs/zksolc-bin")),
+            Err(SvmError::InvalidReleaseSource(_))
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[install]\nattempts = \"many\"").unwrap();
+        assert!(matches!(
+            Config::read(&path),
+            Err(SvmError::InvalidConfig(..))
+        ));
+    }
+
+    #[test]
+    fn keeps_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "mirror = \"https://mirror.example.com\"\n[install]\nattempts = 5\nparallel = true\n",
+        )
+        .unwrap();
+        let mut config = Config::read(&path).unwrap();
+        assert_eq!(config.install.attempts, Some(5));
+        assert_eq!(config.unknown_keys(), ["mirror", "install.parallel"]);
+        assert_eq!(config.warnings().len(), 2);
+
+        // a zksvm that does not know them must not drop them
+        config.set("telemetry", Some("true")).unwrap();
+        config.write(&path).unwrap();
+        let config = Config::read(&path).unwrap();
+        assert_eq!(config.unknown_keys(), ["mirror", "install.parallel"]);
+        assert_eq!(config.telemetry, Some(true));
+    }
+}
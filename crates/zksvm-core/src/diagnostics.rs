@@ -0,0 +1,485 @@
+use crate::{
+    install::{self, LockHolder, LOCK_FILE_PREFIX},
+    paths, platform,
+    releases::{download_releases, ReleaseSource},
+    state, verify_installed, Integrity, LocalState, SvmError, Tool, VerificationResult,
+};
+use serde::Serialize;
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The timeout of the request checking that the release list is reachable.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long an install may hold its lock before it is reported as possibly stuck.
+const LONG_HELD_LOCK: Duration = Duration::from_secs(60 * 60);
+
+/// How deep below the data directory broken symlinks are looked for, enough for the version
+/// directories and the shim.
+const SYMLINK_DEPTH: usize = 2;
+
+/// The findings of [`diagnose`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DiagnosticReport {
+    /// Whether no check found an error. Warnings do not make a report unhealthy.
+    pub healthy: bool,
+    pub findings: Vec<Finding>,
+}
+
+/// How serious a [`Finding`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// The outcome of one of the checks of [`diagnose`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    /// The name of the check, e.g. `data-dir` or `network`.
+    pub check: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    /// How to fix the problem, if there is one.
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn ok(check: &'static str, message: String) -> Self {
+        Self {
+            check,
+            severity: Severity::Ok,
+            message,
+            fix: None,
+        }
+    }
+
+    fn warning(check: &'static str, message: String, fix: impl Into<String>) -> Self {
+        Self {
+            check,
+            severity: Severity::Warning,
+            message,
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn error(check: &'static str, message: String, fix: impl Into<String>) -> Self {
+        Self {
+            check,
+            severity: Severity::Error,
+            message,
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Checks the zksvm installation for problems: the permissions of the data directory, stale lock
+/// files, corrupted state files, broken symlinks, corrupted binaries, the reachability of the
+/// release list and whether the `zksolc` shim is on `PATH`.
+///
+/// Nothing is changed, the findings say how to fix what is wrong.
+pub async fn diagnose() -> DiagnosticReport {
+    let data_dir = paths::data_dir();
+    let mut findings = check_data_dir(&data_dir);
+    findings.extend(check_lock_files(&data_dir));
+    findings.extend(check_state_files(state::verify_state_files()));
+    findings.extend(check_symlinks(&data_dir));
+    findings.extend(check_binaries().await);
+    findings.extend(check_network().await);
+    findings.extend(check_path(std::env::var_os("PATH"), &paths::bin_dir()));
+    DiagnosticReport {
+        healthy: findings.iter().all(|f| f.severity != Severity::Error),
+        findings,
+    }
+}
+
+/// Checks that the data directory is a writable directory.
+fn check_data_dir(dir: &Path) -> Vec<Finding> {
+    let finding = match fs::metadata(dir) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Finding::warning(
+            "data-dir",
+            format!("{} does not exist yet", dir.display()),
+            "install a version with `zksvm install <version>`",
+        ),
+        Err(err) => Finding::error(
+            "data-dir",
+            format!("{} cannot be accessed: {err}", dir.display()),
+            format!("check the permissions of {}", dir.display()),
+        ),
+        Ok(meta) if !meta.is_dir() => Finding::error(
+            "data-dir",
+            format!("{} is not a directory", dir.display()),
+            format!("move {} out of the way", dir.display()),
+        ),
+        Ok(_) => match tempfile::tempfile_in(dir) {
+            Ok(_) => Finding::ok("data-dir", format!("{} is writable", dir.display())),
+            Err(err) => Finding::error(
+                "data-dir",
+                format!("{} is not writable: {err}", dir.display()),
+                format!("make it writable, e.g. `chmod u+rwx {}`", dir.display()),
+            ),
+        },
+    };
+    vec![finding]
+}
+
+/// Reports the lock files of installs that no longer run.
+fn check_lock_files(dir: &Path) -> Vec<Finding> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut findings = vec![];
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(name) = name.to_str().filter(|n| n.starts_with(LOCK_FILE_PREFIX)) else {
+            continue;
+        };
+        let path = entry.path();
+        let holder = LockHolder::read(&path);
+        let stale = fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .map_err(Into::into)
+            .and_then(|file| install::try_lock_exclusive(&file));
+        match stale {
+            Ok(true) => {
+                let fix = match install::parse_lock_file_name(name) {
+                    Some((Tool::Zksolc, platform, version))
+                        if platform::artifact_platform().ok() == Some(platform) =>
+                    {
+                        format!(
+                            "remove it, or reinstall with `zksvm remove {version}` and `zksvm install {version}`"
+                        )
+                    }
+                    _ => "remove it".to_string(),
+                };
+                let started = holder
+                    .map(|holder| {
+                        let age = humantime::format_duration(holder.held_for());
+                        format!(", started {age} ago")
+                    })
+                    .unwrap_or_default();
+                findings.push(Finding::warning(
+                    "locks",
+                    format!(
+                        "{} is left over from an interrupted install{started}",
+                        path.display()
+                    ),
+                    fix,
+                ));
+            }
+            // an install in progress holds its lock, which is only a problem if it seems stuck
+            Ok(false) => match holder {
+                Some(holder) if holder.held_for() > LONG_HELD_LOCK => {
+                    findings.push(Finding::warning(
+                        "locks",
+                        format!(
+                            "{} has been held by process {} for {}",
+                            path.display(),
+                            holder.pid,
+                            humantime::format_duration(holder.held_for())
+                        ),
+                        format!(
+                            "check that the install is not stuck, and stop process {} if it is",
+                            holder.pid
+                        ),
+                    ));
+                }
+                _ => {}
+            },
+            Err(_) => {}
+        }
+    }
+    findings
+}
+
+/// Reports the integrity of the state files zksvm protects with checksums.
+fn check_state_files(files: Vec<(PathBuf, Result<Integrity, SvmError>)>) -> Vec<Finding> {
+    files
+        .into_iter()
+        .filter_map(|(path, integrity)| {
+            let path = path.display();
+            Some(match integrity {
+                Ok(Integrity::Missing) => return None,
+                Ok(Integrity::Valid) => {
+                    Finding::ok("state", format!("{path} matches its checksum"))
+                }
+                Ok(Integrity::Unverified) => Finding::ok(
+                    "state",
+                    format!(
+                        "{path} has no checksum yet, it is recorded the next time zksvm writes it"
+                    ),
+                ),
+                Ok(Integrity::Corrupted) => Finding::error(
+                    "state",
+                    format!("{path} does not match its checksum"),
+                    "run any other zksvm command to restore it from its backup",
+                ),
+                Err(err) => Finding::error(
+                    "state",
+                    format!("{path} cannot be read: {err}"),
+                    format!("check the permissions of {path}"),
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Reports symlinks in the data directory whose target is gone.
+fn check_symlinks(dir: &Path) -> Vec<Finding> {
+    let mut findings = vec![];
+    for path in broken_symlinks(dir, SYMLINK_DEPTH) {
+        let fix = if path.parent() == Some(paths::bin_dir().as_path()) {
+            "reinstall the shim with `zksvm init`".to_string()
+        } else {
+            format!("remove it with `rm {}`", path.display())
+        };
+        findings.push(Finding::error(
+            "symlinks",
+            format!("{} points to a file that does not exist", path.display()),
+            fix,
+        ));
+    }
+    findings
+}
+
+fn broken_symlinks(dir: &Path, depth: usize) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut broken = vec![];
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            if fs::metadata(&path).is_err() {
+                broken.push(path);
+            }
+        } else if file_type.is_dir() && depth > 1 {
+            broken.extend(broken_symlinks(&path, depth - 1));
+        }
+    }
+    broken
+}
+
+/// Checks the installed binaries against their published checksums.
+async fn check_binaries() -> Vec<Finding> {
+    let installed = match LocalState::read() {
+        Ok(state) => state.installed,
+        Err(err) => {
+            return vec![Finding::error(
+                "binaries",
+                format!("the installed versions cannot be read: {err}"),
+                "check the permissions of the data directory",
+            )]
+        }
+    };
+    let mut findings = vec![];
+    let mut verified = 0;
+    for version in &installed {
+        match verify_installed(version).await {
+            Ok(VerificationResult::Mismatch { .. }) => findings.push(Finding::error(
+                "binaries",
+                format!("zksolc {version} does not match its published checksum"),
+                format!("download it again with `zksvm verify --repair {version}`"),
+            )),
+            Ok(VerificationResult::Missing) => findings.push(Finding::error(
+                "binaries",
+                format!("the binary of zksolc {version} is missing"),
+                format!("download it again with `zksvm verify --repair {version}`"),
+            )),
+            Ok(VerificationResult::Verified | VerificationResult::Unverifiable) => verified += 1,
+            Err(err) => findings.push(Finding::warning(
+                "binaries",
+                format!("zksolc {version} could not be verified: {err}"),
+                format!("retry with `zksvm verify {version}` once the release list is reachable"),
+            )),
+        }
+    }
+    if verified > 0 {
+        findings.push(Finding::ok(
+            "binaries",
+            format!(
+                "{verified} of {} installed versions are intact",
+                installed.len()
+            ),
+        ));
+    }
+    findings
+}
+
+/// Checks that the release list of this platform can be fetched.
+async fn check_network() -> Vec<Finding> {
+    let fix = "check your network connection and proxy settings, or set ZKSVM_RELEASES_URL to a reachable mirror";
+    let (source, platform) = match ReleaseSource::from_env()
+        .and_then(|source| platform::artifact_platform().map(|platform| (source, platform)))
+    {
+        Ok(resolved) => resolved,
+        Err(err) => return vec![Finding::error("network", err.to_string(), fix)],
+    };
+    let url = source.releases_url(platform);
+    match download_releases(&source, platform, Some(REQUEST_TIMEOUT)).await {
+        Ok(_) => {
+            let mut findings = vec![Finding::ok(
+                "network",
+                format!(
+                    "the release list is reachable at {}",
+                    source.releases_url(platform)
+                ),
+            )];
+            if let Some(warning) = source.fallback_warning() {
+                findings.push(Finding::warning(
+                    "network",
+                    warning.to_string(),
+                    "update zksvm, or set ZKSVM_RELEASES_URL to the working source",
+                ));
+            }
+            findings
+        }
+        Err(err) => vec![Finding::error(
+            "network",
+            format!("the release list at {url} is unreachable: {err}"),
+            fix,
+        )],
+    }
+}
+
+/// Checks that the `zksolc` shim in `bin_dir` is the first `zksolc` on `path`.
+fn check_path(path: Option<OsString>, bin_dir: &Path) -> Vec<Finding> {
+    let dirs = path
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let name = format!("zksolc{}", std::env::consts::EXE_SUFFIX);
+    let finding = if !dirs.iter().any(|dir| dir == bin_dir) {
+        Finding::warning(
+            "path",
+            format!("{} is not on PATH", bin_dir.display()),
+            format!(
+                "run `zksvm init` and add {} to PATH in your shell profile",
+                bin_dir.display()
+            ),
+        )
+    } else {
+        match dirs.iter().find(|dir| dir.join(&name).is_file()) {
+            Some(dir) if dir == bin_dir => {
+                Finding::ok("path", format!("{} is on PATH", bin_dir.display()))
+            }
+            Some(dir) => Finding::warning(
+                "path",
+                format!(
+                    "{} comes before the zksvm shim on PATH",
+                    dir.join(&name).display()
+                ),
+                format!(
+                    "remove it, or move {} to the front of PATH",
+                    bin_dir.display()
+                ),
+            ),
+            None => Finding::warning(
+                "path",
+                format!(
+                    "{} is on PATH, but the shim is not installed",
+                    bin_dir.display()
+                ),
+                "install it with `zksvm init`",
+            ),
+        }
+    };
+    vec![finding]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_state_files() {
+        let file = |integrity| (PathBuf::from(".global-version"), Ok(integrity));
+        let findings = check_state_files(vec![
+            file(Integrity::Valid),
+            file(Integrity::Missing),
+            file(Integrity::Unverified),
+            file(Integrity::Corrupted),
+        ]);
+        let severities = findings.iter().map(|f| f.severity).collect::<Vec<_>>();
+        assert_eq!(severities, [Severity::Ok, Severity::Ok, Severity::Error]);
+        assert!(findings[2].fix.as_deref().unwrap().contains("backup"));
+    }
+
+    #[test]
+    fn checks_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin_dir = dir.path().join("bin");
+        let other = dir.path().join("other");
+        fs::create_dir_all(&bin_dir).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        let path = |dirs: &[&PathBuf]| Some(std::env::join_paths(dirs).unwrap());
+        let severity = |findings: Vec<Finding>| findings[0].severity;
+
+        assert_eq!(
+            severity(check_path(path(&[&other]), &bin_dir)),
+            Severity::Warning
+        );
+        assert_eq!(
+            severity(check_path(path(&[&bin_dir]), &bin_dir)),
+            Severity::Warning
+        );
+
+        let name = format!("zksolc{}", std::env::consts::EXE_SUFFIX);
+        fs::write(bin_dir.join(&name), "").unwrap();
+        assert_eq!(
+            severity(check_path(path(&[&other, &bin_dir]), &bin_dir)),
+            Severity::Ok
+        );
+        fs::write(other.join(&name), "").unwrap();
+        let findings = check_path(path(&[&other, &bin_dir]), &bin_dir);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0].message.contains("comes before"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_stale_locks_and_broken_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            check_data_dir(&dir.path().join("missing"))[0].severity,
+            Severity::Warning
+        );
+        assert_eq!(check_data_dir(dir.path())[0].severity, Severity::Ok);
+
+        let lock_path = dir
+            .path()
+            .join(format!("{LOCK_FILE_PREFIX}zksolc-linux-amd64-1.4.1"));
+        fs::write(&lock_path, "42 60").unwrap();
+        let findings = check_lock_files(dir.path());
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("interrupted install, started"));
+        let held = fs::File::open(&lock_path).unwrap();
+        assert!(install::try_lock_exclusive(&held).unwrap());
+        let findings = check_lock_files(dir.path());
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("held by process 42"));
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap();
+        fs::write(&lock_path, format!("42 {}", now.as_secs())).unwrap();
+        assert!(check_lock_files(dir.path()).is_empty());
+        drop(held);
+
+        let version_dir = dir.path().join("1.4.1");
+        fs::create_dir_all(&version_dir).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("gone"), version_dir.join("zksolc-1.4.1"))
+            .unwrap();
+        let findings = check_symlinks(dir.path());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+}
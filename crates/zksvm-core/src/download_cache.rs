@@ -0,0 +1,242 @@
+//! A cache of downloaded zksolc binaries, keyed by their SHA-256 checksum.
+//!
+//! The cache may be shared by several users, so nothing in it is trusted: entries are verified
+//! against the expected checksum every time they are used.
+//!
+//! Installs hard link their binary to its entry where possible, so an entry takes no extra space
+//! until the install is removed. [`remove_version`](crate::remove_version) then removes the
+//! entries nothing links to anymore, and [`clean_download_cache`](crate::clean_download_cache)
+//! removes all unused entries.
+
+use crate::{data_dir, fsutil, paths, SvmError};
+use sha2::{Digest, Sha256};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use tempfile::NamedTempFile;
+
+/// Returns the path of the cache entry of the binary with `checksum`.
+fn entry_path(checksum: &[u8]) -> PathBuf {
+    paths::download_cache_dir().join(hex::encode(checksum))
+}
+
+/// Returns whether the file at `path` has `checksum`, hashing it without reading it into memory.
+fn matches(path: &Path, checksum: &[u8]) -> bool {
+    let mut hasher = Sha256::new();
+    fs::File::open(path)
+        .and_then(|mut file| io::copy(&mut file, &mut hasher))
+        .is_ok_and(|_| hasher.finalize().as_slice() == checksum)
+}
+
+/// Opens the cached binary with `checksum`, if there is one.
+///
+/// Its contents are not verified, readers must check them against `checksum`.
+pub(crate) fn open(checksum: &[u8]) -> Option<fs::File> {
+    fs::File::open(entry_path(checksum)).ok()
+}
+
+/// Adds the verified binary at `path` to the cache.
+///
+/// The cache only speeds up installs, so failing to write to it is not an error.
+pub(crate) fn store(path: &Path, checksum: &[u8]) {
+    let _ = try_store(path, checksum);
+}
+
+fn try_store(source: &Path, checksum: &[u8]) -> io::Result<()> {
+    let path = entry_path(checksum);
+    if path.exists() {
+        return Ok(());
+    }
+    let dir = paths::download_cache_dir();
+    fs::create_dir_all(&dir)?;
+    let mut staged = tempfile::Builder::new()
+        .prefix(".zksolc-")
+        .tempfile_in(&dir)?;
+    io::copy(&mut fs::File::open(source)?, &mut staged)?;
+    // entries are linked into installs, which must not be able to modify them
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        staged
+            .as_file()
+            .set_permissions(fs::Permissions::from_mode(0o555))?;
+    }
+    // another install may have stored the same binary in the meantime
+    match staged.persist_noclobber(&path) {
+        Err(err) if err.error.kind() != io::ErrorKind::AlreadyExists => Err(err.error),
+        _ => Ok(()),
+    }
+}
+
+/// Links the cached binary with `checksum` to a temporary file in `dir`, if there is a valid one
+/// and the cache is on the same filesystem.
+///
+/// Entries owned by the current user are hard linked. Entries of other users are reflinked
+/// instead where the filesystem supports it, so that they cannot change the installed binary
+/// afterwards.
+pub(crate) fn link(checksum: &[u8], dir: &Path) -> Option<NamedTempFile> {
+    let entry = entry_path(checksum);
+    let hard_link = owned_by_current_user(&entry).ok()?;
+    let linked = tempfile::Builder::new()
+        .prefix(".zksolc-")
+        .make_in(dir, |path| {
+            if hard_link {
+                fs::hard_link(&entry, path)?;
+            } else {
+                reflink_copy::reflink(&entry, path)?;
+            }
+            fs::File::open(path)
+        })
+        .ok()?;
+    // the entry may have been replaced since it was verified
+    matches(linked.path(), checksum).then_some(linked)
+}
+
+/// Returns the entries of the cache, leaving out the temporary files of stores in progress.
+fn entries() -> Vec<PathBuf> {
+    let Ok(dir) = fs::read_dir(paths::download_cache_dir()) else {
+        return vec![];
+    };
+    dir.flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Returns the entries the `files` of an install are hard linked to.
+#[cfg(unix)]
+pub(crate) fn linked_entries(files: &[PathBuf]) -> Vec<PathBuf> {
+    use std::{collections::HashSet, os::unix::fs::MetadataExt};
+    let linked = files
+        .iter()
+        .filter_map(|path| fs::symlink_metadata(path).ok())
+        .filter(|meta| meta.is_file() && meta.nlink() > 1)
+        .map(|meta| (meta.dev(), meta.ino()))
+        .collect::<HashSet<_>>();
+    if linked.is_empty() {
+        return vec![];
+    }
+    entries()
+        .into_iter()
+        .filter(|entry| {
+            fs::symlink_metadata(entry).is_ok_and(|meta| linked.contains(&(meta.dev(), meta.ino())))
+        })
+        .collect()
+}
+
+/// Link counts are not available here, so no entry is known to be linked to an install.
+#[cfg(not(unix))]
+pub(crate) fn linked_entries(_files: &[PathBuf]) -> Vec<PathBuf> {
+    vec![]
+}
+
+/// Removes those of `entries` nothing links to anymore, returning them along with their size.
+///
+/// Failing to remove an entry is not an error, it only keeps taking up space.
+pub(crate) fn remove_unlinked(entries: Vec<PathBuf>) -> Vec<(PathBuf, u64)> {
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let meta = fs::symlink_metadata(&entry).ok()?;
+            if !meta.is_file() || fsutil::link_count(&meta) > 1 {
+                return None;
+            }
+            fs::remove_file(&entry).ok()?;
+            Some((entry, meta.len()))
+        })
+        .collect()
+}
+
+/// Removes the entries of the current user that no install links to, returning them along with
+/// their size.
+///
+/// Entries of other users sharing the cache are kept, their installs may have reflinked them.
+/// Where link counts are not available, all entries of the current user are removed: installs
+/// keep their binary either way, only later installs have to download it again.
+pub(crate) fn clean() -> Result<Vec<(PathBuf, u64)>, SvmError> {
+    let _lock = crate::state::lock_data_dir(true)?;
+    let owned = entries()
+        .into_iter()
+        .filter(|entry| owned_by_current_user(entry).unwrap_or(false))
+        .collect();
+    Ok(remove_unlinked(owned))
+}
+
+/// Returns whether `path` belongs to the user owning the data directory.
+#[cfg(unix)]
+fn owned_by_current_user(path: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(fs::metadata(path)?.uid() == fs::metadata(data_dir())?.uid())
+}
+
+#[cfg(not(unix))]
+fn owned_by_current_user(path: &Path) -> io::Result<bool> {
+    fs::metadata(path)?;
+    fs::metadata(data_dir())?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[serial_test::serial]
+    fn caches_verified_binaries() {
+        crate::setup_data_dir().unwrap();
+        let bytes = b"zksolc cached binary";
+        let checksum = Sha256::digest(bytes).to_vec();
+        assert!(open(&checksum).is_none());
+
+        let source = tempfile::NamedTempFile::new().unwrap();
+        fs::write(source.path(), bytes).unwrap();
+        store(source.path(), &checksum);
+        store(source.path(), &checksum);
+        let mut cached = vec![];
+        io::Read::read_to_end(&mut open(&checksum).unwrap(), &mut cached).unwrap();
+        assert_eq!(cached, bytes);
+
+        let dir = tempfile::tempdir_in(data_dir()).unwrap();
+        let linked = link(&checksum, dir.path()).unwrap();
+        assert_eq!(fs::read(linked.path()).unwrap(), bytes);
+        assert_eq!(linked.path().parent(), Some(dir.path()));
+
+        // tampered entries are ignored
+        let entry = entry_path(&checksum);
+        drop(linked);
+        fs::remove_file(&entry).unwrap();
+        fs::write(&entry, b"something else").unwrap();
+        assert!(!matches(&entry, &checksum));
+        assert!(link(&checksum, dir.path()).is_none());
+        fs::remove_file(&entry).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn removes_unlinked_entries() {
+        crate::setup_data_dir().unwrap();
+        let bytes = b"zksolc binary to clean up";
+        let checksum = Sha256::digest(bytes).to_vec();
+        let source = tempfile::NamedTempFile::new().unwrap();
+        fs::write(source.path(), bytes).unwrap();
+        store(source.path(), &checksum);
+        let entry = entry_path(&checksum);
+
+        let dir = tempfile::tempdir_in(data_dir()).unwrap();
+        let installed = [dir.path().join("zksolc")];
+        link(&checksum, dir.path())
+            .unwrap()
+            .persist(&installed[0])
+            .unwrap();
+        if cfg!(unix) {
+            assert_eq!(linked_entries(&installed), std::slice::from_ref(&entry));
+            assert!(clean().unwrap().is_empty());
+            assert_eq!(fsutil::unshared_size(&installed).unwrap(), 0);
+        }
+
+        fs::remove_file(&installed[0]).unwrap();
+        assert_eq!(clean().unwrap(), [(entry.clone(), bytes.len() as u64)]);
+        assert!(!entry.exists());
+    }
+}
@@ -0,0 +1,250 @@
+use reqwest::StatusCode;
+use std::path::PathBuf;
+use thiserror::Error;
+use url::Url;
+
+// TODO: reconsider ZkvmErrors
+/// Error types from the svm_lib crate.
+#[derive(Debug, Error)]
+pub enum SvmError {
+    #[error("SVM global version not set")]
+    GlobalVersionNotSet,
+    #[error("Unknown version provided: it is not listed in the release list of this platform")]
+    UnknownVersion,
+    #[error("zksolc {version} is listed for {platform}, but only versions {supported} can be installed there; pick a version in that range")]
+    UnsupportedVersionRange {
+        version: String,
+        platform: String,
+        supported: String,
+    },
+    #[error("zksolc {version} requires zksvm {required} or newer, but this is zksvm {current}; update zksvm to install it")]
+    OutdatedManager {
+        version: String,
+        required: String,
+        current: String,
+    },
+    #[error("The GitHub API rate limit is exhausted, fetching {url} failed; it resets {}", reset.as_deref().map_or("within an hour".to_string(), |reset| format!("at {reset}")))]
+    GithubRateLimited {
+        url: Url,
+        reset: Option<String>,
+        authenticated: bool,
+    },
+    #[error("Invalid release source {0}: expected matter-labs, github:<owner>/<repo>, default or a base URL like https://mirror.example.com/zksolc")]
+    InvalidReleaseSource(String),
+    #[error("No zksolc builds are available for platform {0}")]
+    UnsupportedPlatform(String),
+    #[error("No zksolc release matches {0}")]
+    NoMatchingVersion(String),
+    #[error("No zksolc releases are available for platform {0}")]
+    NoReleases(String),
+    #[error("Version {0} not installed")]
+    VersionNotInstalled(String),
+    #[error("Version {0} is already installed; remove it first to replace it")]
+    VersionAlreadyInstalled(String),
+    #[error("Checksum mismatch for version {version}: expected: {expected}, actual: {actual}")]
+    ChecksumMismatch {
+        version: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("No checksum is available for zksolc {version}, so its download cannot be verified")]
+    MissingChecksum { version: String },
+    #[error("No zksvm {version} binary is published for {platform}")]
+    NoSelfUpdateAsset { version: String, platform: String },
+    #[error("No checksum is published for {0}, so the update cannot be verified")]
+    MissingSelfUpdateChecksum(String),
+    #[error("Download of {url} ended after {received} of {expected} bytes")]
+    CorruptDownload {
+        url: Url,
+        expected: u64,
+        received: u64,
+    },
+    #[error("The release list and the checksum file disagree on the checksum of version {version}: {index} vs {sibling}")]
+    ChecksumSourcesDisagree {
+        version: String,
+        index: String,
+        sibling: String,
+    },
+    #[error("Invalid checksum file {0}: expected `sha256sum` format")]
+    InvalidChecksumFile(Url),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("zksolc {0} has no signature that could be verified, and signatures are required")]
+    SignatureRequired(String),
+    #[error("Invalid signature key: {0}")]
+    InvalidSignatureKey(String),
+    #[error("Invalid artifact name in the release list: {0:?}")]
+    InvalidArtifactName(String),
+    #[error("{0} cannot block inside of this async runtime; use the async API instead")]
+    BlockingInAsyncContext(&'static str),
+    #[error("Install step for solc version {0} timed out after {1} seconds")]
+    Timeout(String, u64),
+    #[error("Operation did not complete within its deadline of {}", humantime::format_duration(*.0))]
+    DeadlineExceeded(std::time::Duration),
+    #[error("Gave up after {} waiting for another install holding {0}", humantime::format_duration(*.1))]
+    LockTimeout(PathBuf, std::time::Duration),
+    #[error("Gave up after {attempts} attempts")]
+    MaxRetriesExceeded {
+        attempts: u32,
+        #[source]
+        last: Box<SvmError>,
+    },
+    #[error(
+        "The zksolc {version} binary reports version {reported} instead, so it was not installed"
+    )]
+    InstalledVersionMismatch { version: String, reported: String },
+    #[error("The install of zksolc {version} stopped unexpectedly: {reason}")]
+    InstallTaskFailed { version: String, reason: String },
+    #[error("Unexpected `--version` output from {0}: {1:?}")]
+    InvalidVersionOutput(PathBuf, String),
+    #[error("Invalid version in ${0}: {1}")]
+    InvalidEnvVersion(&'static str, String),
+    #[error("{} is quarantined by Gatekeeper, which zksvm is not permitted to undo; run `xattr -d com.apple.quarantine {}` and try again", .0.display(), .0.display())]
+    QuarantineNotRemoved(PathBuf),
+    #[error("Invalid CA bundle {0}: {1}")]
+    InvalidCaBundle(PathBuf, String),
+    #[error("Invalid bundle {0}: {1}")]
+    InvalidBundle(PathBuf, String),
+    #[error("Invalid alias name {0:?}: aliases are words starting with a letter that cannot be mistaken for a version, like `stable`")]
+    InvalidAlias(String),
+    #[error("Invalid aliases file {0}: {1}")]
+    InvalidAliases(PathBuf, String),
+    #[error("Invalid usage stats file {0}: {1}")]
+    InvalidUsageStats(PathBuf, String),
+    #[error("Invalid project file {0}: {1}")]
+    InvalidProjectFile(PathBuf, String),
+    #[error("Invalid config file {0}: {1}")]
+    InvalidConfig(PathBuf, String),
+    #[error("Unknown config key {0:?}")]
+    UnknownConfigKey(String),
+    #[error("Invalid value for config key {key}: {reason}")]
+    InvalidConfigValue { key: String, reason: String },
+    #[error("{0} is in use by another process; close any running zksolc and try again")]
+    FileInUse(PathBuf),
+    #[error("{0} was blocked or quarantined by antivirus software; allow it or exclude the zksvm data directory and try again")]
+    Quarantined(PathBuf),
+    #[error("Access to {0} was denied; check its permissions, or whether antivirus software is blocking it")]
+    AccessDenied(PathBuf),
+    #[error("Refusing to remove {0}: it is outside of the zksvm data directory")]
+    PathOutsideDataDir(PathBuf),
+    #[error("Unable to patch solc binary for nixos. stdout: {0}. stderr: {1}")]
+    CouldNotPatchForNixOs(String, String),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    ReqwestError(reqwest::Error),
+    #[error("Could not resolve {host}: {reason}; check your network and proxy settings (HTTPS_PROXY), or set ZKSVM_IP_PREFERENCE=ipv4 or ipv6 on single-stack networks")]
+    DnsResolution { host: String, reason: String },
+    #[error(transparent)]
+    SemverError(#[from] semver::Error),
+    #[error(transparent)]
+    UrlError(#[from] url::ParseError),
+    #[error("Received unsuccessful response with code {1} for {0}")]
+    UnsuccessfulResponse(Url, StatusCode),
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    #[error(transparent)]
+    ZipError(#[from] zip::result::ZipError),
+}
+
+impl SvmError {
+    /// Returns a suggestion of how to resolve the error, phrased for users of the zksvm CLI.
+    ///
+    /// Errors whose message already says what to do have no hint.
+    pub fn hint(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::GlobalVersionNotSet => "run `zksvm use <version>` to set one",
+            Self::UnknownVersion | Self::NoMatchingVersion(_) => {
+                "run `zksvm list` to see supported versions"
+            }
+            Self::UnsupportedPlatform(_) | Self::NoReleases(_) => {
+                "run `zksvm platform` to see which platform zksvm detected"
+            }
+            Self::VersionNotInstalled(_) => "run `zksvm install <version>` to install it",
+            Self::MissingChecksum { .. } => {
+                "retry with `--refresh` to fetch the release list again, with `--checksum-file` to use the published checksum file, or with `--allow-unchecked` to install it anyway"
+            }
+            Self::ChecksumMismatch { .. } | Self::CorruptDownload { .. } => {
+                "the download was corrupted or tampered with; retry, and if it keeps failing report it to the zksolc maintainers"
+            }
+            Self::NoSelfUpdateAsset { .. } | Self::MissingSelfUpdateChecksum(_) => {
+                "reinstall with `cargo install --locked --git https://github.com/dutterbutter/zsvm-rs zksvm-cli`, or download a build from https://github.com/dutterbutter/zsvm-rs/releases"
+            }
+            Self::ChecksumSourcesDisagree { .. } => {
+                "retry with `--refresh` in case the release list is stale"
+            }
+            Self::Timeout(..) | Self::DeadlineExceeded(_) => {
+                "retry with a longer `--deadline`, or check your network connection"
+            }
+            Self::LockTimeout(..) => {
+                "wait for the other zksvm process to finish, or retry with a longer `--lock-timeout`"
+            }
+            Self::MaxRetriesExceeded { last, .. } => {
+                return last.hint().or(Some(
+                    "check your network connection, or retry with more `--attempts`",
+                ))
+            }
+            Self::ReqwestError(_) | Self::UnsuccessfulResponse(..) => {
+                "check your network connection and proxy settings, or retry with `--proxy <URL>`"
+            }
+            Self::InstalledVersionMismatch { .. } | Self::InvalidVersionOutput(..) => {
+                "pass `--no-verify` to install it anyway on platforms whose binaries cannot report their version"
+            }
+            Self::GithubRateLimited {
+                authenticated: false,
+                ..
+            } => "set `$GITHUB_TOKEN` to a GitHub token to raise the rate limit",
+            Self::GithubRateLimited { .. } => {
+                "wait for the rate limit to reset, or track another source with `$ZKSVM_RELEASES_SOURCE`"
+            }
+            Self::InvalidCaBundle(..) => "check the file `$ZKSVM_CA_BUNDLE` points to",
+            Self::InvalidUsageStats(..) => "run `zksvm stats --clear` to start over",
+            Self::InvalidConfig(..) => "fix the file, or point `$ZKSVM_CONFIG` to another one",
+            Self::UnknownConfigKey(_) => "run `zksvm config list` to see all keys",
+            _ => return None,
+        })
+    }
+}
+
+impl From<reqwest::Error> for SvmError {
+    fn from(err: reqwest::Error) -> Self {
+        match crate::http::dns_error(&err) {
+            Some(dns) => Self::DnsResolution {
+                host: dns.host.clone(),
+                reason: dns.reason.clone(),
+            },
+            None => Self::ReqwestError(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_at_remediation() {
+        assert_eq!(
+            SvmError::UnknownVersion.hint(),
+            Some("run `zksvm list` to see supported versions")
+        );
+        let retried = SvmError::MaxRetriesExceeded {
+            attempts: 3,
+            last: Box::new(SvmError::MissingChecksum {
+                version: "1.4.1".to_string(),
+            }),
+        };
+        assert_eq!(
+            retried.hint(),
+            SvmError::MissingChecksum {
+                version: String::new()
+            }
+            .hint()
+        );
+        let retried = SvmError::MaxRetriesExceeded {
+            attempts: 3,
+            last: Box::new(SvmError::InvalidSignature("bad".to_string())),
+        };
+        assert!(retried.hint().unwrap().contains("--attempts"));
+        assert_eq!(SvmError::InvalidSignature("bad".to_string()).hint(), None);
+    }
+}
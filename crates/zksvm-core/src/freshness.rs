@@ -0,0 +1,176 @@
+use crate::Releases;
+use semver::Version;
+use std::{
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+/// Environment variable overriding [`StalenessThresholds::max_index_age`].
+const ZKSVM_INDEX_MAX_AGE: &str = "ZKSVM_INDEX_MAX_AGE";
+
+/// Environment variable overriding [`StalenessThresholds::max_release_age`].
+const ZKSVM_RELEASE_MAX_AGE: &str = "ZKSVM_RELEASE_MAX_AGE";
+
+/// Thresholds after which release information is considered stale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StalenessThresholds {
+    /// How old a fetched release list may be before it should be refreshed.
+    pub max_index_age: Duration,
+    /// How old the newest known release may be before the source is considered suspicious.
+    pub max_release_age: Duration,
+}
+
+impl Default for StalenessThresholds {
+    fn default() -> Self {
+        Self {
+            max_index_age: Duration::from_secs(24 * 60 * 60),
+            max_release_age: Duration::from_secs(180 * 24 * 60 * 60),
+        }
+    }
+}
+
+impl StalenessThresholds {
+    /// Returns the default thresholds, overridden by `$ZKSVM_INDEX_MAX_AGE` and
+    /// `$ZKSVM_RELEASE_MAX_AGE` if set to a valid duration such as `12h` or `90days`.
+    pub fn from_env() -> Self {
+        let mut thresholds = Self::default();
+        if let Some(age) = env_duration(ZKSVM_INDEX_MAX_AGE) {
+            thresholds.max_index_age = age;
+        }
+        if let Some(age) = env_duration(ZKSVM_RELEASE_MAX_AGE) {
+            thresholds.max_release_age = age;
+        }
+        thresholds
+    }
+}
+
+fn env_duration(var: &str) -> Option<Duration> {
+    humantime::parse_duration(&std::env::var(var).ok()?).ok()
+}
+
+/// A sign that release information may be outdated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Staleness {
+    /// The release list was fetched longer ago than the configured threshold.
+    IndexOutdated { age: Duration },
+    /// The newest known release was published longer ago than the configured threshold, which
+    /// may indicate a broken or abandoned source URL.
+    NoRecentRelease { version: Version, age: Duration },
+}
+
+impl fmt::Display for Staleness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndexOutdated { age } => {
+                write!(f, "release list was fetched {} ago", format_age(*age))
+            }
+            Self::NoRecentRelease { version, age } => write!(
+                f,
+                "newest known release {version} was published {} ago; the release source may be outdated",
+                format_age(*age)
+            ),
+        }
+    }
+}
+
+/// Formats an age with day granularity, or hour granularity for ages below a day.
+fn format_age(age: Duration) -> String {
+    let hours = age.as_secs() / 3600;
+    if hours < 24 {
+        format!("{hours}h")
+    } else {
+        format!("{}d", hours / 24)
+    }
+}
+
+impl Releases {
+    /// Returns all signs that this release list may be outdated, according to `thresholds`.
+    pub fn staleness(&self, thresholds: &StalenessThresholds) -> Vec<Staleness> {
+        self.staleness_at(thresholds, SystemTime::now())
+    }
+
+    fn staleness_at(&self, thresholds: &StalenessThresholds, now: SystemTime) -> Vec<Staleness> {
+        let mut warnings = vec![];
+        if let Some(age) = self.fetched_at.and_then(|t| now.duration_since(t).ok()) {
+            if age > thresholds.max_index_age {
+                warnings.push(Staleness::IndexOutdated { age });
+            }
+        }
+        if let Some(latest) = self.latest_build() {
+            if let Some(age) = latest.published_at.and_then(|t| now.duration_since(t).ok()) {
+                if age > thresholds.max_release_age {
+                    warnings.push(Staleness::NoRecentRelease {
+                        version: latest.version.clone(),
+                        age,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::releases::BuildInfo;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn releases(fetched_at: SystemTime, published_at: Option<SystemTime>) -> Releases {
+        Releases {
+            builds: vec![
+                BuildInfo::new(Version::new(1, 3, 16), vec![]),
+                BuildInfo {
+                    published_at,
+                    ..BuildInfo::new(Version::new(1, 3, 17), vec![])
+                },
+            ],
+            releases: Default::default(),
+            fetched_at: Some(fetched_at),
+            min_version: None,
+        }
+    }
+
+    #[test]
+    fn fresh_releases() {
+        let now = SystemTime::now();
+        let releases = releases(now - DAY / 2, Some(now - 10 * DAY));
+        assert!(releases
+            .staleness_at(&StalenessThresholds::default(), now)
+            .is_empty());
+    }
+
+    #[test]
+    fn stale_releases() {
+        let now = SystemTime::now();
+        let releases = releases(now - 2 * DAY, Some(now - 365 * DAY));
+        let warnings = releases.staleness_at(&StalenessThresholds::default(), now);
+        assert_eq!(
+            warnings,
+            vec![
+                Staleness::IndexOutdated { age: 2 * DAY },
+                Staleness::NoRecentRelease {
+                    version: Version::new(1, 3, 17),
+                    age: 365 * DAY
+                }
+            ]
+        );
+        assert_eq!(warnings[0].to_string(), "release list was fetched 2d ago");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn thresholds_from_env() {
+        std::env::set_var(ZKSVM_INDEX_MAX_AGE, "2h");
+        std::env::set_var(ZKSVM_RELEASE_MAX_AGE, "not a duration");
+        let thresholds = StalenessThresholds::from_env();
+        std::env::remove_var(ZKSVM_INDEX_MAX_AGE);
+        std::env::remove_var(ZKSVM_RELEASE_MAX_AGE);
+        assert_eq!(thresholds.max_index_age, Duration::from_secs(2 * 60 * 60));
+        assert_eq!(
+            thresholds.max_release_age,
+            StalenessThresholds::default().max_release_age
+        );
+    }
+}
@@ -0,0 +1,348 @@
+//! Filesystem helpers for managing tool binaries, as used for zksolc itself.
+
+use crate::SvmError;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+use tempfile::{NamedTempFile, PersistError, TempPath};
+
+/// `ERROR_ACCESS_DENIED`
+const ERROR_ACCESS_DENIED: i32 = 5;
+/// `ERROR_SHARING_VIOLATION`
+const ERROR_SHARING_VIOLATION: i32 = 32;
+/// `ERROR_LOCK_VIOLATION`
+const ERROR_LOCK_VIOLATION: i32 = 33;
+/// `ERROR_VIRUS_INFECTED`
+const ERROR_VIRUS_INFECTED: i32 = 225;
+/// `ERROR_VIRUS_DELETED`
+const ERROR_VIRUS_DELETED: i32 = 226;
+
+/// How often moving a file into place is retried on transient sharing violations.
+const PERSIST_RETRIES: u32 = 5;
+
+/// The delay before the first retry, growing linearly with each attempt.
+const PERSIST_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Moves a temporary file to `dest`, retrying a few times if the destination is briefly locked
+/// by another process, e.g. a virus scanner inspecting a fresh binary on Windows.
+///
+/// Fails if `file` is on another filesystem than `dest`, see [`persist_or_copy`].
+pub fn persist(mut file: NamedTempFile, dest: &Path) -> Result<(), PersistError> {
+    let mut attempt = 0;
+    loop {
+        match file.persist(dest) {
+            Ok(_) => return Ok(()),
+            Err(err) if attempt < PERSIST_RETRIES && is_sharing_violation(&err.error) => {
+                attempt += 1;
+                thread::sleep(PERSIST_RETRY_DELAY * attempt);
+                file = err.file;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Moves a temporary file to `dest` like [`persist`], copying it next to `dest` first if it is on
+/// another filesystem, so that `dest` is replaced atomically either way.
+pub fn persist_or_copy(file: NamedTempFile, dest: &Path) -> Result<(), SvmError> {
+    let err = match persist(file, dest) {
+        Ok(()) => return Ok(()),
+        Err(err) => err,
+    };
+    let dir = parent_dir(dest);
+    let mut copy = NamedTempFile::new_in(dir).map_err(|_| file_error(err.error, dest))?;
+    io::copy(&mut fs::File::open(err.file.path())?, &mut copy)?;
+    copy.as_file()
+        .set_permissions(err.file.as_file().metadata()?.permissions())?;
+    copy.as_file().sync_all()?;
+    persist(copy, dest).map_err(|err| file_error(err.error, dest))
+}
+
+/// Moves a staged file to `dest` like [`persist`].
+pub(crate) fn persist_path(mut staged: TempPath, dest: &Path) -> Result<(), SvmError> {
+    let mut attempt = 0;
+    loop {
+        match staged.persist(dest) {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < PERSIST_RETRIES && is_sharing_violation(&err.error) => {
+                attempt += 1;
+                thread::sleep(PERSIST_RETRY_DELAY * attempt);
+                staged = err.path;
+            }
+            Err(err) => return Err(file_error(err.error, dest)),
+        }
+    }
+}
+
+/// Copies the file `src` to `dest` atomically, keeping its permissions: readers see either the
+/// old or the new contents of `dest`.
+pub fn copy_atomic(src: &Path, dest: &Path) -> Result<(), SvmError> {
+    persist_path(stage_copy(src, dest)?, dest)
+}
+
+/// Copies the file `src` next to `dest`, keeping its permissions, to be moved over `dest` with
+/// [`persist_path`].
+pub(crate) fn stage_copy(src: &Path, dest: &Path) -> Result<TempPath, SvmError> {
+    let dir = parent_dir(dest);
+    fs::create_dir_all(dir)?;
+    let mut copy = NamedTempFile::new_in(dir)?;
+    io::copy(&mut fs::File::open(src)?, &mut copy)?;
+    copy.as_file()
+        .set_permissions(fs::metadata(src)?.permissions())?;
+    copy.as_file().sync_all()?;
+    Ok(copy.into_temp_path())
+}
+
+/// Writes `contents` to `path` atomically: readers see either the old or the new contents, even
+/// if writing is interrupted.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), SvmError> {
+    persist_path(stage_write(path, contents)?, path)
+}
+
+/// Writes `contents` to a file next to `path`, to be moved over `path` with [`persist_path`].
+pub(crate) fn stage_write(path: &Path, contents: impl AsRef<[u8]>) -> Result<TempPath, SvmError> {
+    let mut staged = NamedTempFile::new_in(parent_dir(path))?;
+    staged.write_all(contents.as_ref())?;
+    staged.as_file().sync_all()?;
+    Ok(staged.into_temp_path())
+}
+
+/// Makes `link` point at the file `target`, replacing whatever is at `link` atomically, so that
+/// it stays usable while it is updated.
+///
+/// `link` becomes a symlink, or a hard link or copy of `target` where symlinks cannot be created,
+/// e.g. on Windows without developer mode, or across filesystems.
+pub fn link_atomic(target: &Path, link: &Path) -> Result<(), SvmError> {
+    persist_path(stage_link(target, link)?, link)
+}
+
+/// Creates a link to `target` like [`link_atomic`] next to `link`, to be moved over `link` with
+/// [`persist_path`].
+pub(crate) fn stage_link(target: &Path, link: &Path) -> Result<TempPath, SvmError> {
+    let dir = parent_dir(link);
+    fs::create_dir_all(dir)?;
+    // reserves a name next to `link` to stage the link at, which is then moved over `link`
+    let staged = tempfile::Builder::new()
+        .prefix(".link")
+        .tempfile_in(dir)?
+        .into_temp_path();
+    fs::remove_file(&staged)?;
+    #[cfg(unix)]
+    let symlinked = std::os::unix::fs::symlink(target, &staged);
+    #[cfg(windows)]
+    let symlinked = std::os::windows::fs::symlink_file(target, &staged);
+    #[cfg(not(any(unix, windows)))]
+    let symlinked = Err::<(), _>(io::Error::from(io::ErrorKind::Unsupported));
+    if symlinked.is_err() && fs::hard_link(target, &staged).is_err() {
+        fs::copy(target, &staged)?;
+    }
+    Ok(staged)
+}
+
+/// The extended attribute macOS Gatekeeper marks downloaded files with.
+#[cfg(target_os = "macos")]
+const QUARANTINE_ATTRIBUTE: &str = "com.apple.quarantine";
+
+/// Removes the quarantine macOS Gatekeeper puts on downloaded files, which stops binaries from
+/// being run with a "cannot be opened" error. Does nothing on other platforms.
+///
+/// Fails with [`SvmError::QuarantineNotRemoved`] if this is not permitted.
+pub fn remove_quarantine(path: &Path) -> Result<(), SvmError> {
+    #[cfg(target_os = "macos")]
+    if xattr::get(path, QUARANTINE_ATTRIBUTE)
+        .map_err(|err| file_error(err, path))?
+        .is_some()
+    {
+        xattr::remove(path, QUARANTINE_ATTRIBUTE).map_err(|err| match err.kind() {
+            io::ErrorKind::PermissionDenied => SvmError::QuarantineNotRemoved(path.to_path_buf()),
+            _ => file_error(err, path),
+        })?;
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = path;
+    Ok(())
+}
+
+/// Returns the directory `path` is in, which is the current directory for relative file names.
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+fn is_sharing_violation(err: &io::Error) -> bool {
+    cfg!(windows)
+        && matches!(
+            err.raw_os_error(),
+            Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+        )
+}
+
+/// Returns all files and directories below `dir`, sorted, without following symlinks.
+pub(crate) fn files_in(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                dirs.push(entry.path());
+            }
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the total size in bytes of the regular `files` that no other hard link points to,
+/// which is what removing them frees.
+///
+/// Link counts are only available on Unix, elsewhere every file counts.
+pub(crate) fn unshared_size(files: &[PathBuf]) -> io::Result<u64> {
+    let mut size = 0;
+    for path in files {
+        let meta = fs::symlink_metadata(path)?;
+        if meta.is_file() && link_count(&meta) <= 1 {
+            size += meta.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Returns the number of hard links to a file, or 1 where it is not known.
+#[cfg(unix)]
+pub(crate) fn link_count(meta: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.nlink()
+}
+
+#[cfg(not(unix))]
+pub(crate) fn link_count(_meta: &fs::Metadata) -> u64 {
+    1
+}
+
+/// Returns the total size in bytes of the files below `dir`, without following symlinks.
+pub(crate) fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for path in files_in(dir)? {
+        let meta = fs::symlink_metadata(&path)?;
+        if meta.is_file() {
+            size += meta.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Converts an error raised while writing `path` into an [`SvmError`], mapping well-known
+/// Windows errors to variants with remediation hints.
+pub fn file_error(err: io::Error, path: &Path) -> SvmError {
+    if cfg!(windows) {
+        if let Some(err) = err
+            .raw_os_error()
+            .and_then(|code| windows_error(code, path))
+        {
+            return err;
+        }
+    }
+    err.into()
+}
+
+fn windows_error(code: i32, path: &Path) -> Option<SvmError> {
+    match code {
+        ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION => {
+            Some(SvmError::FileInUse(path.to_path_buf()))
+        }
+        ERROR_VIRUS_INFECTED | ERROR_VIRUS_DELETED => {
+            Some(SvmError::Quarantined(path.to_path_buf()))
+        }
+        ERROR_ACCESS_DENIED => Some(SvmError::AccessDenied(path.to_path_buf())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_windows_errors() {
+        let path = Path::new("zksolc-1.3.17.exe");
+        assert!(matches!(
+            windows_error(ERROR_SHARING_VIOLATION, path),
+            Some(SvmError::FileInUse(_))
+        ));
+        assert!(matches!(
+            windows_error(ERROR_VIRUS_DELETED, path),
+            Some(SvmError::Quarantined(_))
+        ));
+        assert!(matches!(
+            windows_error(ERROR_ACCESS_DENIED, path),
+            Some(SvmError::AccessDenied(_))
+        ));
+        assert!(windows_error(2, path).is_none());
+    }
+
+    #[test]
+    fn persists_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = NamedTempFile::new_in(dir.path()).unwrap();
+        let dest = dir.path().join("zksolc");
+        persist(file, &dest).unwrap();
+        assert!(dest.exists());
+
+        let mut file = NamedTempFile::new_in(dir.path()).unwrap();
+        file.write_all(b"zksolc").unwrap();
+        persist_or_copy(file, &dest).unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), b"zksolc");
+    }
+
+    #[test]
+    fn links_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let (old, new) = (dir.path().join("zksolc-1"), dir.path().join("zksolc-2"));
+        fs::write(&old, "1").unwrap();
+        fs::write(&new, "2").unwrap();
+        let link = dir.path().join("current").join("zksolc");
+        link_atomic(&old, &link).unwrap();
+        assert_eq!(fs::read(&link).unwrap(), b"1");
+        link_atomic(&new, &link).unwrap();
+        assert_eq!(fs::read(&link).unwrap(), b"2");
+        assert_eq!(fs::read_dir(link.parent().unwrap()).unwrap().count(), 1);
+
+        copy_atomic(&old, &link).unwrap();
+        assert!(!fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert_eq!(fs::read(&link).unwrap(), b"1");
+    }
+
+    #[test]
+    fn removes_quarantine() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zksolc");
+        fs::write(&path, "zksolc").unwrap();
+        #[cfg(target_os = "macos")]
+        xattr::set(&path, QUARANTINE_ATTRIBUTE, b"0081;00000000;zksvm;").unwrap();
+        remove_quarantine(&path).unwrap();
+        // removing it again is fine
+        remove_quarantine(&path).unwrap();
+        #[cfg(target_os = "macos")]
+        assert_eq!(xattr::get(&path, QUARANTINE_ATTRIBUTE).unwrap(), None);
+    }
+
+    #[test]
+    fn writes_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.json");
+        write_atomic(&path, "{}").unwrap();
+        write_atomic(&path, b"{\"sha256\":\"\"}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"sha256\":\"\"}");
+        // nothing is left behind
+        assert_eq!(files_in(dir.path()).unwrap(), [path]);
+        assert!(write_atomic(&dir.path().join("missing").join("file"), "").is_err());
+    }
+}
@@ -0,0 +1,548 @@
+//! Release lists built from the GitHub Releases of a repository, see
+//! [`SourceLayout::GithubReleases`](crate::SourceLayout::GithubReleases).
+
+use crate::{
+    paths,
+    platform::{Libc, Platform},
+    releases::{platform_dir, BuildInfo, Channel, Releases},
+    SvmError, Tool,
+};
+use reqwest::{
+    header::{self, HeaderMap, HeaderValue},
+    StatusCode,
+};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+use url::Url;
+
+/// Environment variable with a GitHub token to authenticate API requests with, which raises the
+/// rate limit from 60 to 5000 requests per hour.
+pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+/// The repository zksolc is officially released from.
+pub(crate) const MATTER_LABS_REPO: &str = "matter-labs/era-compiler-solidity";
+
+/// The repository zkvyper is officially released from.
+pub(crate) const ZKVYPER_REPO: &str = "matter-labs/era-compiler-vyper";
+
+/// The API URL repositories are found below.
+const GITHUB_API_URL: &str = "https://api.github.com/repos/";
+
+/// How many releases are requested per page, the maximum GitHub allows.
+const PER_PAGE: usize = 100;
+
+/// The most pages of releases fetched, bounding the requests made for a single list.
+const MAX_PAGES: usize = 10;
+
+/// A release as returned by the GitHub Releases API.
+#[derive(Debug, Deserialize)]
+pub(crate) struct GithubRelease {
+    pub(crate) tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    published_at: Option<String>,
+    #[serde(default)]
+    pub(crate) assets: Vec<GithubAsset>,
+}
+
+/// A file attached to a [`GithubRelease`].
+#[derive(Debug, Deserialize)]
+pub(crate) struct GithubAsset {
+    pub(crate) name: String,
+    pub(crate) browser_download_url: String,
+    /// The checksum of the file, e.g. `sha256:<hex>`, for assets uploaded after GitHub started to
+    /// record them.
+    pub(crate) digest: Option<String>,
+    /// The size of the file in bytes.
+    size: Option<u64>,
+}
+
+/// Returns the API URL of `repo`, e.g. `matter-labs/era-compiler-solidity`.
+pub(crate) fn repo_url(repo: &str) -> Result<Url, SvmError> {
+    let repo = repo.trim_matches('/');
+    if repo.split('/').count() != 2 || repo.split('/').any(str::is_empty) {
+        return Err(SvmError::InvalidReleaseSource(format!("github:{repo}")));
+    }
+    Ok(Url::parse(GITHUB_API_URL)
+        .expect("valid API URL")
+        .join(&format!("{repo}/"))?)
+}
+
+/// Returns the URL of the releases of the repository at `base_url`.
+pub(crate) fn releases_url(base_url: &Url) -> Url {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .expect("checked by ReleaseSource::new")
+        .pop_if_empty()
+        .push("releases");
+    url
+}
+
+/// Downloads the releases of the repository at `base_url` and lists those of `channel` built
+/// for `platform`.
+pub(crate) async fn download_releases(
+    client: &reqwest::Client,
+    base_url: &Url,
+    platform: Platform,
+    tool: Tool,
+    channel: Channel,
+) -> Result<Releases, SvmError> {
+    let mut releases = vec![];
+    for page in 1..=MAX_PAGES {
+        let url = page_url(base_url, page);
+        let cached = read_page(&url);
+        let res = client
+            .get(url.clone())
+            .headers(request_headers(cached.as_ref()))
+            .send()
+            .await?;
+        let etag = res.headers().get(header::ETAG).cloned();
+        let body = match check_response(&url, res.status(), res.headers(), cached)? {
+            Some(body) => body,
+            None => cache_page(&url, etag, res.text().await?),
+        };
+        let page = parse_page(&body)?;
+        let last = page.len() < PER_PAGE;
+        releases.extend(page);
+        if last {
+            break;
+        }
+    }
+    Ok(list_releases(releases, platform, tool, channel))
+}
+
+/// Blocking version of [`download_releases`].
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_download_releases(
+    client: &reqwest::blocking::Client,
+    base_url: &Url,
+    platform: Platform,
+    tool: Tool,
+    channel: Channel,
+) -> Result<Releases, SvmError> {
+    let mut releases = vec![];
+    for page in 1..=MAX_PAGES {
+        let url = page_url(base_url, page);
+        let cached = read_page(&url);
+        let res = client
+            .get(url.clone())
+            .headers(request_headers(cached.as_ref()))
+            .send()?;
+        let etag = res.headers().get(header::ETAG).cloned();
+        let body = match check_response(&url, res.status(), res.headers(), cached)? {
+            Some(body) => body,
+            None => cache_page(&url, etag, res.text()?),
+        };
+        let page = parse_page(&body)?;
+        let last = page.len() < PER_PAGE;
+        releases.extend(page);
+        if last {
+            break;
+        }
+    }
+    Ok(list_releases(releases, platform, tool, channel))
+}
+
+fn page_url(base_url: &Url, page: usize) -> Url {
+    let mut url = releases_url(base_url);
+    url.query_pairs_mut()
+        .append_pair("per_page", &PER_PAGE.to_string())
+        .append_pair("page", &page.to_string());
+    url
+}
+
+/// A page of releases cached along with its ETag, so that it is only downloaded again once it
+/// changed. GitHub does not count such conditional requests against the rate limit.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CachedPage {
+    etag: String,
+    body: String,
+}
+
+/// Returns the token in `$GITHUB_TOKEN`, if set.
+fn token() -> Option<String> {
+    std::env::var(GITHUB_TOKEN)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// Returns the headers of a request for a page, which is conditional if it is `cached`.
+pub(crate) fn request_headers(cached: Option<&CachedPage>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::ACCEPT,
+        HeaderValue::from_static("application/vnd.github+json"),
+    );
+    if let Some(mut auth) =
+        token().and_then(|token| HeaderValue::from_str(&format!("Bearer {token}")).ok())
+    {
+        auth.set_sensitive(true);
+        headers.insert(header::AUTHORIZATION, auth);
+    }
+    if let Some(etag) = cached.and_then(|cached| HeaderValue::from_str(&cached.etag).ok()) {
+        headers.insert(header::IF_NONE_MATCH, etag);
+    }
+    headers
+}
+
+/// Checks the response to a request for the page at `url`, returning the `cached` body if it did
+/// not change, or `None` if the body of the response has to be read.
+///
+/// Fails with [`SvmError::GithubRateLimited`] if the rate limit is exhausted.
+pub(crate) fn check_response(
+    url: &Url,
+    status: StatusCode,
+    headers: &HeaderMap,
+    cached: Option<CachedPage>,
+) -> Result<Option<String>, SvmError> {
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some(cached) = cached {
+            return Ok(Some(cached.body));
+        }
+    }
+    if status.is_success() {
+        return Ok(None);
+    }
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    // secondary rate limits are reported with a retry-after header instead
+    let exhausted = header("x-ratelimit-remaining") == Some("0") || header("retry-after").is_some();
+    if matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) && exhausted
+    {
+        let reset = header("x-ratelimit-reset")
+            .and_then(|secs| secs.parse().ok())
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .or_else(|| {
+                let secs = header("retry-after")?.parse().ok()?;
+                Some(SystemTime::now() + Duration::from_secs(secs))
+            });
+        return Err(SvmError::GithubRateLimited {
+            url: url.clone(),
+            reset: reset.map(|reset| humantime::format_rfc3339_seconds(reset).to_string()),
+            authenticated: token().is_some(),
+        });
+    }
+    Err(SvmError::UnsuccessfulResponse(url.clone(), status))
+}
+
+fn parse_page(body: &str) -> Result<Vec<GithubRelease>, SvmError> {
+    Ok(serde_json::from_str(body).map_err(io::Error::from)?)
+}
+
+/// Returns the cache file of the page at `url`.
+///
+/// Its name starts like those of release lists, so that
+/// [`clear_releases_cache`](crate::clear_releases_cache) removes it as well.
+fn page_cache_path(url: &Url) -> PathBuf {
+    let digest = sha2::Sha256::digest(url.as_str());
+    paths::cache_dir().join(format!(
+        "releases-github-{}.json",
+        hex::encode(&digest[..8])
+    ))
+}
+
+fn read_page(url: &Url) -> Option<CachedPage> {
+    serde_json::from_slice(&fs::read(page_cache_path(url)).ok()?).ok()
+}
+
+/// Caches the page at `url` if the response had an ETag, best effort, and returns its `body`.
+fn cache_page(url: &Url, etag: Option<HeaderValue>, body: String) -> String {
+    let Some(etag) = etag.and_then(|etag| etag.to_str().ok().map(str::to_string)) else {
+        return body;
+    };
+    let page = CachedPage { etag, body };
+    if fs::create_dir_all(paths::cache_dir()).is_ok() {
+        if let Ok(json) = serde_json::to_vec(&page) {
+            let _ = crate::fsutil::write_atomic(&page_cache_path(url), &json);
+        }
+    }
+    page.body
+}
+
+/// Lists the assets of `tool` built for `platform` of the `releases` in `channel`.
+///
+/// Assets are recognized by their name, e.g. `zksolc-linux-amd64-musl-v1.5.7`, or
+/// `zksolc-macosx-v1.5.7` for universal macOS binaries, starting with the name of the tool.
+/// Assets without a recorded checksum are listed without a build, so installing them requires
+/// [`allow_unchecked`](crate::InstallOptions::allow_unchecked).
+pub(crate) fn list_releases(
+    releases: Vec<GithubRelease>,
+    platform: Platform,
+    tool: Tool,
+    channel: Channel,
+) -> Releases {
+    let mut list = Releases::default();
+    for release in releases {
+        if release.draft || (release.prerelease && channel == Channel::Stable) {
+            continue;
+        }
+        let Ok(version) = Version::parse(release.tag_name.trim_start_matches('v')) else {
+            continue;
+        };
+        if !channel.includes(&version) {
+            continue;
+        }
+        let published_at = release
+            .published_at
+            .and_then(|time| humantime::parse_rfc3339_weak(&time).ok());
+        let assets = release
+            .assets
+            .into_iter()
+            .filter(|asset| is_platform_asset(&asset.name, platform, tool))
+            .collect::<Vec<_>>();
+        let several = assets.len() > 1;
+        for asset in assets {
+            list.releases
+                .entry(version.clone())
+                .or_insert_with(|| asset.name.clone());
+            let Some(sha256) = asset.digest.as_deref().and_then(parse_digest) else {
+                continue;
+            };
+            list.builds.push(BuildInfo {
+                published_at,
+                libc: platform.is_linux().then(|| libc_of(&asset.name)).flatten(),
+                path: several.then(|| asset.name.clone()),
+                url: Some(asset.browser_download_url),
+                size: asset.size,
+                ..BuildInfo::new(version.clone(), sha256)
+            });
+        }
+    }
+    list
+}
+
+/// Returns whether the asset `name` is a binary of `tool` for `platform`.
+fn is_platform_asset(name: &str, platform: Platform, tool: Tool) -> bool {
+    let universal = matches!(platform, Platform::MacOsAmd64 | Platform::MacOsAarch64)
+        && name.starts_with(&format!("{tool}-macosx-v"));
+    let native = name.starts_with(&format!("{tool}-{}-", platform_dir(platform)));
+    let auxiliary = [".sha256", ".minisig", ".asc", ".sig", ".txt"]
+        .iter()
+        .any(|ext| name.ends_with(ext));
+    (universal || native) && !auxiliary
+}
+
+/// Returns the C library the Linux asset `name` is linked against, if its name tells.
+fn libc_of(name: &str) -> Option<Libc> {
+    if name.contains("-musl-") {
+        Some(Libc::Musl)
+    } else if name.contains("-gnu-") {
+        Some(Libc::Glibc)
+    } else {
+        None
+    }
+}
+
+/// Parses an asset digest like `sha256:<hex>`.
+pub(crate) fn parse_digest(digest: &str) -> Option<Vec<u8>> {
+    let sha256 = hex::decode(digest.strip_prefix("sha256:")?).ok()?;
+    (sha256.len() == 32).then_some(sha256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn github_releases() -> Vec<GithubRelease> {
+        let digest = format!("sha256:{}", "ab".repeat(32));
+        let download = "https://github.com/matter-labs/era-compiler-solidity/releases/download";
+        serde_json::from_value(serde_json::json!([
+            {
+                "tag_name": "1.5.8-rc.1",
+                "prerelease": true,
+                "assets": [{
+                    "name": "zksolc-linux-amd64-musl-v1.5.8-rc.1",
+                    "browser_download_url": format!("{download}/1.5.8-rc.1/zksolc-linux-amd64-musl-v1.5.8-rc.1"),
+                    "digest": digest,
+                }]
+            },
+            {
+                "tag_name": "1.5.7",
+                "published_at": "2024-11-04T10:00:00Z",
+                "assets": [
+                    {
+                        "name": "zksolc-linux-amd64-gnu-v1.5.7",
+                        "browser_download_url": format!("{download}/1.5.7/zksolc-linux-amd64-gnu-v1.5.7"),
+                        "digest": digest,
+                    },
+                    {
+                        "name": "zksolc-linux-amd64-musl-v1.5.7",
+                        "browser_download_url": format!("{download}/1.5.7/zksolc-linux-amd64-musl-v1.5.7"),
+                        "digest": digest,
+                    },
+                    {
+                        "name": "zksolc-linux-amd64-musl-v1.5.7.sha256",
+                        "browser_download_url": format!("{download}/1.5.7/zksolc-linux-amd64-musl-v1.5.7.sha256"),
+                    },
+                    {
+                        "name": "zksolc-macosx-v1.5.7",
+                        "browser_download_url": format!("{download}/1.5.7/zksolc-macosx-v1.5.7"),
+                    },
+                ]
+            },
+            {"tag_name": "not-a-version"},
+            {"tag_name": "1.5.6", "draft": true},
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_release_assets() {
+        let v157 = Version::new(1, 5, 7);
+
+        let linux = list_releases(
+            github_releases(),
+            Platform::LinuxAmd64,
+            Tool::Zksolc,
+            Channel::Stable,
+        );
+        assert_eq!(linux.releases.keys().collect::<Vec<_>>(), [&v157]);
+        assert_eq!(linux.builds.len(), 2);
+        assert!(linux.builds[0].published_at.is_some());
+        let musl = linux.for_libc(Libc::Musl);
+        assert_eq!(
+            musl.get_artifact(&v157).unwrap(),
+            "zksolc-linux-amd64-musl-v1.5.7"
+        );
+        assert!(musl.builds[0]
+            .url
+            .as_ref()
+            .unwrap()
+            .ends_with("/1.5.7/zksolc-linux-amd64-musl-v1.5.7"));
+
+        // universal macOS binaries serve both architectures, but have no recorded checksum
+        let macos = list_releases(
+            github_releases(),
+            Platform::MacOsAarch64,
+            Tool::Zksolc,
+            Channel::Stable,
+        );
+        assert_eq!(macos.get_artifact(&v157).unwrap(), "zksolc-macosx-v1.5.7");
+        assert_eq!(macos.get_checksum(&v157), None);
+
+        let prerelease = list_releases(
+            github_releases(),
+            Platform::LinuxAmd64,
+            Tool::Zksolc,
+            Channel::Prerelease,
+        );
+        assert_eq!(prerelease.releases.len(), 2);
+
+        // the assets of other tools are told apart by their name
+        let zkvyper = list_releases(
+            github_releases(),
+            Platform::LinuxAmd64,
+            Tool::Zkvyper,
+            Channel::Stable,
+        );
+        assert!(zkvyper.releases.is_empty());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn detects_rate_limits() {
+        let url = page_url(&repo_url(MATTER_LABS_REPO).unwrap(), 1);
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        header::HeaderName::from_static(name),
+                        HeaderValue::from_static(value),
+                    )
+                })
+                .collect::<HeaderMap>()
+        };
+        std::env::remove_var(GITHUB_TOKEN);
+        let limited = headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]);
+        match check_response(&url, StatusCode::FORBIDDEN, &limited, None) {
+            Err(err @ SvmError::GithubRateLimited { .. }) => {
+                assert!(err.to_string().contains("2023-11-14T22:13:20Z"), "{err}");
+                assert!(err.hint().unwrap().contains(GITHUB_TOKEN));
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+        std::env::set_var(GITHUB_TOKEN, "ghp_token");
+        assert!(matches!(
+            check_response(
+                &url,
+                StatusCode::TOO_MANY_REQUESTS,
+                &headers(&[("retry-after", "60")]),
+                None
+            ),
+            Err(SvmError::GithubRateLimited {
+                authenticated: true,
+                reset: Some(_),
+                ..
+            })
+        ));
+        let auth = request_headers(None);
+        assert_eq!(auth[header::AUTHORIZATION], "Bearer ghp_token");
+        std::env::remove_var(GITHUB_TOKEN);
+        assert!(!request_headers(None).contains_key(header::AUTHORIZATION));
+
+        // other denials are not mistaken for rate limits
+        assert!(matches!(
+            check_response(&url, StatusCode::FORBIDDEN, &HeaderMap::new(), None),
+            Err(SvmError::UnsuccessfulResponse(_, StatusCode::FORBIDDEN))
+        ));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn reuses_unmodified_pages() {
+        let url = page_url(&repo_url(MATTER_LABS_REPO).unwrap(), 1);
+        let etag = HeaderValue::from_static("\"abc\"");
+        assert_eq!(cache_page(&url, Some(etag), "[]".to_string()), "[]");
+        let cached = read_page(&url).unwrap();
+        assert_eq!(
+            request_headers(Some(&cached))[header::IF_NONE_MATCH],
+            "\"abc\""
+        );
+        assert_eq!(
+            check_response(
+                &url,
+                StatusCode::NOT_MODIFIED,
+                &HeaderMap::new(),
+                Some(cached)
+            )
+            .unwrap(),
+            Some("[]".to_string())
+        );
+        assert_eq!(
+            check_response(&url, StatusCode::OK, &HeaderMap::new(), read_page(&url)).unwrap(),
+            None
+        );
+
+        crate::clear_releases_cache().unwrap();
+        assert_eq!(read_page(&url), None);
+    }
+
+    #[test]
+    fn repo_urls() {
+        assert_eq!(
+            repo_url(MATTER_LABS_REPO).unwrap().as_str(),
+            "https://api.github.com/repos/matter-labs/era-compiler-solidity/"
+        );
+        assert_eq!(
+            releases_url(&repo_url("matter-labs/zksolc-bin").unwrap()).as_str(),
+            "https://api.github.com/repos/matter-labs/zksolc-bin/releases"
+        );
+        assert!(repo_url("matter-labs").is_err());
+        assert!(repo_url("matter-labs//zksolc-bin").is_err());
+    }
+}
@@ -0,0 +1,458 @@
+use crate::SvmError;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::{
+    env, fmt,
+    net::{SocketAddr, ToSocketAddrs},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// Environment variable restricting connections to `ipv4` or `ipv6` addresses.
+const ZKSVM_IP_PREFERENCE: &str = "ZKSVM_IP_PREFERENCE";
+
+/// Environment variable overriding how long resolving a host may take, e.g. `5s`.
+const ZKSVM_DNS_TIMEOUT: &str = "ZKSVM_DNS_TIMEOUT";
+
+/// The default upper bound on resolving a host.
+const DNS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Environment variable pointing at a PEM file of root certificates to trust in addition to the
+/// system's, e.g. those of a corporate proxy intercepting TLS.
+#[cfg(any(feature = "rustls", feature = "openssl"))]
+const ZKSVM_CA_BUNDLE: &str = "ZKSVM_CA_BUNDLE";
+
+/// The configuration set with [`set_client_config`].
+static CLIENT_CONFIG: RwLock<Option<ClientConfig>> = RwLock::new(None);
+
+tokio::task_local! {
+    /// The configuration of the [`SvmClient`](crate::SvmClient) running an async operation in
+    /// this task.
+    static TASK_CLIENT_CONFIG: ClientConfig;
+}
+
+/// The user agent requests are sent with unless [`ClientConfig::user_agent`] is set.
+const USER_AGENT: &str = concat!("zksvm/", env!("CARGO_PKG_VERSION"));
+
+/// The timeout of requests unless [`ClientConfig::timeout`] is set.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Settings for the HTTP clients zksvm fetches release lists and downloads binaries with.
+///
+/// Without a configuration, proxies are taken from `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and
+/// `NO_PROXY`. The system's root certificates are trusted, along with those in the PEM file at
+/// `$ZKSVM_CA_BUNDLE` if set.
+#[derive(Clone, Debug, Default)]
+pub struct ClientConfig {
+    proxy: Option<reqwest::Proxy>,
+    no_proxy: bool,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    #[cfg(any(feature = "rustls", feature = "openssl"))]
+    root_certificates: Vec<reqwest::Certificate>,
+}
+
+impl ClientConfig {
+    /// Creates a configuration with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends all requests through the proxy at `url`, e.g. `http://proxy.internal:3128`,
+    /// instead of the proxies in the environment. Hosts in `NO_PROXY` are still reached
+    /// directly.
+    pub fn proxy(mut self, url: &str) -> Result<Self, SvmError> {
+        let proxy = reqwest::Proxy::all(url)?.no_proxy(reqwest::NoProxy::from_env());
+        self.proxy = Some(proxy);
+        Ok(self)
+    }
+
+    /// Connects directly, ignoring the proxies in the environment.
+    pub fn no_proxy(mut self) -> Self {
+        self.no_proxy = true;
+        self
+    }
+
+    /// Gives up on requests that take longer than `timeout`, two minutes by default. Downloads
+    /// are bounded by it as a whole, so it must leave time for the largest binaries.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Gives up on connecting to a server after `timeout`, which is not bounded by default
+    /// except by the [`timeout`](Self::timeout) of the request.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends requests with `user_agent` instead of `zksvm/<version>`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Trusts the PEM encoded `certificates` in addition to the system's root certificates, e.g.
+    /// those of a proxy intercepting TLS.
+    #[cfg(any(feature = "rustls", feature = "openssl"))]
+    pub fn add_root_certificates(mut self, certificates: &[u8]) -> Result<Self, SvmError> {
+        self.root_certificates
+            .extend(reqwest::Certificate::from_pem_bundle(certificates)?);
+        Ok(self)
+    }
+}
+
+/// Sets the configuration of all HTTP clients zksvm creates from now on, e.g. for
+/// [`install`](crate::install()) and [`all_releases`](crate::all_releases).
+pub fn set_client_config(config: ClientConfig) {
+    *CLIENT_CONFIG.write().unwrap_or_else(|err| err.into_inner()) = Some(config);
+}
+
+/// Returns the configuration set with [`set_client_config`], or the default one, along with the
+/// certificates in `$ZKSVM_CA_BUNDLE`.
+///
+/// The default configuration sends requests through the `proxy` of the [`Config`](crate::Config)
+/// unless a proxy is set in the environment.
+fn client_config() -> Result<ClientConfig, SvmError> {
+    let config = match configured() {
+        Some(config) => config,
+        None => match crate::config::file_config().proxy {
+            Some(proxy) if !proxy_in_env() => ClientConfig::new().proxy(&proxy)?,
+            _ => ClientConfig::default(),
+        },
+    };
+    #[cfg(any(feature = "rustls", feature = "openssl"))]
+    if let Some(path) = env::var_os(ZKSVM_CA_BUNDLE).filter(|path| !path.is_empty()) {
+        return add_ca_bundle(config, std::path::Path::new(&path));
+    }
+    Ok(config)
+}
+
+/// Returns the configuration of the [`SvmClient`](crate::SvmClient) running the current task,
+/// otherwise the one set with [`set_client_config`], if any.
+fn configured() -> Option<ClientConfig> {
+    TASK_CLIENT_CONFIG
+        .try_with(ClientConfig::clone)
+        .ok()
+        .or_else(|| {
+            CLIENT_CONFIG
+                .read()
+                .unwrap_or_else(|err| err.into_inner())
+                .clone()
+        })
+}
+
+/// Runs `fut` with `config` as the configuration of all clients it creates.
+pub(crate) async fn scope_async<F: std::future::Future>(config: ClientConfig, fut: F) -> F::Output {
+    TASK_CLIENT_CONFIG.scope(config, fut).await
+}
+
+/// Returns the configuration of the [`SvmClient`](crate::SvmClient) running the current task, to
+/// pass on to the tasks it spawns.
+pub(crate) fn task_config() -> Option<ClientConfig> {
+    TASK_CLIENT_CONFIG.try_with(ClientConfig::clone).ok()
+}
+
+/// Returns the timeout of single requests, see [`ClientConfig::timeout`].
+pub(crate) fn request_timeout() -> Duration {
+    configured()
+        .and_then(|config| config.timeout)
+        .unwrap_or(REQUEST_TIMEOUT)
+}
+
+/// Returns whether a proxy is set in the environment.
+fn proxy_in_env() -> bool {
+    ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"]
+        .into_iter()
+        .flat_map(|var| [var.to_string(), var.to_ascii_lowercase()])
+        .any(|var| env::var_os(var).is_some_and(|value| !value.is_empty()))
+}
+
+/// Adds the certificates in the PEM file at `path` to `config`, failing if there are none.
+#[cfg(any(feature = "rustls", feature = "openssl"))]
+fn add_ca_bundle(config: ClientConfig, path: &std::path::Path) -> Result<ClientConfig, SvmError> {
+    let invalid = |reason: String| SvmError::InvalidCaBundle(path.to_path_buf(), reason);
+    let pem = std::fs::read(path).map_err(|err| invalid(err.to_string()))?;
+    let added = config.root_certificates.len();
+    let config = config
+        .add_root_certificates(&pem)
+        .map_err(|err| invalid(err.to_string()))?;
+    if config.root_certificates.len() == added {
+        return Err(invalid("it contains no certificates".to_string()));
+    }
+    Ok(config)
+}
+
+/// Returns a client builder resolving hosts with a [`Resolver`] configured from the environment,
+/// and applying the [`ClientConfig`].
+///
+/// All requests zksvm makes go through clients built from this, or [`blocking_client`].
+pub(crate) fn client() -> Result<reqwest::ClientBuilder, SvmError> {
+    Ok(configure(
+        reqwest::Client::builder().dns_resolver(Arc::new(Resolver::from_env())),
+        client_config()?,
+    ))
+}
+
+fn configure(mut builder: reqwest::ClientBuilder, config: ClientConfig) -> reqwest::ClientBuilder {
+    // GitHub rejects API requests without a user agent
+    builder = builder.user_agent(config.user_agent.as_deref().unwrap_or(USER_AGENT));
+    if let Some(timeout) = config.timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = config.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    if config.no_proxy {
+        builder = builder.no_proxy();
+    }
+    if let Some(proxy) = config.proxy {
+        builder = builder.proxy(proxy);
+    }
+    #[cfg(any(feature = "rustls", feature = "openssl"))]
+    for certificate in config.root_certificates {
+        builder = builder.add_root_certificate(certificate);
+    }
+    builder
+}
+
+/// Blocking version of [`client`].
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_client() -> Result<reqwest::blocking::ClientBuilder, SvmError> {
+    let config = client_config()?;
+    let mut builder = reqwest::blocking::Client::builder()
+        .dns_resolver(Arc::new(Resolver::from_env()))
+        .user_agent(config.user_agent.as_deref().unwrap_or(USER_AGENT));
+    if let Some(timeout) = config.timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = config.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    if config.no_proxy {
+        builder = builder.no_proxy();
+    }
+    if let Some(proxy) = config.proxy {
+        builder = builder.proxy(proxy);
+    }
+    #[cfg(any(feature = "rustls", feature = "openssl"))]
+    for certificate in config.root_certificates {
+        builder = builder.add_root_certificate(certificate);
+    }
+    Ok(builder)
+}
+
+/// Which address families to connect to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum IpPreference {
+    /// Use all resolved addresses in the order the system returns them.
+    #[default]
+    Any,
+    /// Only connect to IPv4 addresses, e.g. on hosts without IPv6 connectivity.
+    Ipv4,
+    /// Only connect to IPv6 addresses, e.g. on IPv6-only hosts.
+    Ipv6,
+}
+
+impl IpPreference {
+    fn from_env() -> Self {
+        match env::var(ZKSVM_IP_PREFERENCE).as_deref() {
+            Ok("ipv4" | "IPv4" | "4") => Self::Ipv4,
+            Ok("ipv6" | "IPv6" | "6") => Self::Ipv6,
+            _ => Self::Any,
+        }
+    }
+
+    fn allows(self, addr: &SocketAddr) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Ipv4 => addr.is_ipv4(),
+            Self::Ipv6 => addr.is_ipv6(),
+        }
+    }
+}
+
+/// A failure to resolve a host, reported as [`SvmError::DnsResolution`](crate::SvmError).
+#[derive(Debug)]
+pub(crate) struct DnsError {
+    pub(crate) host: String,
+    pub(crate) reason: String,
+}
+
+impl fmt::Display for DnsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to resolve {}: {}", self.host, self.reason)
+    }
+}
+
+impl std::error::Error for DnsError {}
+
+/// Resolves hosts with the system resolver, bounded by a timeout and filtered by address family.
+#[derive(Clone, Copy, Debug)]
+struct Resolver {
+    preference: IpPreference,
+    timeout: Duration,
+}
+
+impl Resolver {
+    fn from_env() -> Self {
+        let timeout = env::var(ZKSVM_DNS_TIMEOUT)
+            .ok()
+            .and_then(|timeout| humantime::parse_duration(&timeout).ok())
+            .unwrap_or(DNS_TIMEOUT);
+        Self {
+            preference: IpPreference::from_env(),
+            timeout,
+        }
+    }
+
+    async fn lookup(self, host: String) -> Result<Vec<SocketAddr>, DnsError> {
+        let error = |reason: String| DnsError {
+            host: host.clone(),
+            reason,
+        };
+        let name = host.clone();
+        let lookup = tokio::task::spawn_blocking(move || {
+            (name.as_str(), 0).to_socket_addrs().map(Iterator::collect)
+        });
+        let addrs: Vec<SocketAddr> = match tokio::time::timeout(self.timeout, lookup).await {
+            Err(_) => return Err(error(format!("timed out after {:?}", self.timeout))),
+            Ok(Err(err)) => return Err(error(err.to_string())),
+            Ok(Ok(Err(err))) => return Err(error(err.to_string())),
+            Ok(Ok(Ok(addrs))) => addrs,
+        };
+        let addrs = filter(addrs, self.preference);
+        if addrs.is_empty() {
+            return Err(error(format!(
+                "no addresses allowed by {ZKSVM_IP_PREFERENCE}"
+            )));
+        }
+        Ok(addrs)
+    }
+}
+
+fn filter(addrs: Vec<SocketAddr>, preference: IpPreference) -> Vec<SocketAddr> {
+    addrs
+        .into_iter()
+        .filter(|addr| preference.allows(addr))
+        .collect()
+}
+
+impl Resolve for Resolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = *self;
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = resolver.lookup(host).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Returns the DNS failure behind a request error, if that is what caused it.
+pub(crate) fn dns_error(err: &reqwest::Error) -> Option<&DnsError> {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(dns) = err.downcast_ref::<DnsError>() {
+            return Some(dns);
+        }
+        source = err.source();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_preference() {
+        let v4: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let v6: SocketAddr = "[::1]:0".parse().unwrap();
+        assert_eq!(filter(vec![v6, v4], IpPreference::Any), vec![v6, v4]);
+        assert_eq!(filter(vec![v6, v4], IpPreference::Ipv4), vec![v4]);
+        assert_eq!(filter(vec![v4], IpPreference::Ipv6), vec![]);
+    }
+
+    #[tokio::test]
+    async fn reports_unresolvable_hosts() {
+        let resolver = Resolver {
+            preference: IpPreference::Any,
+            timeout: Duration::from_secs(5),
+        };
+        let err = reqwest::Client::builder()
+            .dns_resolver(Arc::new(resolver))
+            .build()
+            .unwrap()
+            .get("https://zksvm.invalid/list.json")
+            .send()
+            .await
+            .unwrap_err();
+        match SvmError::from(err) {
+            SvmError::DnsResolution { host, .. } => assert_eq!(host, "zksvm.invalid"),
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_requests_through_the_proxy() {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy = format!("http://{}", listener.local_addr().unwrap());
+        let requested = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            BufReader::new(&stream).read_line(&mut request).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 7\r\n\r\nproxied")
+                .unwrap();
+            request
+        });
+
+        let config = ClientConfig::new().proxy(&proxy).unwrap();
+        let body = configure(reqwest::Client::builder(), config)
+            .build()
+            .unwrap()
+            .get("http://zksvm.invalid/list.json")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "proxied");
+        // the proxy resolves the host, which would fail locally
+        assert!(requested
+            .join()
+            .unwrap()
+            .starts_with("GET http://zksvm.invalid/list.json"));
+        assert!(ClientConfig::new().proxy("not a url").is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "rustls", feature = "openssl"))]
+    fn rejects_invalid_ca_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pem");
+        let empty = dir.path().join("empty.pem");
+        std::fs::write(&empty, "no certificates here\n").unwrap();
+        let garbled = dir.path().join("garbled.pem");
+        std::fs::write(
+            &garbled,
+            "-----BEGIN CERTIFICATE-----\nnot base64\n-----END CERTIFICATE-----\n",
+        )
+        .unwrap();
+
+        for path in [missing, empty, garbled] {
+            match add_ca_bundle(ClientConfig::new(), &path) {
+                Err(SvmError::InvalidCaBundle(invalid, _)) => assert_eq!(invalid, path),
+                res => panic!("unexpected result for {}: {res:?}", path.display()),
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{all_platform_artifacts, Platform, Releases, SvmError};
+use semver::Version;
+use sha2::Digest;
+use std::{fs, path::Path};
+
+/// Identifies an arbitrary zksolc binary by matching its checksum against the release lists of
+/// all platforms.
+///
+/// Returns the version and platform of the matching release, or `None` if the binary is not a
+/// known release, e.g. because it was built from source or modified. Release lists that cannot be
+/// fetched only fail the lookup if no other list knows the binary.
+pub async fn identify_binary(path: &Path) -> Result<Option<(Version, Platform)>, SvmError> {
+    let checksum = sha2::Sha256::digest(fs::read(path)?);
+    let artifacts = all_platform_artifacts().await?;
+    match find_checksum(&artifacts.releases, &checksum) {
+        Some(found) => Ok(Some(found)),
+        None => match artifacts.failed.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(None),
+        },
+    }
+}
+
+/// Returns the version and platform of the build with the given checksum, if any.
+fn find_checksum(indexes: &[(Platform, Releases)], checksum: &[u8]) -> Option<(Version, Platform)> {
+    indexes.iter().find_map(|(platform, releases)| {
+        releases
+            .builds
+            .iter()
+            .find(|build| build.sha256 == checksum)
+            .map(|build| (build.version.clone(), *platform))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BuildInfo;
+
+    fn releases(builds: &[(Version, &[u8])]) -> Releases {
+        Releases {
+            builds: builds
+                .iter()
+                .map(|(version, bytes)| {
+                    BuildInfo::new(version.clone(), sha2::Sha256::digest(bytes).to_vec())
+                })
+                .collect(),
+            releases: Default::default(),
+            fetched_at: None,
+            min_version: None,
+        }
+    }
+
+    #[test]
+    fn finds_matching_build() {
+        let indexes = [
+            (
+                Platform::LinuxAmd64,
+                releases(&[(Version::new(1, 3, 17), b"linux 1.3.17")]),
+            ),
+            (
+                Platform::MacOsAarch64,
+                releases(&[
+                    (Version::new(1, 3, 17), b"macos 1.3.17"),
+                    (Version::new(1, 4, 0), b"macos 1.4.0"),
+                ]),
+            ),
+        ];
+        assert_eq!(
+            find_checksum(&indexes, &sha2::Sha256::digest(b"macos 1.4.0")),
+            Some((Version::new(1, 4, 0), Platform::MacOsAarch64))
+        );
+        assert_eq!(
+            find_checksum(&indexes, &sha2::Sha256::digest(b"custom build")),
+            None
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    fsutil, install,
+    manifest::{self, CompilerInfo, Manifest},
+    paths, setup_data_dir, setup_version, state, version_binary, version_path, SvmError,
+};
+use semver::Version;
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A zksolc binary imported by [`import_binary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportedBinary {
+    /// The version the binary was installed as.
+    pub version: Version,
+    /// The path of the installed copy.
+    pub path: PathBuf,
+    /// The hex encoded SHA-256 checksum of the binary.
+    pub sha256: String,
+}
+
+/// Copies a local zksolc binary, e.g. a custom build, into the data directory so that it can be
+/// used like a downloaded version.
+///
+/// The binary is installed as `version`, or as the version it reports with `--version` if not
+/// set. Its checksum is recorded, so that [`verify_installed`](crate::verify_installed) detects
+/// later changes. Fails if the version is already installed.
+pub fn import_binary(path: &Path, version: Option<&Version>) -> Result<ImportedBinary, SvmError> {
+    let binary = fs::read(path)?;
+    let output = Command::new(path).arg("--version").output();
+    let stdout = output
+        .as_ref()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    let compiler = CompilerInfo::parse(&stdout);
+    let version = match (version, &compiler) {
+        (Some(version), _) => version.clone(),
+        (None, Some(compiler)) => compiler.version.clone(),
+        (None, None) => {
+            output?;
+            return Err(SvmError::InvalidVersionOutput(path.to_path_buf(), stdout));
+        }
+    };
+
+    setup_data_dir()?;
+    let _lock = state::lock_data_dir(true)?;
+    // an interrupted install or import of the version is replaced
+    if version_path(&version.to_string()).exists()
+        && !paths::incomplete_marker(&version.to_string()).exists()
+    {
+        return Err(SvmError::VersionAlreadyInstalled(version.to_string()));
+    }
+    setup_version(&version.to_string())?;
+    install::mark_incomplete(&version_path(&version.to_string()))?;
+    let dest = version_binary(&version.to_string());
+    let mut staged = tempfile::Builder::new()
+        .prefix(".zksolc-")
+        .tempfile_in(dest.parent().expect("binary is in a version directory"))?;
+    staged.write_all(&binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        staged
+            .as_file()
+            .set_permissions(fs::Permissions::from_mode(0o755))?;
+    }
+    staged.as_file().sync_all()?;
+    fsutil::persist(staged, &dest).map_err(|err| fsutil::file_error(err.error, &dest))?;
+    install::mark_complete(&version_path(&version.to_string()))?;
+
+    let sha256 = hex::encode(Sha256::digest(&binary));
+    let manifest = Manifest {
+        // what the binary reports, which may differ from the version it was imported as
+        compiler,
+        imported_from: Some(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())),
+        sha256: Some(sha256.clone()),
+    };
+    manifest::write_manifest(&version, &manifest)?;
+    Ok(ImportedBinary {
+        version,
+        path: dest,
+        sha256,
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{installed_versions, remove_version, Releases, VerificationResult};
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    #[serial_test::serial]
+    fn imports_local_builds() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zksolc");
+        fs::write(&path, "#!/bin/sh\necho 'zksolc v0.0.9 (LLVM build abc)'\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let imported = import_binary(&path, None).unwrap();
+        let version = Version::new(0, 0, 9);
+        assert_eq!(imported.version, version);
+        assert_eq!(fs::read(&imported.path).unwrap(), fs::read(&path).unwrap());
+        assert!(installed_versions().unwrap().contains(&version));
+        assert!(matches!(
+            import_binary(&path, None),
+            Err(SvmError::VersionAlreadyInstalled(_))
+        ));
+        assert_eq!(
+            crate::verify::verify_binary(&version, &Releases::default()).unwrap(),
+            VerificationResult::Verified
+        );
+        fs::write(&imported.path, "changed").unwrap();
+        assert!(matches!(
+            crate::verify::verify_binary(&version, &Releases::default()).unwrap(),
+            VerificationResult::Mismatch { .. }
+        ));
+
+        let custom = Version::parse("0.0.9+custom").unwrap();
+        assert_eq!(import_binary(&path, Some(&custom)).unwrap().version, custom);
+        assert_eq!(
+            crate::compiler_info(&custom).unwrap().version,
+            Version::new(0, 0, 9)
+        );
+
+        remove_version(&version).unwrap();
+        remove_version(&custom).unwrap();
+    }
+}
@@ -0,0 +1,2682 @@
+use crate::{
+    bundle, checksum, data_dir, download_cache, fsutil, http,
+    manifest::{self, Manifest},
+    paths,
+    platform::{self, Libc, Platform},
+    releases::{artifact_url, fetch_releases, Channel, ReleaseSource, Releases},
+    retry::{Retry, RetryPolicy},
+    setup_data_dir, setup_version, verify, SvmError, Tool, Verifier, Warning,
+};
+use reqwest::{
+    header::{self, HeaderMap},
+    StatusCode,
+};
+use semver::Version;
+use sha2::Digest;
+use std::{
+    fs,
+    io::{Seek, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use subtle::ConstantTimeEq;
+use tracing::{debug, info, warn};
+use url::Url;
+
+#[cfg(target_family = "unix")]
+use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+/// Options to customize a single installation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstallOptions {
+    /// The directory downloads are staged in before being moved into the data directory.
+    ///
+    /// Defaults to [`tmp_dir`](crate::tmp_dir) if not set.
+    pub tmp_dir: Option<PathBuf>,
+    /// Upper bound on the total time of the installation, including fetching the release list,
+    /// downloading and waiting for locks held by other installs.
+    ///
+    /// Exceeding it fails with [`SvmError::DeadlineExceeded`]. Unbounded if not set.
+    pub deadline: Option<Duration>,
+    /// Where to download the release list and artifacts from.
+    ///
+    /// Defaults to [`ReleaseSource::from_env`] if not set.
+    pub source: Option<ReleaseSource>,
+    /// How long to wait for another install of the same version to finish.
+    ///
+    /// Exceeding it fails with [`SvmError::LockTimeout`]. Unbounded if not set, though
+    /// [`deadline`](Self::deadline) still applies.
+    pub lock_timeout: Option<Duration>,
+    /// How to retry the download after transient network errors.
+    ///
+    /// Defaults to [`RetryPolicy::default`] if not set.
+    pub retry: Option<RetryPolicy>,
+    /// Also verify the download against the `<artifact>.sha256` file some mirrors publish next to
+    /// artifacts, in `sha256sum` format.
+    ///
+    /// A missing file is ignored. If the release list has a checksum as well, both must agree.
+    pub sibling_checksum: bool,
+    /// Verifies the detached signature of the download, taken from the release list or fetched
+    /// from next to the artifact.
+    ///
+    /// Downloads without a signature are accepted unless
+    /// [`require_signature`](Self::require_signature) is set.
+    pub verifier: Option<Verifier>,
+    /// Refuse to install downloads whose signature could not be verified.
+    ///
+    /// Fails with [`SvmError::SignatureRequired`] if there is no verifier or no signature.
+    pub require_signature: bool,
+    /// What to do when the release list declares that the version requires a newer zksvm.
+    pub outdated_manager: ManagerVersionPolicy,
+    /// The minimum time between [`InstallEvent::Downloading`] events, which are also reported
+    /// whenever another percent of the download arrived. Zero reports every received chunk.
+    ///
+    /// Defaults to [`DEFAULT_PROGRESS_INTERVAL`] if not set.
+    pub progress_interval: Option<Duration>,
+    /// Keep the quarantine macOS Gatekeeper may put on the binary, instead of removing it once
+    /// the download was verified. Quarantined binaries cannot be run until it is removed.
+    pub keep_quarantine: bool,
+    /// Install versions no checksum is available for, reporting [`Warning::Unchecked`] through
+    /// [`InstallEvent::Warning`] instead of failing with [`SvmError::MissingChecksum`].
+    pub allow_unchecked: bool,
+    /// Run `zksolc --version` on the installed binary, failing with
+    /// [`SvmError::InstalledVersionMismatch`] and removing the install again unless it reports
+    /// the version that was requested.
+    ///
+    /// Only the major, minor and patch versions are compared, as pre-release builds do not label
+    /// their version consistently.
+    pub verify_version: bool,
+    /// Install the binary built for this platform instead of the one for this machine, e.g. to
+    /// pre-populate a cache for another target. Platforms no binaries are published for get
+    /// those they can run under emulation, see [`Platform::artifacts`].
+    ///
+    /// Binaries of other platforms are kept in their
+    /// [`platform_version_path`](crate::platform_version_path), are not reported as installed and
+    /// skip [`verify_version`](Self::verify_version), as they cannot be run here.
+    pub platform: Option<Platform>,
+    /// Install the Linux build linked against this C library, for versions that are published
+    /// for several of them.
+    ///
+    /// Defaults to the C library of this machine, unless [`platform`](Self::platform) is set.
+    pub libc: Option<Libc>,
+    /// Fail with [`SvmError::UnknownVersion`] for versions without a build for the platform,
+    /// instead of installing the build of the platform it can run under emulation, e.g. the x64
+    /// build under Rosetta on Apple Silicon, reporting [`Warning::EmulatedBuild`].
+    pub native_only: bool,
+    /// The compiler to install, zksolc by default.
+    ///
+    /// Its releases are downloaded from [`Tool::source`] unless [`source`](Self::source) is set,
+    /// and installed in its [`tool_version_path`](crate::tool_version_path). Other tools than
+    /// zksolc skip [`verify_version`](Self::verify_version).
+    pub tool: Tool,
+}
+
+/// How often [`InstallEvent::Downloading`] is reported at most, unless another percent of the
+/// download arrived.
+pub const DEFAULT_PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+/// What to do when installing a zksolc version that requires a newer zksvm, as declared by
+/// [`BuildInfo::min_manager_version`](crate::BuildInfo::min_manager_version).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ManagerVersionPolicy {
+    /// Install it anyway, reporting [`Warning::OutdatedManager`] through
+    /// [`InstallEvent::Warning`].
+    #[default]
+    Warn,
+    /// Fail with [`SvmError::OutdatedManager`].
+    Refuse,
+}
+
+impl InstallOptions {
+    /// Sets the directory downloads are staged in.
+    pub fn tmp_dir(mut self, tmp_dir: impl Into<PathBuf>) -> Self {
+        self.tmp_dir = Some(tmp_dir.into());
+        self
+    }
+
+    /// Sets the upper bound on the total time of the installation.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets where to download the release list and artifacts from.
+    pub fn source(mut self, source: ReleaseSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Sets how long to wait for another install of the same version to finish.
+    pub fn lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = Some(lock_timeout);
+        self
+    }
+
+    /// Sets how to retry the download after transient network errors.
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets whether to also verify the download against the checksum file next to the artifact.
+    pub fn sibling_checksum(mut self, sibling_checksum: bool) -> Self {
+        self.sibling_checksum = sibling_checksum;
+        self
+    }
+
+    /// Sets how to verify the signature of the download.
+    pub fn verifier(mut self, verifier: Verifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Sets whether to refuse downloads whose signature could not be verified.
+    pub fn require_signature(mut self, require_signature: bool) -> Self {
+        self.require_signature = require_signature;
+        self
+    }
+
+    /// Sets what to do when the version requires a newer zksvm.
+    pub fn outdated_manager(mut self, outdated_manager: ManagerVersionPolicy) -> Self {
+        self.outdated_manager = outdated_manager;
+        self
+    }
+
+    /// Sets the minimum time between download progress events.
+    pub fn progress_interval(mut self, progress_interval: Duration) -> Self {
+        self.progress_interval = Some(progress_interval);
+        self
+    }
+
+    /// Sets whether to keep the quarantine macOS Gatekeeper may put on the binary.
+    pub fn keep_quarantine(mut self, keep_quarantine: bool) -> Self {
+        self.keep_quarantine = keep_quarantine;
+        self
+    }
+
+    /// Sets whether to install versions no checksum is available for.
+    pub fn allow_unchecked(mut self, allow_unchecked: bool) -> Self {
+        self.allow_unchecked = allow_unchecked;
+        self
+    }
+
+    /// Sets whether to check the version the installed binary reports.
+    pub fn verify_version(mut self, verify_version: bool) -> Self {
+        self.verify_version = verify_version;
+        self
+    }
+
+    /// Sets the platform to install the binary of.
+    pub fn platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    /// Sets the C library whose Linux build to install.
+    pub fn libc(mut self, libc: Libc) -> Self {
+        self.libc = Some(libc);
+        self
+    }
+
+    /// Sets whether to refuse installing builds of other platforms under emulation.
+    pub fn native_only(mut self, native_only: bool) -> Self {
+        self.native_only = native_only;
+        self
+    }
+
+    /// Sets the compiler to install.
+    pub fn tool(mut self, tool: Tool) -> Self {
+        self.tool = tool;
+        self
+    }
+
+    /// Returns the platform to install the binary of, falling back to the one of this machine.
+    fn resolved_platform(&self) -> Result<Platform, SvmError> {
+        match self.platform {
+            Some(platform) => platform.artifacts(),
+            None => platform::artifact_platform(),
+        }
+    }
+
+    /// Selects the builds of the C library to install from the release list of `platform`.
+    fn select_libc(&self, releases: Releases, platform: Platform) -> Releases {
+        if !platform.is_linux() {
+            return releases;
+        }
+        let libc = match self.platform {
+            Some(_) => self.libc,
+            None => self.libc.or_else(platform::libc),
+        };
+        match libc {
+            Some(libc) => releases.for_libc(libc),
+            None => releases,
+        }
+    }
+
+    /// Returns the platform whose build of `version` to install instead, if `releases` of
+    /// `platform` do not list it.
+    fn fallback_platform(
+        &self,
+        version: &Version,
+        platform: Platform,
+        releases: &Releases,
+    ) -> Option<Platform> {
+        if self.native_only || releases.get_artifact(version).is_some() {
+            return None;
+        }
+        platform.fallback()
+    }
+
+    /// Returns the release source to use for the tool, falling back to its default.
+    fn resolved_source(&self) -> Result<ReleaseSource, SvmError> {
+        match &self.source {
+            Some(source) => Ok(source.clone().with_tool(self.tool)),
+            None => self.tool.source(),
+        }
+    }
+
+    /// Returns the staging directory to use, falling back to the default.
+    fn resolved_tmp_dir(&self) -> PathBuf {
+        self.tmp_dir.clone().unwrap_or_else(paths::tmp_dir)
+    }
+}
+
+/// What had to be done to make a zksolc version available.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstallOutcome {
+    /// The version was downloaded and installed.
+    Installed,
+    /// The version was already installed.
+    AlreadyInstalled,
+    /// The version was already installed and is the global default.
+    AlreadyActive,
+}
+
+/// Something that happened during an install, reported to the callback of
+/// [`install_with_events`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InstallEvent {
+    /// Another process or task is installing the same version, and this install is waiting for
+    /// it to finish.
+    WaitingForLock {
+        version: Version,
+        lock_path: PathBuf,
+        /// The process id of the install holding the lock, if it recorded one.
+        pid: Option<u32>,
+        /// How long the install holding the lock has held it, if it recorded when it took it.
+        held_for: Option<Duration>,
+    },
+    /// The install lock was acquired after waiting for it.
+    LockAcquired { version: Version, waited: Duration },
+    /// Part of the binary was downloaded. `total` is `None` if the server did not announce the
+    /// size of the download.
+    Downloading { downloaded: u64, total: Option<u64> },
+    /// The download failed with a transient error and is retried after `delay`.
+    Retrying {
+        attempt: u32,
+        delay: Duration,
+        error: String,
+    },
+    /// Something is off, but the install goes ahead.
+    Warning(Warning),
+}
+
+/// Returns whether the provided version is already installed and whether it is the global
+/// default, or `None` if it is not installed.
+pub fn install_status(version: &Version) -> Result<Option<InstallOutcome>, SvmError> {
+    Ok(crate::LocalState::read()?.install_status(version))
+}
+
+/// Checks `current`, the version of zksvm, against the oldest version `required` to install
+/// zksolc `version`, returning the warning to report if it is too old but `policy` allows it.
+fn check_manager_version(
+    version: &Version,
+    required: Option<&Version>,
+    current: &Version,
+    policy: ManagerVersionPolicy,
+) -> Result<Option<Warning>, SvmError> {
+    let Some(required) = required.filter(|required| current < *required) else {
+        return Ok(None);
+    };
+    match policy {
+        ManagerVersionPolicy::Warn => Ok(Some(Warning::OutdatedManager {
+            version: version.clone(),
+            required: required.clone(),
+            current: current.clone(),
+        })),
+        ManagerVersionPolicy::Refuse => Err(SvmError::OutdatedManager {
+            version: version.to_string(),
+            required: required.to_string(),
+            current: current.to_string(),
+        }),
+    }
+}
+
+/// Returns the checksum to verify the download of `version` against, combining the one of the
+/// release list with the one of the checksum file, or `None` if neither has one but unchecked
+/// installs are allowed.
+fn expected_checksum(
+    version: &Version,
+    index: Option<Vec<u8>>,
+    sibling: Option<Vec<u8>>,
+    allow_unchecked: bool,
+) -> Result<Option<Vec<u8>>, SvmError> {
+    match checksum::reconcile(version, index, sibling)? {
+        Some(checksum) => Ok(Some(checksum)),
+        None if allow_unchecked => Ok(None),
+        None => Err(SvmError::MissingChecksum {
+            version: version.to_string(),
+        }),
+    }
+}
+
+/// Returns the version of this zksvm.
+fn manager_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("crate version is valid semver")
+}
+
+/// Tracks the remaining time of an operation bounded by [`InstallOptions::deadline`].
+#[derive(Clone, Copy, Debug)]
+struct Deadline {
+    started: Instant,
+    budget: Option<Duration>,
+}
+
+impl Deadline {
+    fn start(budget: Option<Duration>) -> Self {
+        Self {
+            started: Instant::now(),
+            budget,
+        }
+    }
+
+    /// Returns the remaining time, or an error if the deadline has passed.
+    fn remaining(&self) -> Result<Option<Duration>, SvmError> {
+        let Some(budget) = self.budget else {
+            return Ok(None);
+        };
+        match budget.checked_sub(self.started.elapsed()) {
+            Some(remaining) if !remaining.is_zero() => Ok(Some(remaining)),
+            _ => Err(SvmError::DeadlineExceeded(budget)),
+        }
+    }
+
+    /// Returns the timeout for the next request, bounded by the remaining time.
+    fn request_timeout(&self) -> Result<Duration, SvmError> {
+        Ok(self
+            .remaining()?
+            .map_or(http::request_timeout(), |remaining| {
+                remaining.min(http::request_timeout())
+            }))
+    }
+
+    /// Bounds the delay before a retry by the remaining time.
+    fn retry_delay(&self, delay: Duration) -> Result<Duration, SvmError> {
+        Ok(self
+            .remaining()?
+            .map_or(delay, |remaining| remaining.min(delay)))
+    }
+
+    /// Reports errors caused by running out of time, e.g. request timeouts, as
+    /// [`SvmError::DeadlineExceeded`].
+    fn check(&self, err: impl Into<SvmError>) -> SvmError {
+        self.remaining().err().unwrap_or_else(|| err.into())
+    }
+}
+
+/// Installs the provided version of zksolc unless it is already installed.
+pub async fn ensure_installed(
+    version: &Version,
+    opts: &InstallOptions,
+) -> Result<InstallOutcome, SvmError> {
+    if let Some(outcome) = install_status(version)? {
+        return Ok(outcome);
+    }
+    install_with_options(version, opts).await?;
+    Ok(InstallOutcome::Installed)
+}
+
+/// Installs exactly the provided version of zksolc unless it is already installed and intact.
+///
+/// Unlike [`ensure_installed`], the installed binary is checked against its published checksum
+/// and downloaded again if it is missing or does not match, so that provisioning scripts can run
+/// this as often as they like.
+pub async fn ensure_version_exact(version: &Version) -> Result<InstallOutcome, SvmError> {
+    ensure_version_exact_with_options(version, &InstallOptions::default()).await
+}
+
+/// [`ensure_version_exact`] with the provided [`InstallOptions`].
+pub async fn ensure_version_exact_with_options(
+    version: &Version,
+    opts: &InstallOptions,
+) -> Result<InstallOutcome, SvmError> {
+    if let Some(outcome) = install_status(version)? {
+        if !crate::verify_installed(version).await?.needs_repair() {
+            return Ok(outcome);
+        }
+    }
+    install_with_options(version, opts).await?;
+    Ok(InstallOutcome::Installed)
+}
+
+/// Blocking version of [`install`]
+#[cfg(feature = "blocking")]
+pub fn blocking_install(version: &Version) -> Result<PathBuf, SvmError> {
+    blocking_install_with_options(version, &InstallOptions::default())
+}
+
+/// Blocking version of [`install_with_options`]
+///
+/// Returns [`SvmError::BlockingInAsyncContext`] if called from within an async runtime, use
+/// [`install_blocking_in_place`] there instead.
+#[cfg(feature = "blocking")]
+pub fn blocking_install_with_options(
+    version: &Version,
+    opts: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    blocking_install_with_events(version, opts, |_| {})
+}
+
+/// Blocking version of [`install_with_events`]
+///
+/// Warnings and retries are reported to `on_event`, but neither download progress nor waiting
+/// for the install lock.
+#[cfg(feature = "blocking")]
+pub fn blocking_install_with_events(
+    version: &Version,
+    opts: &InstallOptions,
+    on_event: impl Fn(InstallEvent),
+) -> Result<PathBuf, SvmError> {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(SvmError::BlockingInAsyncContext("blocking_install"));
+    }
+    let deadline = Deadline::start(opts.deadline);
+    setup_data_dir()?;
+
+    let platform = opts.resolved_platform()?;
+    let source = opts.resolved_source()?.with_channel(Channel::of(version));
+    let artifacts =
+        crate::releases::blocking_fetch_releases(&source, platform, deadline.remaining()?)
+            .map_err(|err| deadline.check(err))?;
+    let artifacts = opts.select_libc(artifacts, platform);
+    if let Some(warning) = source.fallback_warning() {
+        on_event(InstallEvent::Warning(warning));
+    }
+    let (platform, artifacts) = match opts.fallback_platform(version, platform, &artifacts) {
+        Some(fallback) => {
+            match crate::releases::blocking_fetch_releases(&source, fallback, deadline.remaining()?)
+            {
+                Ok(emulated) if emulated.get_artifact(version).is_some() => {
+                    on_event(InstallEvent::Warning(Warning::EmulatedBuild {
+                        version: version.clone(),
+                        platform,
+                        artifacts: fallback,
+                    }));
+                    (fallback, emulated)
+                }
+                _ => (platform, artifacts),
+            }
+        }
+        None => (platform, artifacts),
+    };
+    let artifact = artifacts
+        .get_artifact(version)
+        .ok_or(SvmError::UnknownVersion)?;
+    if let Some(warning) = check_manager_version(
+        version,
+        artifacts.get_min_manager_version(version),
+        &manager_version(),
+        opts.outdated_manager,
+    )? {
+        on_event(InstallEvent::Warning(warning));
+    }
+    let download_url = artifact_url(&source, platform, version, &artifacts)?;
+    debug!(%platform, url = %download_url, "resolved the artifact");
+
+    let sibling = if opts.sibling_checksum {
+        checksum::blocking_fetch_sibling_checksum(
+            &download_url,
+            artifact,
+            deadline.request_timeout()?,
+        )
+        .map_err(|err| deadline.check(err))?
+    } else {
+        None
+    };
+    let expected_checksum = expected_checksum(
+        version,
+        artifacts.get_checksum(version),
+        sibling,
+        opts.allow_unchecked,
+    )?;
+    if expected_checksum.is_none() {
+        on_event(InstallEvent::Warning(Warning::Unchecked {
+            version: version.clone(),
+        }));
+    }
+
+    let tmp_dir = opts.resolved_tmp_dir();
+    let download = match expected_checksum
+        .as_deref()
+        .and_then(|checksum| cached_download(checksum, &tmp_dir))
+    {
+        Some(download) => download,
+        None => {
+            let policy = opts.retry.unwrap_or_default();
+            let mut attempt = 1;
+            let mut writer = DownloadWriter::for_checksum(&tmp_dir, expected_checksum.as_deref())?;
+            loop {
+                match blocking_download(&download_url, &deadline, &mut writer) {
+                    Ok(()) => break,
+                    Err(err) => match (err.to_string(), policy.on_error(attempt, err)) {
+                        (error, Retry::After(delay)) => {
+                            let delay = deadline.retry_delay(delay)?;
+                            on_event(InstallEvent::Retrying {
+                                attempt,
+                                delay,
+                                error,
+                            });
+                            std::thread::sleep(delay);
+                            attempt += 1;
+                        }
+                        (_, Retry::GiveUp(err)) => return Err(err),
+                    },
+                }
+            }
+            let download = writer.finish()?;
+            if let Some(expected_checksum) = &expected_checksum {
+                download.ensure_checksum(version, expected_checksum)?;
+                download_cache::store(download.path(), expected_checksum);
+            }
+            download
+        }
+    };
+
+    let signature = match (&opts.verifier, artifacts.get_signature(version)) {
+        (_, Some(signature)) => Some(signature.as_bytes().to_vec()),
+        (Some(verifier), None) => verify::blocking_fetch_signature(
+            &download_url,
+            verifier.extension(),
+            deadline.request_timeout()?,
+        )
+        .map_err(|err| deadline.check(err))?,
+        (None, None) => None,
+    };
+    check_signature(version, opts, &download, signature.as_deref())?;
+
+    // lock file to indicate that installation of this zksolc version will be in progress.
+    let lock_path = install_lock_path(version, opts)?;
+    // wait until lock file is released, possibly by another parallel thread trying to install the
+    // same version of zksolc.
+    let _lock = try_lock_file(lock_path, &deadline, opts.lock_timeout)?;
+
+    do_install(version, download, artifact.to_string().as_str(), opts)
+}
+
+/// Installs the provided version of zksolc from synchronous code that may or may not be running
+/// inside of a tokio runtime.
+///
+/// Inside of a multi-threaded runtime the current worker thread is handed off with
+/// [`tokio::task::block_in_place`], outside of a runtime a temporary one is created. A
+/// current-thread runtime cannot be blocked safely, so [`SvmError::BlockingInAsyncContext`] is
+/// returned instead of panicking.
+#[cfg(feature = "blocking")]
+pub fn install_blocking_in_place(
+    version: &Version,
+    opts: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    block_on_any_runtime(
+        "install_blocking_in_place",
+        install_with_options(version, opts),
+    )
+}
+
+/// Drives `fut` to completion from synchronous code, whether or not a runtime is already running.
+#[cfg(feature = "blocking")]
+fn block_on_any_runtime<T>(
+    caller: &'static str,
+    fut: impl std::future::Future<Output = Result<T, SvmError>>,
+) -> Result<T, SvmError> {
+    use tokio::runtime::{Builder, Handle, RuntimeFlavor};
+
+    match Handle::try_current() {
+        Ok(handle) => match handle.runtime_flavor() {
+            RuntimeFlavor::MultiThread => tokio::task::block_in_place(|| handle.block_on(fut)),
+            _ => Err(SvmError::BlockingInAsyncContext(caller)),
+        },
+        Err(_) => Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(fut),
+    }
+}
+
+/// Installs the provided version of zksolc in the machine.
+///
+/// Returns the path to the zksolc file.
+pub async fn install(version: &Version) -> Result<PathBuf, SvmError> {
+    install_with_options(version, &InstallOptions::default()).await
+}
+
+/// Installs the provided version of zksolc in the machine, using the given [`InstallOptions`].
+///
+/// Returns the path to the zksolc file.
+pub async fn install_with_options(
+    version: &Version,
+    opts: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    install_with_events(version, opts, |_| {}).await
+}
+
+/// Installs the provided version of zksolc like [`install`], then checks that the installed
+/// binary runs and reports that version.
+///
+/// See [`InstallOptions::verify_version`].
+pub async fn install_verified(version: &Version) -> Result<PathBuf, SvmError> {
+    install_with_options(version, &InstallOptions::default().verify_version(true)).await
+}
+
+/// Installs the binary of the provided version of zksolc built for `platform`, which may differ
+/// from the platform of this machine.
+///
+/// See [`InstallOptions::platform`].
+pub async fn install_for_platform(
+    version: &Version,
+    platform: Platform,
+) -> Result<PathBuf, SvmError> {
+    install_with_options(version, &InstallOptions::default().platform(platform)).await
+}
+
+/// The number of versions [`install_many`] downloads at the same time.
+pub const DEFAULT_INSTALL_CONCURRENCY: usize = 4;
+
+/// Installs all provided versions of zksolc, downloading up to [`DEFAULT_INSTALL_CONCURRENCY`]
+/// of them at the same time.
+///
+/// Returns the result of each install, in the order of `versions`. One failing install does not
+/// stop the others.
+pub async fn install_many(versions: &[Version]) -> Vec<Result<PathBuf, SvmError>> {
+    install_many_with_events(
+        versions,
+        &InstallOptions::default(),
+        DEFAULT_INSTALL_CONCURRENCY,
+        |_, _| {},
+    )
+    .await
+}
+
+/// Installs all provided versions of zksolc, downloading up to `concurrency` of them at the same
+/// time and reporting the [`InstallEvent`]s of each version to `on_event`.
+///
+/// Returns the result of each install, in the order of `versions`.
+pub async fn install_many_with_events(
+    versions: &[Version],
+    opts: &InstallOptions,
+    concurrency: usize,
+    on_event: impl Fn(&Version, InstallEvent) + Send + Sync + 'static,
+) -> Vec<Result<PathBuf, SvmError>> {
+    let on_event = Arc::new(on_event);
+    let permits = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+    for (i, version) in versions.iter().cloned().enumerate() {
+        let (opts, on_event, permits) = (opts.clone(), on_event.clone(), permits.clone());
+        paths::spawn_scoped(&mut tasks, async move {
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .expect("semaphore is never closed");
+            let res = install_with_events(&version, &opts, |event| on_event(&version, event)).await;
+            (i, res)
+        });
+    }
+
+    join_installs(versions, tasks).await
+}
+
+/// Collects the results of the install `tasks` of `versions`, which report the index of their
+/// version along with their result.
+///
+/// A task that panicked or was cancelled fails its version without affecting the others.
+async fn join_installs(
+    versions: &[Version],
+    mut tasks: tokio::task::JoinSet<(usize, Result<PathBuf, SvmError>)>,
+) -> Vec<Result<PathBuf, SvmError>> {
+    let mut results = versions.iter().map(|_| None).collect::<Vec<_>>();
+    // a failed task does not say which version it installed, so each version without a result
+    // reports all failures
+    let mut failures = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((i, res)) => results[i] = Some(res),
+            Err(err) => failures.push(err.to_string()),
+        }
+    }
+    versions
+        .iter()
+        .zip(results)
+        .map(|(version, res)| {
+            res.unwrap_or_else(|| {
+                Err(SvmError::InstallTaskFailed {
+                    version: version.to_string(),
+                    reason: failures.join("; "),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Installs the provided version of zksolc in the machine, calling `on_progress` with the number
+/// of bytes downloaded so far and the total size of the download as it progresses.
+///
+/// The total is 0 if the server did not announce the size of the download.
+///
+/// Returns the path to the zksolc file.
+pub async fn install_with_progress(
+    version: &Version,
+    on_progress: impl Fn(u64, u64),
+) -> Result<PathBuf, SvmError> {
+    install_with_events(version, &InstallOptions::default(), |event| {
+        if let InstallEvent::Downloading { downloaded, total } = event {
+            on_progress(downloaded, total.unwrap_or_default())
+        }
+    })
+    .await
+}
+
+/// Installs the provided version of zksolc in the machine, reporting [`InstallEvent`]s to
+/// `on_event` as they happen.
+///
+/// Waiting for another install of the same version does not block the async runtime.
+///
+/// Returns the path to the zksolc file.
+#[tracing::instrument(skip_all, fields(%version, tool = %opts.tool))]
+pub async fn install_with_events(
+    version: &Version,
+    opts: &InstallOptions,
+    on_event: impl Fn(InstallEvent),
+) -> Result<PathBuf, SvmError> {
+    let deadline = Deadline::start(opts.deadline);
+    setup_data_dir()?;
+
+    let platform = opts.resolved_platform()?;
+    let source = opts.resolved_source()?.with_channel(Channel::of(version));
+    let artifacts = fetch_releases(&source, platform, deadline.remaining()?)
+        .await
+        .map_err(|err| deadline.check(err))?;
+    let artifacts = opts.select_libc(artifacts, platform);
+    if let Some(warning) = source.fallback_warning() {
+        on_event(InstallEvent::Warning(warning));
+    }
+    let (platform, artifacts) = match opts.fallback_platform(version, platform, &artifacts) {
+        Some(fallback) => match fetch_releases(&source, fallback, deadline.remaining()?).await {
+            Ok(emulated) if emulated.get_artifact(version).is_some() => {
+                on_event(InstallEvent::Warning(Warning::EmulatedBuild {
+                    version: version.clone(),
+                    platform,
+                    artifacts: fallback,
+                }));
+                (fallback, emulated)
+            }
+            _ => (platform, artifacts),
+        },
+        None => (platform, artifacts),
+    };
+    let artifact = artifacts
+        .releases
+        .get(version)
+        .ok_or(SvmError::UnknownVersion)?;
+    if let Some(warning) = check_manager_version(
+        version,
+        artifacts.get_min_manager_version(version),
+        &manager_version(),
+        opts.outdated_manager,
+    )? {
+        on_event(InstallEvent::Warning(warning));
+    }
+    let download_url = artifact_url(&source, platform, version, &artifacts)?;
+    debug!(%platform, url = %download_url, "resolved the artifact");
+
+    let sibling = if opts.sibling_checksum {
+        checksum::fetch_sibling_checksum(&download_url, artifact, deadline.request_timeout()?)
+            .await
+            .map_err(|err| deadline.check(err))?
+    } else {
+        None
+    };
+    let expected_checksum = expected_checksum(
+        version,
+        artifacts.get_checksum(version),
+        sibling,
+        opts.allow_unchecked,
+    )?;
+    if expected_checksum.is_none() {
+        on_event(InstallEvent::Warning(Warning::Unchecked {
+            version: version.clone(),
+        }));
+    }
+
+    install_artifact(
+        version,
+        &download_url,
+        artifact,
+        expected_checksum.as_deref(),
+        artifacts.get_signature(version),
+        opts,
+        &deadline,
+        on_event,
+    )
+    .await
+}
+
+/// Installs `version` from `url` instead of the artifact in the release list, e.g. from a custom
+/// mirror or a pinned artifact URL.
+///
+/// The download must match `expected_sha256`, the raw SHA-256 checksum of the binary. It is
+/// installed like any other version, under the same locks and in the same layout.
+///
+/// Returns the path to the zksolc file.
+pub async fn install_from_url(
+    version: &Version,
+    url: &Url,
+    expected_sha256: &[u8],
+) -> Result<PathBuf, SvmError> {
+    install_from_url_with_options(version, url, expected_sha256, &InstallOptions::default()).await
+}
+
+/// Installs `version` from `url`, using the given [`InstallOptions`].
+///
+/// [`InstallOptions::source`], [`InstallOptions::sibling_checksum`] and
+/// [`InstallOptions::outdated_manager`] are ignored, as the release list is not used. A
+/// signature is fetched from next to `url` if [`InstallOptions::verifier`] is set.
+///
+/// Returns the path to the zksolc file.
+#[tracing::instrument(skip_all, fields(%version, %url))]
+pub async fn install_from_url_with_options(
+    version: &Version,
+    url: &Url,
+    expected_sha256: &[u8],
+    opts: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    let deadline = Deadline::start(opts.deadline);
+    setup_data_dir()?;
+    let artifact = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_default();
+    install_artifact(
+        version,
+        url,
+        artifact,
+        Some(expected_sha256),
+        None,
+        opts,
+        &deadline,
+        |_| {},
+    )
+    .await
+}
+
+/// Downloads the `artifact` of `version` from `url`, unless it is in the download cache, checks
+/// it against `expected_checksum`, unless there is none, and its signature, and installs it.
+#[allow(clippy::too_many_arguments)]
+async fn install_artifact(
+    version: &Version,
+    url: &Url,
+    artifact: &str,
+    expected_checksum: Option<&[u8]>,
+    signature: Option<&str>,
+    opts: &InstallOptions,
+    deadline: &Deadline,
+    on_event: impl Fn(InstallEvent),
+) -> Result<PathBuf, SvmError> {
+    let tmp_dir = opts.resolved_tmp_dir();
+    let download = match expected_checksum.and_then(|checksum| cached_download(checksum, &tmp_dir))
+    {
+        Some(download) => {
+            debug!(artifact, "using the cached download");
+            download
+        }
+        None => {
+            let mut writer = DownloadWriter::for_checksum(&tmp_dir, expected_checksum)?;
+            download_with_retries(url, opts, deadline, &on_event, &mut writer).await?;
+            let download = writer.finish()?;
+            if let Some(expected_checksum) = expected_checksum {
+                download.ensure_checksum(version, expected_checksum)?;
+                download_cache::store(download.path(), expected_checksum);
+            }
+            download
+        }
+    };
+
+    let signature = match (&opts.verifier, signature) {
+        (_, Some(signature)) => Some(signature.as_bytes().to_vec()),
+        (Some(verifier), None) => {
+            verify::fetch_signature(url, verifier.extension(), deadline.request_timeout()?)
+                .await
+                .map_err(|err| deadline.check(err))?
+        }
+        (None, None) => None,
+    };
+    check_signature(version, opts, &download, signature.as_deref())?;
+
+    // lock file to indicate that installation of this zksolc version will be in progress.
+    let lock_path = install_lock_path(version, opts)?;
+    // wait until lock file is released, possibly by another parallel thread trying to install the
+    // same version of zksolc.
+    let _lock = lock_file(lock_path, version, deadline, opts.lock_timeout, on_event).await?;
+
+    do_install(version, download, artifact, opts)
+}
+
+/// Installs the versions in a bundle written by [`export_bundle`](crate::export_bundle), without
+/// network access, returning what had to be done for each of them.
+///
+/// Each binary is verified against the checksum in the bundle, which is recorded so that
+/// [`verify_installed`](crate::verify_installed) works offline as well. Versions that are already
+/// installed are left as they are.
+pub fn install_bundle(path: &Path) -> Result<Vec<(Version, InstallOutcome)>, SvmError> {
+    setup_data_dir()?;
+    let tmp_dir = InstallOptions::default().resolved_tmp_dir();
+    let mut outcomes = vec![];
+    bundle::read_bundle(path, platform::artifact_platform()?, |bundled, binbytes| {
+        let version = &bundled.version;
+        if let Some(outcome) = install_status(version)? {
+            outcomes.push((version.clone(), outcome));
+            return Ok(());
+        }
+        let checksum = hex::decode(&bundled.sha256).map_err(|_| {
+            SvmError::InvalidBundle(
+                path.to_path_buf(),
+                format!("invalid checksum for {version}: {}", bundled.sha256),
+            )
+        })?;
+        ensure_checksum(&binbytes, version, &checksum)?;
+        let download = Download::from_bytes(&binbytes, &tmp_dir)?;
+
+        let _lock = crate::state::lock_data_dir(true)?;
+        setup_version(&version.to_string())?;
+        // the bundled file name is named like the binary, which starts like its artifact
+        let file_name = bundled.path.rsplit('/').next().unwrap_or_default();
+        Installer {
+            version,
+            dir: &crate::version_path(&version.to_string()),
+            name: &paths::binary_name(file_name),
+            download,
+        }
+        .install()
+        .and_then(|zksolc_path| fsutil::remove_quarantine(&zksolc_path))?;
+        let manifest = Manifest {
+            sha256: Some(bundled.sha256.clone()),
+            ..bundled.manifest.clone()
+        };
+        manifest::write_manifest(version, &manifest)?;
+        outcomes.push((version.clone(), InstallOutcome::Installed));
+        Ok(())
+    })?;
+    Ok(outcomes)
+}
+
+/// Downloads `url` to `writer`, retrying transient failures as `opts` allows before `deadline`.
+async fn download_with_retries(
+    url: &Url,
+    opts: &InstallOptions,
+    deadline: &Deadline,
+    on_event: &impl Fn(InstallEvent),
+    writer: &mut DownloadWriter,
+) -> Result<(), SvmError> {
+    let policy = opts.retry.unwrap_or_default();
+    let interval = opts.progress_interval.unwrap_or(DEFAULT_PROGRESS_INTERVAL);
+    let mut attempt = 1;
+    loop {
+        debug!(%url, attempt, "downloading");
+        match download(url, deadline, interval, on_event, writer).await {
+            Ok(()) => return Ok(()),
+            Err(err) => match (err.to_string(), policy.on_error(attempt, err)) {
+                (error, Retry::After(delay)) => {
+                    let delay = deadline.retry_delay(delay)?;
+                    warn!(attempt, ?delay, %error, "download failed, retrying");
+                    on_event(InstallEvent::Retrying {
+                        attempt,
+                        delay,
+                        error,
+                    });
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                (_, Retry::GiveUp(err)) => {
+                    warn!(attempt, %err, "download failed, giving up");
+                    return Err(err);
+                }
+            },
+        }
+    }
+}
+
+/// Downloads `url` to a temporary file in `dir` and checks it against `expected_checksum`, named
+/// `name` in errors.
+///
+/// Like the binaries of installs, it is streamed to disk, retried as `opts` allows and bounded
+/// by its [`deadline`](InstallOptions::deadline).
+#[cfg(feature = "self-update")]
+pub(crate) async fn download_checked(
+    url: &Url,
+    dir: &Path,
+    name: &str,
+    expected_checksum: &[u8],
+    opts: &InstallOptions,
+) -> Result<tempfile::NamedTempFile, SvmError> {
+    let deadline = Deadline::start(opts.deadline);
+    let mut writer = DownloadWriter::new(dir)?;
+    download_with_retries(url, opts, &deadline, &|_| {}, &mut writer).await?;
+    let download = writer.finish()?;
+    compare_checksums(&download.sha256, name, expected_checksum)?;
+    Ok(download.file)
+}
+
+/// Downloads `url` chunk by chunk to `writer`, reporting the progress to `on_event`.
+///
+/// If `writer` holds part of the download already, e.g. from an attempt that was interrupted,
+/// only the rest is requested. Servers that do not support range requests send everything again.
+async fn download(
+    url: &Url,
+    deadline: &Deadline,
+    progress_interval: Duration,
+    on_event: &impl Fn(InstallEvent),
+    writer: &mut DownloadWriter,
+) -> Result<(), SvmError> {
+    let mut res = request(url, deadline, writer.len).await?;
+    if continue_partial(writer, res.status(), res.headers())? {
+        res = request(url, deadline, 0).await?;
+    }
+    if !res.status().is_success() {
+        return Err(SvmError::UnsuccessfulResponse(url.clone(), res.status()));
+    }
+
+    let total = res.content_length().map(|len| len + writer.len);
+    let mut downloaded = writer.len;
+    on_event(InstallEvent::Downloading { downloaded, total });
+    let mut progress = ProgressThrottle::new(progress_interval, Instant::now());
+    while let Some(chunk) = res.chunk().await.map_err(|err| deadline.check(err))? {
+        writer.write_all(&chunk)?;
+        downloaded = writer.len;
+        if progress.is_due(downloaded, total, Instant::now()) {
+            on_event(InstallEvent::Downloading { downloaded, total });
+        }
+    }
+    // the end of the download is always reported, so that consumers see it complete
+    if progress.reported != downloaded {
+        on_event(InstallEvent::Downloading { downloaded, total });
+    }
+    ensure_complete(url, total, downloaded)
+}
+
+/// Requests `url`, only asking for the bytes after the first `resume_from` if it is not zero.
+async fn request(
+    url: &Url,
+    deadline: &Deadline,
+    resume_from: u64,
+) -> Result<reqwest::Response, SvmError> {
+    let mut req = http::client()?
+        .timeout(deadline.request_timeout()?)
+        .build()?
+        .get(url.clone());
+    if resume_from > 0 {
+        req = req.header(header::RANGE, format!("bytes={resume_from}-"));
+    }
+    req.send().await.map_err(|err| deadline.check(err))
+}
+
+/// Handles the response to a request for the rest of the partial download in `writer`,
+/// returning whether the download has to be requested again from the start.
+fn continue_partial(
+    writer: &mut DownloadWriter,
+    status: StatusCode,
+    headers: &HeaderMap,
+) -> Result<bool, SvmError> {
+    let resume_from = writer.len;
+    if resume_from == 0 || !(status.is_success() || status == StatusCode::RANGE_NOT_SATISFIABLE) {
+        // errors leave the partial download to be resumed by the next attempt
+        return Ok(false);
+    }
+    let start = headers
+        .get(header::CONTENT_RANGE)
+        .and_then(|range| range.to_str().ok())
+        .and_then(content_range_start);
+    if status == StatusCode::PARTIAL_CONTENT && start == Some(resume_from) {
+        debug!(resume_from, "resuming the download");
+        return Ok(false);
+    }
+    // the server sent the whole binary, or the partial download does not fit it, e.g. as the
+    // binary was republished
+    writer.restart()?;
+    Ok(status == StatusCode::PARTIAL_CONTENT || status == StatusCode::RANGE_NOT_SATISFIABLE)
+}
+
+/// Parses the first byte of a `Content-Range: bytes <first>-<last>/<size>` header.
+fn content_range_start(range: &str) -> Option<u64> {
+    range
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Coalesces [`InstallEvent::Downloading`] events, so that fast downloads do not flood consumers
+/// with an event per chunk.
+struct ProgressThrottle {
+    interval: Duration,
+    reported_at: Instant,
+    reported: u64,
+}
+
+impl ProgressThrottle {
+    /// Starts throttling after nothing was downloaded yet has been reported at `started`.
+    fn new(interval: Duration, started: Instant) -> Self {
+        Self {
+            interval,
+            reported_at: started,
+            reported: 0,
+        }
+    }
+
+    /// Returns whether `downloaded` bytes should be reported at `now`, which is the case once
+    /// `interval` passed or another percent of `total` arrived since the last report.
+    fn is_due(&mut self, downloaded: u64, total: Option<u64>, now: Instant) -> bool {
+        let due = now.saturating_duration_since(self.reported_at) >= self.interval
+            || total.is_some_and(|total| {
+                downloaded.saturating_sub(self.reported).saturating_mul(100) >= total
+            });
+        if due {
+            self.reported_at = now;
+            self.reported = downloaded;
+        }
+        due
+    }
+}
+
+/// Blocking version of [`download`], without progress reporting.
+#[cfg(feature = "blocking")]
+fn blocking_download(
+    url: &Url,
+    deadline: &Deadline,
+    writer: &mut DownloadWriter,
+) -> Result<(), SvmError> {
+    let mut res = blocking_request(url, deadline, writer.len)?;
+    if continue_partial(writer, res.status(), res.headers())? {
+        res = blocking_request(url, deadline, 0)?;
+    }
+    if !res.status().is_success() {
+        return Err(SvmError::UnsuccessfulResponse(url.clone(), res.status()));
+    }
+
+    let total = res.content_length().map(|len| len + writer.len);
+    res.copy_to(writer).map_err(|err| deadline.check(err))?;
+    ensure_complete(url, total, writer.len)
+}
+
+/// Blocking version of [`request`].
+#[cfg(feature = "blocking")]
+fn blocking_request(
+    url: &Url,
+    deadline: &Deadline,
+    resume_from: u64,
+) -> Result<reqwest::blocking::Response, SvmError> {
+    let mut req = http::blocking_client()?
+        .timeout(deadline.request_timeout()?)
+        .build()?
+        .get(url.clone());
+    if resume_from > 0 {
+        req = req.header(header::RANGE, format!("bytes={resume_from}-"));
+    }
+    req.send().map_err(|err| deadline.check(err))
+}
+
+/// A binary downloaded to a temporary file, along with the SHA-256 checksum of its contents.
+///
+/// Downloads are streamed to disk rather than held in memory, as binaries are tens of MB.
+struct Download {
+    file: tempfile::NamedTempFile,
+    sha256: Vec<u8>,
+}
+
+impl Download {
+    /// Stages `binbytes` that are already in memory, e.g. those of a bundle.
+    fn from_bytes(binbytes: &[u8], tmp_dir: &Path) -> Result<Self, SvmError> {
+        let mut writer = DownloadWriter::new(tmp_dir)?;
+        writer.write_all(binbytes)?;
+        writer.finish()
+    }
+
+    fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Fails unless the download has the `expected` checksum.
+    fn ensure_checksum(&self, version: &Version, expected: &[u8]) -> Result<(), SvmError> {
+        compare_checksums(&self.sha256, version, expected)
+    }
+}
+
+/// Writes a download to a temporary file, hashing it along the way.
+///
+/// Binaries with a known checksum are downloaded to a partial file named after it, which is kept
+/// if the download is interrupted, so that the next attempt or install can resume it.
+struct DownloadWriter {
+    file: tempfile::NamedTempFile,
+    hasher: sha2::Sha256,
+    len: u64,
+}
+
+impl DownloadWriter {
+    fn new(tmp_dir: &Path) -> Result<Self, SvmError> {
+        fs::create_dir_all(tmp_dir)?;
+        let file = tempfile::Builder::new()
+            .prefix(".zksolc-")
+            .tempfile_in(tmp_dir)?;
+        Ok(Self {
+            file,
+            hasher: sha2::Sha256::new(),
+            len: 0,
+        })
+    }
+
+    /// Opens the partial download of the binary with `checksum` in `tmp_dir` to resume it, or
+    /// starts a new download if the checksum is unknown or another install is downloading it.
+    fn for_checksum(tmp_dir: &Path, checksum: Option<&[u8]>) -> Result<Self, SvmError> {
+        let Some(checksum) = checksum else {
+            return Self::new(tmp_dir);
+        };
+        fs::create_dir_all(tmp_dir)?;
+        let mut file = tempfile::Builder::new()
+            .prefix(&format!(".zksolc-{}", hex::encode(checksum)))
+            .suffix(".part")
+            .rand_bytes(0)
+            .make_in(tmp_dir, |path| {
+                fs::OpenOptions::new()
+                    .create(true)
+                    .truncate(false)
+                    .read(true)
+                    .append(true)
+                    .open(path)
+            })?;
+        // kept until the download is complete, also if the file belongs to another install
+        file.disable_cleanup(true);
+        if !try_lock_exclusive(file.as_file())? {
+            return Self::new(tmp_dir);
+        }
+        let mut hasher = sha2::Sha256::new();
+        let len = std::io::copy(file.as_file_mut(), &mut hasher)?;
+        Ok(Self { file, hasher, len })
+    }
+
+    /// Discards what was downloaded so far.
+    fn restart(&mut self) -> Result<(), SvmError> {
+        self.file.as_file().set_len(0)?;
+        self.file.rewind()?;
+        self.hasher = sha2::Sha256::new();
+        self.len = 0;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Download, SvmError> {
+        self.file.flush()?;
+        // complete downloads are installed or discarded, never resumed
+        self.file.disable_cleanup(false);
+        Ok(Download {
+            file: self.file,
+            sha256: self.hasher.finalize().to_vec(),
+        })
+    }
+}
+
+impl Write for DownloadWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Copies the cached binary with `checksum` to a temporary file in `tmp_dir`, if there is a valid
+/// one.
+fn cached_download(checksum: &[u8], tmp_dir: &Path) -> Option<Download> {
+    let mut entry = download_cache::open(checksum)?;
+    let mut writer = DownloadWriter::new(tmp_dir).ok()?;
+    std::io::copy(&mut entry, &mut writer).ok()?;
+    // the cache may be shared, so its entries are verified every time they are used
+    writer
+        .finish()
+        .ok()
+        .filter(|download| download.sha256 == checksum)
+}
+
+/// Checks the signature of `download`, which is only read back into memory if there is a
+/// signature to verify, see [`verify::check`].
+fn check_signature(
+    version: &Version,
+    opts: &InstallOptions,
+    download: &Download,
+    signature: Option<&[u8]>,
+) -> Result<(), SvmError> {
+    let binbytes = match (&opts.verifier, signature) {
+        (Some(_), Some(_)) => fs::read(download.path())?,
+        _ => Vec::new(),
+    };
+    verify::check(
+        version,
+        opts.verifier.as_ref(),
+        opts.require_signature,
+        &binbytes,
+        signature,
+    )
+}
+
+/// Fails if fewer or more bytes than the server announced were downloaded.
+fn ensure_complete(url: &Url, total: Option<u64>, received: u64) -> Result<(), SvmError> {
+    match total {
+        Some(expected) if expected != received => Err(SvmError::CorruptDownload {
+            url: url.clone(),
+            expected,
+            received,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn do_install(
+    version: &Version,
+    download: Download,
+    artifact: &str,
+    opts: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    let _lock = crate::state::lock_data_dir(true)?;
+    let native = opts.platform.map_or(Ok(true), |platform| {
+        platform::artifact_platform().map(|native| native == platform)
+    })?;
+    let foreign = opts.platform.filter(|_| !native);
+    let dir = paths::versions_dir(opts.tool, foreign).join(version.to_string());
+    fs::create_dir_all(&dir)?;
+    let name = match opts.tool {
+        Tool::Zksolc => paths::binary_name(artifact),
+        tool => tool.name().to_string(),
+    };
+    let installer = Installer {
+        version,
+        dir: &dir,
+        name: &name,
+        download,
+    };
+
+    // zksolc versions <= 0.7.1 are .zip files for Windows only
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    let zksolc_path = if artifact.ends_with(".zip") {
+        installer.install_zip()?
+    } else {
+        installer.install()?
+    };
+    #[cfg(not(all(target_os = "windows", target_arch = "x86_64")))]
+    let zksolc_path = installer.install()?;
+    // the checksum was verified or deliberately skipped, Gatekeeper has nothing left to check
+    if !opts.keep_quarantine {
+        fsutil::remove_quarantine(&zksolc_path)?;
+    }
+
+    // binaries of other platforms cannot be run here, and only zksolc has its output cached
+    if opts.verify_version && native && opts.tool == Tool::Zksolc {
+        if let Err(err) = check_installed_version(version) {
+            // the data directory is still locked, so `remove_version` cannot be used
+            paths::remove_dir_within_data_dir(&dir)?;
+            return Err(err);
+        }
+    }
+    info!(path = %zksolc_path.display(), "installed");
+    Ok(zksolc_path)
+}
+
+/// Runs the installed binary of `version`, failing unless it reports the same major, minor and
+/// patch version. Its output is cached in the manifest on success.
+fn check_installed_version(version: &Version) -> Result<(), SvmError> {
+    let info = manifest::refresh_compiler_info(version, manifest::read_manifest(version))?;
+    let reported = &info.version;
+    if (reported.major, reported.minor, reported.patch)
+        != (version.major, version.minor, version.patch)
+    {
+        return Err(SvmError::InstalledVersionMismatch {
+            version: version.to_string(),
+            reported: reported.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// How often a contended lock file is polled.
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Creates the file and locks it exclusively, waiting while another install holds it.
+///
+/// With a deadline or a lock timeout, waiting gives up once either has passed.
+#[cfg(any(test, feature = "blocking"))]
+fn try_lock_file(
+    lock_path: PathBuf,
+    deadline: &Deadline,
+    lock_timeout: Option<Duration>,
+) -> Result<LockFile, SvmError> {
+    let _lock_file = open_lock_file(&lock_path)?;
+    let started = Instant::now();
+    while !try_lock_install(&_lock_file)? {
+        check_lock_wait(&lock_path, started, deadline, lock_timeout)?;
+        std::thread::sleep(LOCK_POLL_INTERVAL);
+    }
+    Ok(LockFile {
+        lock_path,
+        _lock_file,
+    })
+}
+
+/// Async version of [`try_lock_file`], which reports waiting to `on_event`.
+async fn lock_file(
+    lock_path: PathBuf,
+    version: &Version,
+    deadline: &Deadline,
+    lock_timeout: Option<Duration>,
+    on_event: impl Fn(InstallEvent),
+) -> Result<LockFile, SvmError> {
+    let _lock_file = {
+        let lock_path = lock_path.clone();
+        tokio::task::spawn_blocking(move || open_lock_file(&lock_path))
+            .await
+            .expect("opening the lock file panicked")?
+    };
+    let started = Instant::now();
+    let mut waiting = false;
+    while !try_lock_install(&_lock_file)? {
+        if !waiting {
+            waiting = true;
+            let holder = LockHolder::read(&lock_path);
+            let (pid, held_for) = (holder.map(|h| h.pid), holder.map(|h| h.held_for()));
+            debug!(lock = %lock_path.display(), ?pid, ?held_for, "waiting for another install");
+            on_event(InstallEvent::WaitingForLock {
+                version: version.clone(),
+                lock_path: lock_path.clone(),
+                pid,
+                held_for,
+            });
+        }
+        check_lock_wait(&lock_path, started, deadline, lock_timeout)?;
+        tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+    }
+    if waiting {
+        debug!(waited = ?started.elapsed(), "acquired the install lock");
+        on_event(InstallEvent::LockAcquired {
+            version: version.clone(),
+            waited: started.elapsed(),
+        });
+    }
+    Ok(LockFile {
+        lock_path,
+        _lock_file,
+    })
+}
+
+/// Tries to lock the lock file of an install without blocking, returning whether it was locked.
+///
+/// A lock held by another process is never taken over, however long it is held or whether the
+/// recorded holder seems to be gone: the holder may run in another container or on a slow link.
+/// Killed installs release their lock with their file descriptors, so a held lock is a live one.
+fn try_lock_install(file: &fs::File) -> Result<bool, SvmError> {
+    if !try_lock_exclusive(file)? {
+        return Ok(false);
+    }
+    // waiting installs report it, so it is best effort
+    let _ = LockHolder::current().write(file);
+    Ok(true)
+}
+
+fn open_lock_file(lock_path: &Path) -> Result<fs::File, SvmError> {
+    // not truncated, as the file records the current holder of the lock
+    Ok(fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(lock_path)?)
+}
+
+/// The process holding the lock of an install, as recorded in the lock file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LockHolder {
+    pub(crate) pid: u32,
+    locked_at: SystemTime,
+}
+
+impl LockHolder {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            locked_at: SystemTime::now(),
+        }
+    }
+
+    /// Reads the holder from the lock file, if it recorded one.
+    pub(crate) fn read(lock_path: &Path) -> Option<Self> {
+        Self::parse(&fs::read_to_string(lock_path).ok()?)
+    }
+
+    /// How long ago the lock was taken, in whole seconds like the recorded time.
+    pub(crate) fn held_for(&self) -> Duration {
+        let held_for = SystemTime::now()
+            .duration_since(self.locked_at)
+            .unwrap_or_default();
+        Duration::from_secs(held_for.as_secs())
+    }
+
+    /// Parses `{pid} {seconds since the epoch}`.
+    fn parse(s: &str) -> Option<Self> {
+        let (pid, locked_at) = s.trim().split_once(' ')?;
+        Some(Self {
+            pid: pid.parse().ok()?,
+            locked_at: SystemTime::UNIX_EPOCH + Duration::from_secs(locked_at.parse().ok()?),
+        })
+    }
+
+    /// Replaces the contents of the locked `file` with this holder.
+    fn write(&self, mut file: &fs::File) -> std::io::Result<()> {
+        let secs = self
+            .locked_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        file.set_len(0)?;
+        std::io::Seek::rewind(&mut file)?;
+        write!(file, "{} {secs}", self.pid)
+    }
+}
+
+/// Tries to lock the file without blocking, returning whether it was locked.
+pub(crate) fn try_lock_exclusive(file: &fs::File) -> Result<bool, SvmError> {
+    use fs4::FileExt;
+    match file.try_lock_exclusive() {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == fs4::lock_contended_error().kind() => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Fails once waiting for a lock since `started` has exceeded the deadline or the lock timeout.
+fn check_lock_wait(
+    lock_path: &Path,
+    started: Instant,
+    deadline: &Deadline,
+    lock_timeout: Option<Duration>,
+) -> Result<(), SvmError> {
+    deadline.remaining()?;
+    match lock_timeout {
+        Some(timeout) if started.elapsed() >= timeout => {
+            Err(SvmError::LockTimeout(lock_path.to_path_buf(), timeout))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Removes the lock file of `version` unless an install holds it, recording the outcome in
+/// `report`.
+pub(crate) fn remove_stale_lock(
+    version: &Version,
+    report: &mut crate::RemovalReport,
+) -> Result<(), SvmError> {
+    let lock_path = lock_file_path(Tool::Zksolc, platform::artifact_platform()?, version);
+    let file = match fs::OpenOptions::new().write(true).open(&lock_path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    if try_lock_exclusive(&file)? {
+        fs::remove_file(&lock_path)?;
+        report.removed.push(lock_path);
+    } else {
+        report.kept_locks.push(lock_path);
+    }
+    Ok(())
+}
+
+/// Represents a lockfile that's removed once dropped
+struct LockFile {
+    _lock_file: fs::File,
+    lock_path: PathBuf,
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.lock_path);
+    }
+}
+
+/// The prefix of the lock files of installs in the data directory, see [`lock_file_path`].
+pub(crate) const LOCK_FILE_PREFIX: &str = ".lock-install-";
+
+/// Returns the lock file of installs of `version` of `tool` for `platform`, so that only installs
+/// to the same directory wait for each other.
+fn lock_file_path(tool: Tool, platform: Platform, version: &Version) -> PathBuf {
+    data_dir().join(format!(
+        "{LOCK_FILE_PREFIX}{}-{platform}-{version}",
+        tool.name()
+    ))
+}
+
+/// Returns the lock file of installing `version` with `opts`, see [`lock_file_path`].
+fn install_lock_path(version: &Version, opts: &InstallOptions) -> Result<PathBuf, SvmError> {
+    Ok(lock_file_path(
+        opts.tool,
+        opts.resolved_platform()?,
+        version,
+    ))
+}
+
+/// Returns the tool, platform and version of the install lock file `name`, see
+/// [`lock_file_path`].
+pub(crate) fn parse_lock_file_name(name: &str) -> Option<(Tool, Platform, Version)> {
+    let name = name.strip_prefix(LOCK_FILE_PREFIX)?;
+    let (tool, name) = Tool::ALL.iter().find_map(|tool| {
+        let name = name.strip_prefix(tool.name())?.strip_prefix('-')?;
+        Some((*tool, name))
+    })?;
+    let (platform, version) = Platform::WITH_ARTIFACTS.iter().find_map(|platform| {
+        let version = name
+            .strip_prefix(platform.to_string().as_str())?
+            .strip_prefix('-')?;
+        Some((*platform, version))
+    })?;
+    Some((tool, platform, version.parse().ok()?))
+}
+
+// Installer type that copies binary data to the appropriate zksolc binary file:
+// 1. mark the install as incomplete, so that an interrupted install is not reported as installed
+// 2. link the binary from the download cache, or take the download staged in a temporary file
+// 3. make the staged file executable and flush it to disk
+// 4. atomically rename the staged file to the target file and remove the marker
+struct Installer<'a> {
+    // version of zksolc
+    version: &'a Version,
+    // directory to install the version in, see `InstallOptions::platform`
+    dir: &'a Path,
+    // name the binary is installed under, see `paths::binary_name`
+    name: &'a str,
+    // the downloaded zksolc executable, whose checksum keys the download cache
+    download: Download,
+}
+
+impl Installer<'_> {
+    /// Installs the zksolc version at the version specific destination and returns the path to the installed zksolc file.
+    fn install(self) -> Result<PathBuf, SvmError> {
+        let zksolc_path = self.dir.join(format!("{}-{}", self.name, self.version));
+        mark_incomplete(self.dir)?;
+
+        let staged = match download_cache::link(&self.download.sha256, self.dir) {
+            Some(linked) => linked,
+            None => self.download.file,
+        };
+        // hard links share the mode of the cache entry, which is already executable
+        #[cfg(target_family = "unix")]
+        if staged.as_file().metadata()?.permissions().mode() & 0o111 != 0o111 {
+            staged
+                .as_file()
+                .set_permissions(Permissions::from_mode(0o755))?;
+        }
+        staged.as_file().sync_all()?;
+
+        // the staging directory may live on another filesystem, in which case renaming fails
+        fsutil::persist_or_copy(staged, &zksolc_path)?;
+        mark_complete(self.dir)?;
+
+        Ok(zksolc_path)
+    }
+
+    /// Extracts the zksolc archive at the version specified destination and returns the path to the
+    /// installed zksolc binary.
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    fn install_zip(self) -> Result<PathBuf, SvmError> {
+        let zksolc_path = self.dir.join(format!("{}-{}", self.name, self.version));
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(self.download.path())?)?;
+        mark_incomplete(self.dir)?;
+        archive.extract(self.dir)?;
+
+        std::fs::rename(self.dir.join("zksolc.exe"), &zksolc_path)
+            .map_err(|err| fsutil::file_error(err, &zksolc_path))?;
+        mark_complete(self.dir)?;
+
+        Ok(zksolc_path)
+    }
+}
+
+/// Marks the install into `version_dir` as incomplete until [`mark_complete`] is called, so that
+/// [`installed_versions`](crate::installed_versions) skips it if the install is interrupted.
+pub(crate) fn mark_incomplete(version_dir: &Path) -> Result<(), SvmError> {
+    let marker = version_dir.join(paths::INCOMPLETE_MARKER_NAME);
+    fs::File::create(&marker).map_err(|err| fsutil::file_error(err, &marker))?;
+    Ok(())
+}
+
+/// Marks the install into `version_dir` as complete, once its binary is in place.
+pub(crate) fn mark_complete(version_dir: &Path) -> Result<(), SvmError> {
+    let marker = version_dir.join(paths::INCOMPLETE_MARKER_NAME);
+    match fs::remove_file(&marker) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(fsutil::file_error(err, &marker))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn ensure_checksum(
+    binbytes: &[u8],
+    version: &Version,
+    expected_checksum: &[u8],
+) -> Result<(), SvmError> {
+    compare_checksums(&sha2::Sha256::digest(binbytes), version, expected_checksum)
+}
+
+fn compare_checksums(
+    checksum: &[u8],
+    version: impl std::fmt::Display,
+    expected_checksum: &[u8],
+) -> Result<(), SvmError> {
+    // checksum does not match, compared in constant time to not leak how much of it matched
+    if !bool::from(checksum.ct_eq(expected_checksum)) {
+        return Err(SvmError::ChecksumMismatch {
+            version: version.to_string(),
+            expected: hex::encode(expected_checksum),
+            actual: hex::encode(checksum),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{all_releases, platform, version_binary};
+    use rand::seq::SliceRandom;
+    use std::process::Command;
+
+    #[allow(unused)]
+    const LATEST: Version = Version::new(1, 4, 1);
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_install() {
+        let versions = all_releases(platform())
+            .await
+            .unwrap()
+            .releases
+            .into_keys()
+            .collect::<Vec<Version>>();
+        let rand_version = versions.choose(&mut rand::thread_rng()).unwrap();
+        assert!(install(rand_version).await.is_ok());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[serial_test::serial]
+    #[test]
+    fn blocking_test_install() {
+        let versions = crate::releases::blocking_all_releases(platform::platform())
+            .unwrap()
+            .into_versions();
+        let rand_version = versions.choose(&mut rand::thread_rng()).unwrap();
+        assert!(blocking_install(rand_version).is_ok());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[serial_test::serial]
+    #[test]
+    fn blocking_install_reports_warnings() {
+        use std::{
+            io::{BufRead, BufReader},
+            net::TcpListener,
+        };
+
+        let version = Version::new(0, 0, 47);
+        let list = format!(r#"{{"builds":[],"releases":{{"{version}":"zksolc-v{version}"}}}}"#);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let body = if request.contains(".json") {
+                    list.as_bytes()
+                } else {
+                    b"zksolc without a checksum"
+                };
+                let mut response =
+                    format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len())
+                        .into_bytes();
+                response.extend_from_slice(body);
+                let _ = stream.write_all(&response);
+            }
+        });
+
+        let opts = InstallOptions::default()
+            .source(ReleaseSource::new(base).unwrap())
+            .allow_unchecked(true);
+        let events = std::sync::Mutex::new(Vec::new());
+        let _ = crate::remove_version(&version);
+        blocking_install_with_events(&version, &opts, |event| events.lock().unwrap().push(event))
+            .unwrap();
+        assert!(events.into_inner().unwrap().iter().any(|event| matches!(
+            event,
+            InstallEvent::Warning(Warning::Unchecked { version: unchecked }) if *unchecked == version
+        )));
+        crate::remove_version(&version).unwrap();
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_version() {
+        let version = "1.3.17".parse().unwrap();
+        install(&version).await.unwrap();
+        let zksolc_path = version_binary(version.to_string().as_str());
+        let output = Command::new(zksolc_path).arg("--version").output().unwrap();
+        assert!(String::from_utf8_lossy(&output.stdout)
+            .as_ref()
+            .contains("1.3.17"));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[serial_test::serial]
+    #[test]
+    fn blocking_test_latest() {
+        blocking_install(&LATEST).unwrap();
+        let zksolc_path = version_binary(LATEST.to_string().as_str());
+        let output = Command::new(zksolc_path).arg("--version").output().unwrap();
+
+        assert!(String::from_utf8_lossy(&output.stdout)
+            .as_ref()
+            .contains(&LATEST.to_string()));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[serial_test::serial]
+    #[test]
+    fn blocking_test_version() {
+        let version = "1.3.17".parse().unwrap();
+        blocking_install(&version).unwrap();
+        let zksolc_path = version_binary(version.to_string().as_str());
+        let output = Command::new(zksolc_path).arg("--version").output().unwrap();
+
+        assert!(String::from_utf8_lossy(&output.stdout)
+            .as_ref()
+            .contains("1.3.17"));
+    }
+
+    fn sha256(bytes: &[u8]) -> Vec<u8> {
+        sha2::Sha256::digest(bytes).to_vec()
+    }
+
+    /// Stages `binbytes` like a download.
+    fn staged(binbytes: &[u8]) -> Download {
+        Download::from_bytes(binbytes, &crate::tmp_dir()).unwrap()
+    }
+
+    #[test]
+    fn downloads_hash_what_they_write() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut writer = DownloadWriter::new(&tmp_dir.path().join("staging")).unwrap();
+        for chunk in [&b"zk"[..], b"", b"solc"] {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(writer.len, 6);
+        let download = writer.finish().unwrap();
+        assert_eq!(download.sha256, sha256(b"zksolc"));
+        assert_eq!(fs::read(download.path()).unwrap(), b"zksolc");
+        download
+            .ensure_checksum(&Version::new(0, 0, 1), &sha256(b"zksolc"))
+            .unwrap();
+        assert!(download
+            .ensure_checksum(&Version::new(0, 0, 1), &sha256(b"zkvyper"))
+            .is_err());
+
+        let path = download.path().to_path_buf();
+        drop(download);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn resumes_partial_downloads() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let checksum = sha256(b"zksolc");
+        let mut writer = DownloadWriter::for_checksum(tmp_dir.path(), Some(&checksum)).unwrap();
+        writer.write_all(b"zks").unwrap();
+        drop(writer);
+
+        let mut writer = DownloadWriter::for_checksum(tmp_dir.path(), Some(&checksum)).unwrap();
+        assert_eq!(writer.len, 3);
+        writer.write_all(b"olc").unwrap();
+        let download = writer.finish().unwrap();
+        assert_eq!(download.sha256, checksum);
+        assert_eq!(fs::read(download.path()).unwrap(), b"zksolc");
+        drop(download);
+        assert_eq!(fs::read_dir(tmp_dir.path()).unwrap().count(), 0);
+
+        let mut writer = DownloadWriter::for_checksum(tmp_dir.path(), Some(&checksum)).unwrap();
+        writer.write_all(b"zkvyper").unwrap();
+        writer.restart().unwrap();
+        writer.write_all(b"zksolc").unwrap();
+        assert_eq!(writer.finish().unwrap().sha256, checksum);
+    }
+
+    #[test]
+    fn parses_content_range() {
+        assert_eq!(content_range_start("bytes 100-199/200"), Some(100));
+        assert_eq!(content_range_start("bytes 0-199/*"), Some(0));
+        assert_eq!(content_range_start("bytes */200"), None);
+        assert_eq!(content_range_start("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn checks_min_manager_version() {
+        let version = Version::new(1, 5, 0);
+        let current = Version::new(0, 2, 0);
+        let check = |required: Option<Version>, policy| {
+            check_manager_version(&version, required.as_ref(), &current, policy)
+        };
+
+        for policy in [ManagerVersionPolicy::Warn, ManagerVersionPolicy::Refuse] {
+            assert_eq!(check(None, policy).unwrap(), None);
+            assert_eq!(check(Some(Version::new(0, 2, 0)), policy).unwrap(), None);
+            assert_eq!(check(Some(Version::new(0, 1, 9)), policy).unwrap(), None);
+        }
+        assert_eq!(
+            check(Some(Version::new(0, 3, 0)), ManagerVersionPolicy::Warn).unwrap(),
+            Some(Warning::OutdatedManager {
+                version: version.clone(),
+                required: Version::new(0, 3, 0),
+                current: current.clone(),
+            })
+        );
+        assert!(matches!(
+            check(Some(Version::new(0, 3, 0)), ManagerVersionPolicy::Refuse),
+            Err(SvmError::OutdatedManager { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_checksums_need_opt_in() {
+        let version = Version::new(1, 4, 1);
+        assert!(matches!(
+            expected_checksum(&version, None, None, false),
+            Err(SvmError::MissingChecksum { .. })
+        ));
+        assert_eq!(expected_checksum(&version, None, None, true).unwrap(), None);
+        assert_eq!(
+            expected_checksum(&version, Some(vec![1; 32]), None, false).unwrap(),
+            Some(vec![1; 32])
+        );
+    }
+
+    #[test]
+    fn checksum_matches() {
+        let version = Version::new(1, 3, 17);
+        ensure_checksum(b"zksolc", &version, &sha256(b"zksolc")).unwrap();
+        ensure_checksum(b"", &version, &sha256(b"")).unwrap();
+    }
+
+    #[test]
+    fn checksum_empty_body() {
+        let version = Version::new(1, 3, 17);
+        let expected = sha256(b"zksolc");
+        let err = ensure_checksum(b"", &version, &expected).unwrap_err();
+        let SvmError::ChecksumMismatch {
+            version: v,
+            expected: e,
+            actual,
+        } = err
+        else {
+            panic!("unexpected error: {err}")
+        };
+        assert_eq!(v, "1.3.17");
+        assert_eq!(e, hex::encode(&expected));
+        assert_eq!(actual, hex::encode(sha256(b"")));
+    }
+
+    #[test]
+    fn checksum_truncated_download() {
+        let version = Version::new(1, 3, 17);
+        let body = vec![0xab; 1024];
+        let expected = sha256(&body);
+        assert!(matches!(
+            ensure_checksum(&body[..1000], &version, &expected),
+            Err(SvmError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn checksum_malformed_expected() {
+        let version = Version::new(1, 3, 17);
+        let checksum = sha256(b"zksolc");
+        // a prefix of the correct checksum must not be accepted
+        for expected in [
+            &[][..],
+            &checksum[..16],
+            &[checksum.clone(), vec![0]].concat(),
+        ] {
+            assert!(matches!(
+                ensure_checksum(b"zksolc", &version, expected),
+                Err(SvmError::ChecksumMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn install_status_of_local_versions() {
+        let version = Version::new(0, 0, 2);
+        assert_eq!(install_status(&version).unwrap(), None);
+
+        setup_version(&version.to_string()).unwrap();
+        assert_eq!(
+            install_status(&version).unwrap(),
+            Some(InstallOutcome::AlreadyInstalled)
+        );
+
+        crate::set_global_version(&version).unwrap();
+        assert_eq!(
+            install_status(&version).unwrap(),
+            Some(InstallOutcome::AlreadyActive)
+        );
+
+        crate::remove_version(&version).unwrap();
+        crate::unset_global_version().unwrap();
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[serial_test::serial]
+    fn verifies_installed_version() {
+        let version = Version::new(0, 0, 4);
+        let opts = InstallOptions::default().verify_version(true);
+        let script =
+            |reported: &str| format!("#!/bin/sh\necho 'zksolc v{reported} (LLVM build abc)'\n");
+
+        let binbytes = script("0.0.4");
+        let artifact = "zksolc-linux-amd64-musl-v0.0.4";
+        do_install(&version, staged(binbytes.as_bytes()), artifact, &opts).unwrap();
+        assert_eq!(
+            manifest::read_manifest(&version).compiler.unwrap().version,
+            version
+        );
+        crate::remove_version(&version).unwrap();
+
+        let binbytes = script("0.0.5");
+        assert!(matches!(
+            do_install(&version, staged(binbytes.as_bytes()), artifact, &opts),
+            Err(SvmError::InstalledVersionMismatch { .. })
+        ));
+        assert!(!crate::version_path(&version.to_string()).exists());
+
+        // without the check, whatever the binary reports is installed
+        do_install(
+            &version,
+            staged(binbytes.as_bytes()),
+            artifact,
+            &InstallOptions::default(),
+        )
+        .unwrap();
+        crate::remove_version(&version).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn installs_binaries_of_other_platforms_separately() {
+        let version = Version::new(0, 0, 5);
+        let other = match platform::artifact_platform().unwrap() {
+            Platform::LinuxAmd64 => Platform::MacOsAarch64,
+            _ => Platform::LinuxAmd64,
+        };
+        // cannot be run, so the version it reports is not checked
+        let opts = InstallOptions::default()
+            .platform(other)
+            .verify_version(true);
+        let binbytes = b"zksolc for another platform";
+        let path = do_install(
+            &version,
+            staged(binbytes),
+            "zksolc-macosx-arm64-v0.0.5",
+            &opts,
+        )
+        .unwrap();
+
+        let dir = paths::platform_version_path(other, &version.to_string());
+        assert_eq!(path, dir.join("zksolc-0.0.5"));
+        assert_eq!(fs::read(&path).unwrap(), binbytes);
+        assert!(!crate::installed_versions().unwrap().contains(&version));
+        assert!(!crate::version_path(&version.to_string()).exists());
+
+        paths::remove_dir_within_data_dir(&data_dir().join(paths::PLATFORMS_DIR_NAME)).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn installs_other_tools_separately() {
+        let version = Version::new(0, 0, 7);
+        // zkvyper does not report its version like zksolc does, so it is not checked
+        let opts = InstallOptions::default()
+            .tool(Tool::Zkvyper)
+            .verify_version(true);
+        let binbytes = b"zkvyper";
+        let path = do_install(
+            &version,
+            staged(binbytes),
+            "zkvyper-linux-amd64-musl-v0.0.7",
+            &opts,
+        )
+        .unwrap();
+
+        assert_eq!(
+            path,
+            paths::tool_version_binary(Tool::Zkvyper, &version.to_string())
+        );
+        assert_eq!(fs::read(&path).unwrap(), binbytes);
+        assert_eq!(
+            crate::installed_tool_versions(Tool::Zkvyper).unwrap(),
+            vec![version.clone()]
+        );
+        assert!(!crate::installed_versions().unwrap().contains(&version));
+
+        paths::remove_dir_within_data_dir(&data_dir().join(paths::TOOLS_DIR_NAME)).unwrap();
+    }
+
+    #[test]
+    fn installer_stages_in_tmp_dir() {
+        let version = Version::new(0, 0, 1);
+        let tmp_dir = tempfile::tempdir().unwrap();
+        setup_version(&version.to_string()).unwrap();
+        let installer = Installer {
+            version: &version,
+            dir: &crate::version_path(&version.to_string()),
+            name: "zksolc",
+            download: Download::from_bytes(b"zksolc", &tmp_dir.path().join("staging")).unwrap(),
+        };
+        let zksolc_path = installer.install().unwrap();
+        assert_eq!(fs::read(&zksolc_path).unwrap(), b"zksolc");
+        assert_eq!(
+            fs::read_dir(tmp_dir.path().join("staging"))
+                .unwrap()
+                .count(),
+            0
+        );
+        #[cfg(target_family = "unix")]
+        assert_eq!(
+            fs::metadata(&zksolc_path).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn interrupted_installs_are_not_installed() {
+        let version = Version::new(0, 0, 3);
+        setup_version(&version.to_string()).unwrap();
+        // an install that was interrupted before moving the binary into place
+        mark_incomplete(&crate::version_path(&version.to_string())).unwrap();
+        assert!(!crate::installed_versions().unwrap().contains(&version));
+        assert_eq!(install_status(&version).unwrap(), None);
+
+        let tmp_dir = tempfile::tempdir().unwrap();
+        Installer {
+            version: &version,
+            dir: &crate::version_path(&version.to_string()),
+            name: "zksolc",
+            download: Download::from_bytes(b"zksolc", tmp_dir.path()).unwrap(),
+        }
+        .install()
+        .unwrap();
+        assert!(!paths::incomplete_marker(&version.to_string()).exists());
+        assert!(crate::installed_versions().unwrap().contains(&version));
+
+        crate::remove_version(&version).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn installer_links_from_download_cache() {
+        let version = Version::new(0, 0, 2);
+        let binbytes = b"zksolc from the download cache";
+        let checksum = sha256(binbytes);
+        let tmp_dir = tempfile::tempdir().unwrap();
+        setup_version(&version.to_string()).unwrap();
+        let download = Download::from_bytes(binbytes, &tmp_dir.path().join("staging")).unwrap();
+        assert_eq!(download.sha256, checksum);
+        download_cache::store(download.path(), &checksum);
+
+        let installer = Installer {
+            version: &version,
+            dir: &crate::version_path(&version.to_string()),
+            name: "zksolc",
+            download,
+        };
+        let zksolc_path = installer.install().unwrap();
+        assert_eq!(fs::read(&zksolc_path).unwrap(), binbytes);
+        // the binary was linked, the staged download discarded
+        assert_eq!(
+            fs::read_dir(tmp_dir.path().join("staging"))
+                .unwrap()
+                .count(),
+            0
+        );
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::MetadataExt;
+            assert_eq!(fs::metadata(&zksolc_path).unwrap().nlink(), 2);
+        }
+
+        crate::remove_version(&version).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fails_versions_of_panicked_installs() {
+        let versions = [Version::new(1, 3, 16), Version::new(1, 3, 17)];
+        let mut tasks = tokio::task::JoinSet::new();
+        tasks.spawn(async { (0, Ok(PathBuf::from("zksolc-1.3.16"))) });
+        tasks.spawn(async { panic!("no space left") });
+
+        let results = join_installs(&versions, tasks).await;
+        assert_eq!(results[0].as_ref().unwrap(), Path::new("zksolc-1.3.16"));
+        match &results[1] {
+            Err(SvmError::InstallTaskFailed { version, reason }) => {
+                assert_eq!(version, "1.3.17");
+                assert!(reason.contains("panicked"), "{reason}");
+            }
+            res => panic!("unexpected result {res:?}"),
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn installs_from_url() {
+        use std::{
+            io::{BufRead, BufReader},
+            net::TcpListener,
+        };
+
+        let binbytes = b"zksolc from a mirror";
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!(
+            "http://{}/mirror/era-solc-linux-amd64-v0.0.4",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                BufReader::new(&stream)
+                    .read_line(&mut String::new())
+                    .unwrap();
+                let mut response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                    binbytes.len()
+                )
+                .into_bytes();
+                response.extend_from_slice(binbytes);
+                let _ = stream.write_all(&response);
+            }
+        });
+
+        let version = Version::new(0, 0, 4);
+        let opts = InstallOptions::default().retry(RetryPolicy {
+            attempts: 1,
+            ..Default::default()
+        });
+        assert!(matches!(
+            install_from_url_with_options(&version, &url, &sha256(b"other"), &opts).await,
+            Err(SvmError::ChecksumMismatch { .. })
+        ));
+        assert!(!crate::installed_versions().unwrap().contains(&version));
+
+        let zksolc_path = install_from_url(&version, &url, &sha256(binbytes))
+            .await
+            .unwrap();
+        assert_eq!(fs::read(&zksolc_path).unwrap(), binbytes);
+        assert!(zksolc_path.ends_with("era-solc-0.0.4"));
+        assert_eq!(version_binary(&version.to_string()), zksolc_path);
+        crate::remove_version(&version).unwrap();
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn ensure_version_exact_repairs_binaries() {
+        let releases = all_releases(platform()).await.unwrap();
+        let version = releases
+            .clone()
+            .into_versions()
+            .into_iter()
+            .rfind(|version| releases.is_supported(version))
+            .unwrap();
+        let _ = crate::remove_version(&version);
+        assert_eq!(
+            ensure_version_exact(&version).await.unwrap(),
+            InstallOutcome::Installed
+        );
+        assert_eq!(
+            ensure_version_exact(&version).await.unwrap(),
+            InstallOutcome::AlreadyInstalled
+        );
+
+        // a corrupted binary is downloaded again
+        let zksolc_path = version_binary(&version.to_string());
+        fs::write(&zksolc_path, "corrupted").unwrap();
+        assert_eq!(
+            ensure_version_exact(&version).await.unwrap(),
+            InstallOutcome::Installed
+        );
+        assert_ne!(fs::read(&zksolc_path).unwrap(), b"corrupted");
+        crate::remove_version(&version).unwrap();
+    }
+
+    #[test]
+    fn rejects_incomplete_downloads() {
+        let url = Url::parse("https://example.com/zksolc").unwrap();
+        assert!(ensure_complete(&url, Some(3), 3).is_ok());
+        assert!(ensure_complete(&url, None, 3).is_ok());
+        let err = ensure_complete(&url, Some(4), 3).unwrap_err();
+        assert!(matches!(
+            err,
+            SvmError::CorruptDownload {
+                expected: 4,
+                received: 3,
+                ..
+            }
+        ));
+        assert!(crate::netutil::is_transient(&err));
+    }
+
+    #[test]
+    fn throttles_progress_events() {
+        let started = Instant::now();
+        let at = |millis| started + Duration::from_millis(millis);
+        let mut progress = ProgressThrottle::new(Duration::from_millis(50), started);
+        // small chunks in quick succession are coalesced
+        assert!(!progress.is_due(10, Some(10_000), at(1)));
+        assert!(!progress.is_due(20, Some(10_000), at(2)));
+        // until the interval passed
+        assert!(progress.is_due(30, Some(10_000), at(50)));
+        assert!(!progress.is_due(40, Some(10_000), at(60)));
+        // or another percent arrived
+        assert!(progress.is_due(130, Some(10_000), at(61)));
+        assert!(!progress.is_due(200, None, at(62)));
+        assert_eq!(progress.reported, 130);
+
+        let mut every_chunk = ProgressThrottle::new(Duration::ZERO, started);
+        assert!(every_chunk.is_due(1, None, started));
+        assert!(every_chunk.is_due(2, None, started));
+    }
+
+    #[test]
+    fn deadline_bounds_request_timeouts() {
+        let unbounded = Deadline::start(None);
+        assert_eq!(unbounded.remaining().unwrap(), None);
+        assert_eq!(
+            unbounded.request_timeout().unwrap(),
+            Duration::from_secs(120)
+        );
+
+        let bounded = Deadline::start(Some(Duration::from_secs(30)));
+        assert!(bounded.request_timeout().unwrap() <= Duration::from_secs(30));
+
+        let expired = Deadline::start(Some(Duration::ZERO));
+        assert!(matches!(
+            expired.request_timeout(),
+            Err(SvmError::DeadlineExceeded(_))
+        ));
+        assert!(matches!(
+            expired.check(SvmError::UnknownVersion),
+            SvmError::DeadlineExceeded(_)
+        ));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn deadline_bounds_lock_waits() {
+        let version = Version::new(0, 0, 42);
+        setup_data_dir().unwrap();
+        let lock_path = lock_file_path(Tool::Zksolc, Platform::LinuxAmd64, &version);
+        let _held = try_lock_file(lock_path.clone(), &Deadline::start(None), None).unwrap();
+        let deadline = Deadline::start(Some(Duration::from_millis(200)));
+        assert!(matches!(
+            try_lock_file(lock_path.clone(), &deadline, None),
+            Err(SvmError::DeadlineExceeded(_))
+        ));
+        assert!(matches!(
+            try_lock_file(
+                lock_path.clone(),
+                &Deadline::start(None),
+                Some(Duration::from_millis(100))
+            ),
+            Err(SvmError::LockTimeout(..))
+        ));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn never_takes_over_held_locks() {
+        let version = Version::new(0, 0, 44);
+        setup_data_dir().unwrap();
+        let lock_path = lock_file_path(Tool::Zksolc, Platform::LinuxAmd64, &version);
+        let held = try_lock_file(lock_path.clone(), &Deadline::start(None), None).unwrap();
+        assert_eq!(
+            LockHolder::read(&lock_path).map(|holder| holder.pid),
+            Some(std::process::id())
+        );
+
+        // however old or gone the recorded holder looks, the lock is held, so it is waited for
+        let timeout = Some(Duration::from_millis(100));
+        for holder in [
+            LockHolder {
+                pid: std::process::id(),
+                locked_at: SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+            },
+            LockHolder {
+                pid: u32::MAX,
+                locked_at: SystemTime::UNIX_EPOCH + Duration::from_secs(3_000_000_000),
+            },
+        ] {
+            holder.write(&held._lock_file).unwrap();
+            assert!(matches!(
+                try_lock_file(lock_path.clone(), &Deadline::start(None), timeout),
+                Err(SvmError::LockTimeout(..))
+            ));
+            assert_eq!(LockHolder::read(&held.lock_path), Some(holder));
+        }
+        drop(held);
+
+        assert_eq!(
+            LockHolder::parse("42 60"),
+            Some(LockHolder {
+                pid: 42,
+                locked_at: SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+            })
+        );
+        assert_eq!(LockHolder::parse(""), None);
+        let holder = LockHolder::parse(&format!("42 {}", 60 * 60)).unwrap();
+        assert!(holder.held_for() > Duration::from_secs(50 * 365 * 24 * 60 * 60));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn lock_wait_reports_events_without_blocking() {
+        let version = Version::new(0, 0, 43);
+        setup_data_dir().unwrap();
+        let lock_path = lock_file_path(Tool::Zksolc, Platform::LinuxAmd64, &version);
+        let held = try_lock_file(lock_path.clone(), &Deadline::start(None), None).unwrap();
+
+        // the current-thread runtime must keep running other tasks while the lock is contended
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            drop(held);
+        });
+        let events = std::sync::Mutex::new(vec![]);
+        let lock = lock_file(
+            lock_path.clone(),
+            &version,
+            &Deadline::start(None),
+            Some(Duration::from_secs(10)),
+            |event| events.lock().unwrap().push(event),
+        )
+        .await
+        .unwrap();
+        release.await.unwrap();
+        drop(lock);
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            InstallEvent::WaitingForLock {
+                held_for: Some(held_for),
+                ..
+            } if held_for < Duration::from_secs(60)
+        ));
+        assert!(matches!(events[1], InstallEvent::LockAcquired { .. }));
+    }
+
+    #[test]
+    fn locks_installs_by_tool_platform_and_version() {
+        let version = Version::parse("1.4.2-beta.1").unwrap();
+        let lock_path = lock_file_path(Tool::Zkvyper, Platform::MacOsAarch64, &version);
+        assert_ne!(
+            lock_path,
+            lock_file_path(Tool::Zksolc, Platform::MacOsAarch64, &version)
+        );
+        assert_ne!(
+            lock_path,
+            lock_file_path(Tool::Zkvyper, Platform::MacOsAmd64, &version)
+        );
+        let name = lock_path.file_name().unwrap().to_str().unwrap();
+        assert_eq!(
+            parse_lock_file_name(name),
+            Some((Tool::Zkvyper, Platform::MacOsAarch64, version))
+        );
+        assert_eq!(parse_lock_file_name(".lock-state"), None);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn block_on_outside_runtime() {
+        assert_eq!(block_on_any_runtime("test", async { Ok(1) }).unwrap(), 1);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn block_on_inside_multi_thread_runtime() {
+        assert_eq!(block_on_any_runtime("test", async { Ok(1) }).unwrap(), 1);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[tokio::test(flavor = "current_thread")]
+    async fn block_on_inside_current_thread_runtime() {
+        assert!(matches!(
+            block_on_any_runtime("test", async { Ok(1) }),
+            Err(SvmError::BlockingInAsyncContext("test"))
+        ));
+        assert!(matches!(
+            blocking_install(&Version::new(1, 3, 17)),
+            Err(SvmError::BlockingInAsyncContext("blocking_install"))
+        ));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn can_install_parallel() {
+        let version: Version = "1.3.17".parse().unwrap();
+        let cloned_version = version.clone();
+        let t = std::thread::spawn(move || blocking_install(&cloned_version));
+        blocking_install(&version).unwrap();
+        t.join().unwrap().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn can_install_parallel_async() {
+        let version: Version = "1.3.17".parse().unwrap();
+        let cloned_version = version.clone();
+        let t = tokio::task::spawn(async move { install(&cloned_version).await });
+        install(&version).await.unwrap();
+        t.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn install_many_reports_each_version() {
+        let versions = [Version::new(0, 0, 1), Version::new(0, 0, 2)];
+        let events = Arc::new(std::sync::Mutex::new(vec![]));
+        let recorded = events.clone();
+        let results = install_many_with_events(
+            &versions,
+            &InstallOptions::default().retry(RetryPolicy::none()),
+            1,
+            move |version, _| recorded.lock().unwrap().push(version.clone()),
+        )
+        .await;
+        // neither version exists, so both fail without downloading anything
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|res| res.is_err()));
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    // ensures we can download the latest universal zksolc for apple silicon
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    async fn can_install_latest_native_apple_silicon() {
+        let zksolc = install(&LATEST).await.unwrap();
+        let output = Command::new(zksolc).arg("--version").output().unwrap();
+        let version = String::from_utf8_lossy(&output.stdout);
+        assert!(version.contains("1.4.1"), "{}", version);
+    }
+
+    // ensures we can download the latest native zksolc for linux aarch64
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+    async fn can_download_latest_linux_aarch64() {
+        let artifacts = all_releases(Platform::LinuxAarch64).await.unwrap();
+
+        let artifact = artifacts.releases.get(&LATEST).unwrap();
+        let download_url = artifact_url(
+            &ReleaseSource::default(),
+            Platform::LinuxAarch64,
+            &LATEST,
+            &artifacts,
+        )
+        .unwrap();
+
+        let checksum = artifacts.get_checksum(&LATEST).unwrap();
+
+        let resp = reqwest::get(download_url).await.unwrap();
+        assert!(resp.status().is_success());
+        let binbytes = resp.bytes().await.unwrap();
+        ensure_checksum(&binbytes, &LATEST, &checksum).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    async fn can_install_windows_zip_release() {
+        let version = "1.3.17".parse().unwrap();
+        install(&version).await.unwrap();
+        let zksolc_path = version_binary(version.to_string().as_str());
+        let output = Command::new(&zksolc_path)
+            .arg("--version")
+            .output()
+            .unwrap();
+
+        assert!(String::from_utf8_lossy(&output.stdout)
+            .as_ref()
+            .contains("1.3.17"));
+    }
+}
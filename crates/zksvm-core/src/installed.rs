@@ -0,0 +1,102 @@
+use crate::{
+    fsutil, installed_versions, platform,
+    releases::{all_releases_in, Channel, Releases},
+    verify::verify_binary,
+    version_binary, version_path, SvmError, VerificationResult,
+};
+use semver::Version;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    path::PathBuf,
+    time::SystemTime,
+};
+
+/// An installed zksolc version, as listed by [`list_installed`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstalledVersion {
+    pub version: Version,
+    /// The path to the installed binary.
+    pub path: PathBuf,
+    /// The size of the version's directory on disk in bytes, including its manifest.
+    pub size: u64,
+    /// When the version was installed, if the filesystem records it.
+    pub installed_at: Option<SystemTime>,
+    /// The binary checked against its published checksum, or `None` if the release list could
+    /// not be fetched.
+    pub checksum: Option<VerificationResult>,
+}
+
+/// Lists the installed versions along with where they are, how much space they take up, when
+/// they were installed and whether their binaries match the published checksums.
+///
+/// The checksums are taken from the cached release lists, which are only fetched if they are
+/// missing or expired. Use [`installed_versions`] if only the versions are of interest.
+pub async fn list_installed() -> Result<Vec<InstalledVersion>, SvmError> {
+    let platform = platform::artifact_platform()?;
+    let mut releases = HashMap::new();
+    let mut installed = vec![];
+    for version in installed_versions()? {
+        let channel = Channel::of(&version);
+        if let Entry::Vacant(entry) = releases.entry(channel) {
+            entry.insert(all_releases_in(platform, channel).await.ok());
+        }
+        installed.push(describe_installed(version, releases[&channel].as_ref())?);
+    }
+    Ok(installed)
+}
+
+/// Collects what [`list_installed`] reports about `version`, checking its binary against
+/// `releases` if available.
+fn describe_installed(
+    version: Version,
+    releases: Option<&Releases>,
+) -> Result<InstalledVersion, SvmError> {
+    let dir = version_path(&version.to_string());
+    let size = fsutil::dir_size(&dir)?;
+    // creation times are not available on all filesystems, unlike modification times
+    let meta = fs::metadata(&dir)?;
+    let installed_at = meta.created().or_else(|_| meta.modified()).ok();
+    let checksum = releases
+        .map(|releases| verify_binary(&version, releases))
+        .transpose()?;
+    Ok(InstalledVersion {
+        path: version_binary(&version.to_string()),
+        version,
+        size,
+        installed_at,
+        checksum,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup_version;
+
+    #[test]
+    #[serial_test::serial]
+    fn describes_installed_versions() {
+        let version = Version::new(0, 0, 9);
+        setup_version(&version.to_string()).unwrap();
+        let binary = version_binary(&version.to_string());
+        fs::write(&binary, "zksolc").unwrap();
+        fs::write(
+            version_path(&version.to_string()).join("manifest.json"),
+            "{}",
+        )
+        .unwrap();
+
+        let installed = describe_installed(version.clone(), Some(&Releases::default())).unwrap();
+        assert_eq!(installed.version, version);
+        assert_eq!(installed.path, binary);
+        assert_eq!(installed.size, 8);
+        assert!(installed.installed_at.is_some());
+        assert_eq!(installed.checksum, Some(VerificationResult::Unverifiable));
+
+        let installed = describe_installed(version.clone(), None).unwrap();
+        assert_eq!(installed.checksum, None);
+
+        crate::remove_version(&version).unwrap();
+    }
+}
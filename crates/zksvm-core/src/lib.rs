@@ -0,0 +1,836 @@
+//! Core library of zksvm, the zksolc compiler version manager.
+//!
+//! This crate has no CLI dependencies. Most users should depend on `zksvm-rs`, which re-exports
+//! it as `zksvm`.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/favicon.ico"
+)]
+#![warn(rustdoc::all)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+mod alias;
+pub use alias::{list_aliases, remove_alias, resolve_alias, set_alias};
+
+mod audit;
+pub use audit::{audit_releases, AuditFinding, AuditReport};
+
+mod build_info;
+pub use build_info::{build_info, BuildDetails};
+
+mod bundle;
+pub use bundle::export_bundle;
+
+mod checksum;
+
+mod client;
+pub use client::{SvmClient, SvmClientBuilder};
+
+mod config;
+pub use config::{config_path, Config, InstallDefaults, CONFIG_KEYS, ZKSVM_CONFIG};
+
+mod diagnostics;
+pub use diagnostics::{diagnose, DiagnosticReport, Finding, Severity};
+
+mod download_cache;
+
+mod error;
+pub use error::SvmError;
+
+mod freshness;
+pub use freshness::{Staleness, StalenessThresholds};
+
+pub mod fsutil;
+
+mod http;
+pub use http::{set_client_config, ClientConfig};
+
+mod github;
+pub use github::GITHUB_TOKEN;
+
+mod identify;
+pub use identify::identify_binary;
+
+mod import;
+pub use import::{import_binary, ImportedBinary};
+
+mod install;
+#[cfg(feature = "blocking")]
+pub use install::{
+    blocking_install, blocking_install_with_events, blocking_install_with_options,
+    install_blocking_in_place,
+};
+pub use install::{
+    ensure_installed, ensure_version_exact, ensure_version_exact_with_options, install,
+    install_bundle, install_for_platform, install_from_url, install_from_url_with_options,
+    install_many, install_many_with_events, install_status, install_verified, install_with_events,
+    install_with_options, install_with_progress, InstallEvent, InstallOptions, InstallOutcome,
+    ManagerVersionPolicy, DEFAULT_INSTALL_CONCURRENCY, DEFAULT_PROGRESS_INTERVAL,
+};
+
+mod installed;
+pub use installed::{list_installed, InstalledVersion};
+
+mod manifest;
+pub use manifest::{compiler_info, CompilerInfo};
+
+mod monitor;
+pub use monitor::{
+    check_availability, check_availability_from, release_details, release_details_from,
+    ArtifactHealth, HealthReport, PlatformHealth, ReleaseDetails,
+};
+
+pub mod netutil;
+
+mod paths;
+pub use paths::{
+    aliases_path, bin_dir, cache_dir, current_binary, current_script, data_dir, download_cache_dir,
+    global_version_path, platform_version_path, set_data_dir, setup_data_dir, tmp_dir,
+    tool_version_binary, tool_version_path, usage_path, version_binary, version_path, DataDir,
+    ZKSVM_HOME,
+};
+
+mod platform;
+pub use platform::{artifact_platform, emulation_warning, libc, platform, Libc, Platform};
+
+mod releases;
+pub use releases::{
+    all_platform_artifacts, all_platform_artifacts_from, all_releases, all_releases_from,
+    all_releases_in, clear_releases_cache, current_releases, releases_cache_ttl, BuildInfo,
+    Channel, PlatformArtifacts, ReleaseSource, Releases, SourceLayout, ZKSVM_RELEASES_SOURCE,
+    ZKSVM_RELEASES_URL,
+};
+
+#[cfg(feature = "blocking")]
+pub use releases::blocking_all_releases;
+
+mod resolve;
+#[cfg(feature = "blocking")]
+pub use resolve::blocking_resolve_version;
+pub use resolve::{best_matching_version, resolve_version, VersionFilter};
+
+mod retry;
+pub use retry::RetryPolicy;
+
+#[cfg(feature = "self-update")]
+mod self_update;
+#[cfg(feature = "self-update")]
+pub use self_update::{
+    check_self_update, remove_replaced_exe, self_update, SelfUpdate, ZKSVM_REPO,
+};
+
+mod state;
+pub use state::{
+    recover_state_file, recover_state_files, verify_state_file, Integrity, LocalState, Recovery,
+};
+
+mod svm;
+pub use svm::Svm;
+
+mod tool;
+#[cfg(feature = "solc")]
+pub use tool::SOLC_RELEASES_URL;
+pub use tool::{install_tool, installed_tool_versions, Tool};
+
+mod usage;
+pub use usage::{
+    clear_usage_stats, record_command, record_version_use, telemetry_enabled, usage_stats,
+    UsageStats, VersionUsage,
+};
+
+mod verify;
+#[cfg(feature = "blocking")]
+pub use verify::blocking_verify_installed;
+pub use verify::{
+    verify_installed, CommandVerifier, MinisignVerifier, SignatureVerifier, VerificationResult,
+    Verifier,
+};
+
+mod warning;
+pub use warning::{Warning, WithWarnings};
+
+mod workspace;
+pub use workspace::{
+    find_project_files, find_project_version, repository_root, set_project_version, ActiveVersion,
+    Policy, ProjectFile, Ready, VersionSource, Workspace, PROJECT_CONFIG_FILE,
+    PROJECT_VERSION_FILE, ZKSVM_ZKSOLC_VERSION,
+};
+
+/// Reads the currently set global version for Solc. Returns None if none has yet been set.
+///
+/// The global version file is replaced atomically, so this never sees a half-written version,
+/// but the version may not be installed anymore, see [`get_global_version_verified`].
+pub fn get_global_version() -> Result<Option<Version>, SvmError> {
+    let v = match fs::read_to_string(global_version_path()) {
+        Ok(v) => v,
+        // the data directory is only created by the first mutating operation
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(Version::parse(v.trim_end_matches('\n')).ok())
+}
+
+/// Reads the global version like [`get_global_version`], but only returns it if its binary exists.
+///
+/// The version is read under a shared lock of the data directory, so that it is not switched or
+/// removed by another process while it is checked. See [`global_version_state`] for why a set
+/// global version cannot be used.
+pub fn get_global_version_verified() -> Result<Option<Version>, SvmError> {
+    let _lock = state::lock_data_dir(false)?;
+    Ok(get_global_version()?.filter(|version| version_binary(&version.to_string()).is_file()))
+}
+
+/// The state of the global version, see [`global_version_state`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GlobalVersionState {
+    /// No global version is set.
+    Unset,
+    /// The global version is installed, with the path to its binary.
+    Active(Version, PathBuf),
+    /// A global version is set but cannot be used.
+    Broken {
+        /// The global version as written in the global version file.
+        version: String,
+        /// Why the global version cannot be used, for display.
+        reason: BrokenGlobalVersion,
+    },
+}
+
+/// Why the global version cannot be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrokenGlobalVersion {
+    /// The global version file does not contain a valid version.
+    InvalidVersion,
+    /// The version is not installed, e.g. because it was removed by hand.
+    NotInstalled,
+    /// The version directory exists but its binary does not.
+    MissingBinary,
+}
+
+impl std::fmt::Display for BrokenGlobalVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::InvalidVersion => "not a valid version",
+            Self::NotInstalled => "not installed",
+            Self::MissingBinary => "missing its binary",
+        })
+    }
+}
+
+/// Reads the global version and checks that it can be used.
+///
+/// Unlike [`get_global_version`], this tells an unset global version apart from one that points
+/// at a missing install.
+pub fn global_version_state() -> Result<GlobalVersionState, SvmError> {
+    let contents = match fs::read_to_string(global_version_path()) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(GlobalVersionState::Unset),
+        Err(err) => return Err(err.into()),
+    };
+    let contents = contents.trim();
+    if contents.is_empty() {
+        return Ok(GlobalVersionState::Unset);
+    }
+    let broken = |reason| {
+        Ok(GlobalVersionState::Broken {
+            version: contents.to_string(),
+            reason,
+        })
+    };
+    let Ok(version) = Version::parse(contents) else {
+        return broken(BrokenGlobalVersion::InvalidVersion);
+    };
+    if !version_path(&version.to_string()).is_dir() {
+        return broken(BrokenGlobalVersion::NotInstalled);
+    }
+    let binary = version_binary(&version.to_string());
+    if !binary.is_file() {
+        return broken(BrokenGlobalVersion::MissingBinary);
+    }
+    Ok(GlobalVersionState::Active(version, binary))
+}
+
+/// How the binary of the global version is provided at a fixed path, see
+/// [`set_global_version_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GlobalBinary {
+    /// [`current_binary`] links to the binary, or is a hard link or copy of it where symlinks
+    /// cannot be created, e.g. on Windows without developer mode.
+    #[default]
+    Link,
+    /// [`current_binary`] is a copy of the binary, e.g. for tools that do not follow links.
+    Copy,
+    /// [`current_script`] is a `.cmd` script running the binary, which needs neither link
+    /// privileges nor a copy of the binary on Windows.
+    Cmd,
+}
+
+impl FromStr for GlobalBinary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "link" => Ok(Self::Link),
+            "copy" => Ok(Self::Copy),
+            "cmd" => Ok(Self::Cmd),
+            _ => Err(format!("expected link, copy or cmd, got {s:?}")),
+        }
+    }
+}
+
+impl std::fmt::Display for GlobalBinary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Link => "link",
+            Self::Copy => "copy",
+            Self::Cmd => "cmd",
+        })
+    }
+}
+
+/// Sets the provided version as the global version for Solc.
+///
+/// Also provides the binary of the version at a fixed path, as configured by `global-binary` in
+/// the [`Config`], see [`set_global_version_with`].
+pub fn set_global_version(version: &Version) -> Result<(), SvmError> {
+    let binary = config::file_config().global_binary.unwrap_or_default();
+    set_global_version_with(version, binary)
+}
+
+/// Sets the provided version as the global version, providing its binary at [`current_binary`]
+/// or [`current_script`], depending on `binary`. Either is replaced in one step, so that it stays
+/// usable while switching versions.
+///
+/// Both are removed if the version is not installed.
+pub fn set_global_version_with(version: &Version, binary: GlobalBinary) -> Result<(), SvmError> {
+    setup_data_dir()?;
+    let _lock = state::lock_data_dir(true)?;
+    let target = version_binary(&version.to_string());
+    let mut switch = CurrentSwitch::default();
+    if !target.is_file() {
+        switch.remove(&current_binary())?;
+        switch.remove(&current_script())?;
+    } else {
+        // the new file is staged before anything is replaced, as copying the binary may take a
+        // while, so that an interrupted switch leaves the previous version active
+        fs::create_dir_all(data_dir().join(paths::CURRENT_DIR_NAME))?;
+        let (staged, dest, other) = match binary {
+            GlobalBinary::Cmd => {
+                // quotes cannot occur in paths on Windows, so they need no escaping
+                let script = format!("@\"{}\" %*\r\n", target.display());
+                let staged = fsutil::stage_write(&current_script(), script)?;
+                (staged, current_script(), current_binary())
+            }
+            GlobalBinary::Copy => {
+                let staged = fsutil::stage_copy(&target, &current_binary())?;
+                (staged, current_binary(), current_script())
+            }
+            GlobalBinary::Link => {
+                let staged = fsutil::stage_link(&target, &current_binary())?;
+                (staged, current_binary(), current_script())
+            }
+        };
+        switch.replace(staged, &dest)?;
+        switch.remove(&other)?;
+    }
+    state::write_state_file(&global_version_path(), &version.to_string())?;
+    switch.commit();
+    Ok(())
+}
+
+/// Changes the files of `current/`, keeping the previous ones aside to restore them when dropped
+/// before [`commit`](Self::commit), so that `current/` keeps running the recorded global version
+/// if recording the new one fails.
+#[derive(Default)]
+struct CurrentSwitch {
+    /// The previous files, along with where they were.
+    previous: Vec<(tempfile::TempPath, PathBuf)>,
+    /// The new files.
+    replaced: Vec<PathBuf>,
+}
+
+impl CurrentSwitch {
+    /// Moves the file at `path` aside, if there is one.
+    fn remove(&mut self, path: &Path) -> Result<(), SvmError> {
+        if let Some(aside) = Self::aside(path)? {
+            fs::rename(path, &aside).map_err(|err| fsutil::file_error(err, path))?;
+            self.previous.push((aside, path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    /// Moves `staged` over the file at `path`, keeping a link to the previous one.
+    fn replace(&mut self, staged: tempfile::TempPath, path: &Path) -> Result<(), SvmError> {
+        if let Some(aside) = Self::aside(path)? {
+            // `path` is replaced atomically, so that it stays usable while it is switched
+            fs::hard_link(path, &aside)
+                .or_else(|_| fs::copy(path, &aside).map(drop))
+                .map_err(|err| fsutil::file_error(err, path))?;
+            self.previous.push((aside, path.to_path_buf()));
+        }
+        fsutil::persist_path(staged, path)?;
+        self.replaced.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Reserves a name next to `path` to keep its previous file at, unless there is none.
+    fn aside(path: &Path) -> Result<Option<tempfile::TempPath>, SvmError> {
+        if let Err(err) = fs::symlink_metadata(path) {
+            return match err.kind() {
+                io::ErrorKind::NotFound => Ok(None),
+                _ => Err(fsutil::file_error(err, path)),
+            };
+        }
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let aside = tempfile::Builder::new()
+            .prefix(".previous")
+            .tempfile_in(dir)?
+            .into_temp_path();
+        fs::remove_file(&aside)?;
+        Ok(Some(aside))
+    }
+
+    /// Keeps the new files, removing the previous ones.
+    fn commit(mut self) {
+        self.replaced.clear();
+        self.previous.clear();
+    }
+}
+
+impl Drop for CurrentSwitch {
+    fn drop(&mut self) {
+        for path in self.replaced.drain(..) {
+            let _ = fs::remove_file(path);
+        }
+        for (aside, path) in self.previous.drain(..) {
+            let _ = aside.persist(path);
+        }
+    }
+}
+
+/// Unset the global version. This should be done if all versions are removed.
+pub fn unset_global_version() -> Result<(), SvmError> {
+    if !data_dir().exists() {
+        return Ok(());
+    }
+    let _lock = state::lock_data_dir(true)?;
+    remove_current()?;
+    state::write_state_file(&global_version_path(), "")
+}
+
+/// Removes [`current_binary`] and [`current_script`], so that they do not run a version that is
+/// no longer global.
+fn remove_current() -> Result<(), SvmError> {
+    remove_file_if_exists(&current_binary())?;
+    remove_file_if_exists(&current_script())
+}
+
+fn remove_file_if_exists(path: &Path) -> Result<(), SvmError> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the list of Solc versions that have been installed in the machine.
+/// The version list is sorted in ascending order.
+pub fn installed_versions() -> Result<Vec<Version>, SvmError> {
+    read_installed_versions(&data_dir())
+}
+
+pub(crate) fn read_installed_versions(data_dir: &Path) -> Result<Vec<Version>, SvmError> {
+    let entries = match fs::read_dir(data_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err.into()),
+    };
+    let mut versions = vec![];
+    for v in entries {
+        let v = v?;
+        let path = v.path();
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        // skip `.global-version`, lock files and other bookkeeping, the shim directory, the link
+        // to the global version, the binaries of other platforms and those of other tools
+        if file_name.starts_with('.')
+            || file_name == paths::BIN_DIR_NAME
+            || file_name == paths::CURRENT_DIR_NAME
+            || file_name == paths::PLATFORMS_DIR_NAME
+            || file_name == paths::TOOLS_DIR_NAME
+        {
+            continue;
+        }
+        // installs that were interrupted before the binary was moved into place
+        if path.join(paths::INCOMPLETE_MARKER_NAME).exists() {
+            continue;
+        }
+        versions.push(Version::parse(file_name)?);
+    }
+    versions.sort();
+    Ok(versions)
+}
+
+/// Blocking version of [`all_versions`]
+#[cfg(feature = "blocking")]
+pub fn blocking_all_versions() -> Result<Vec<Version>, SvmError> {
+    Ok(releases::blocking_all_releases(platform::artifact_platform()?)?.into_versions())
+}
+
+/// Fetches the list of all the available versions of Solc. The list is platform dependent, so
+/// different versions can be found for macosx vs linux.
+pub async fn all_versions() -> Result<Vec<Version>, SvmError> {
+    Ok(releases::all_releases(platform::artifact_platform()?)
+        .await?
+        .into_versions())
+}
+
+/// Blocking version of [`latest_version`]
+#[cfg(feature = "blocking")]
+pub fn blocking_latest_version() -> Result<Version, SvmError> {
+    let platform = platform::artifact_platform()?;
+    releases::blocking_all_releases(platform)?
+        .latest_version()
+        .cloned()
+        .ok_or_else(|| SvmError::NoReleases(platform.to_string()))
+}
+
+/// Fetches the newest version of zksolc available for this machine.
+pub async fn latest_version() -> Result<Version, SvmError> {
+    let platform = platform::artifact_platform()?;
+    releases::all_releases(platform)
+        .await?
+        .latest_version()
+        .cloned()
+        .ok_or_else(|| SvmError::NoReleases(platform.to_string()))
+}
+
+/// Blocking version of [`checksums_for`]
+#[cfg(feature = "blocking")]
+pub fn blocking_checksums_for(
+    versions: &[Version],
+) -> Result<BTreeMap<Version, [u8; 32]>, SvmError> {
+    Ok(releases::blocking_all_releases(platform::artifact_platform()?)?.checksums(versions))
+}
+
+/// Returns the SHA-256 checksums of the zksolc binaries of `versions`, without installing them.
+///
+/// All checksums come from a single fetch of the release list, so this is cheap enough to compute
+/// cache keys for many versions at once. Versions that are not released for this machine are
+/// left out.
+pub async fn checksums_for(versions: &[Version]) -> Result<BTreeMap<Version, [u8; 32]>, SvmError> {
+    Ok(releases::all_releases(platform::artifact_platform()?)
+        .await?
+        .checksums(versions))
+}
+
+/// Everything [`remove_version`] deleted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RemovalReport {
+    /// The removed files and directories, such as the binary, its manifest and stale lock files.
+    pub removed: Vec<PathBuf>,
+    /// Lock files that were kept because an install of the version is in progress.
+    pub kept_locks: Vec<PathBuf>,
+    /// The disk space freed, in bytes. Files still hard linked elsewhere free nothing.
+    pub reclaimed: u64,
+}
+
+/// Removes the provided version of Solc from the machine, along with all state tied to it.
+///
+/// The entry of the download cache its binary was linked to is removed as well, unless another
+/// install still links to it, and so is [`current_binary`] if it is the global version. Symlinks
+/// are never followed out of the data directory.
+pub fn remove_version(version: &Version) -> Result<RemovalReport, SvmError> {
+    let _lock = state::lock_data_dir(true)?;
+    let path = version_path(version.to_string().as_str());
+    if fs::symlink_metadata(&path).is_err() {
+        return Err(SvmError::VersionNotInstalled(version.to_string()));
+    }
+
+    let mut report = RemovalReport::default();
+    let mut cached = vec![];
+    if !fs::symlink_metadata(&path)?.file_type().is_symlink() {
+        let files = fsutil::files_in(&path)?;
+        cached = download_cache::linked_entries(&files);
+        report.reclaimed = fsutil::unshared_size(&files)?;
+        report.removed.extend(files);
+    }
+    paths::remove_dir_within_data_dir(&path)?;
+    report.removed.push(path);
+    // current/ must not keep providing the removed binary of the global version
+    if get_global_version()?.as_ref() == Some(version) {
+        report.removed.extend(
+            [current_binary(), current_script()]
+                .into_iter()
+                .filter(|path| fs::symlink_metadata(path).is_ok()),
+        );
+        remove_current()?;
+    }
+    for (entry, size) in download_cache::remove_unlinked(cached) {
+        report.reclaimed += size;
+        report.removed.push(entry);
+    }
+    install::remove_stale_lock(version, &mut report)?;
+    Ok(report)
+}
+
+/// What [`prune`] removed and kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// The removed versions, with everything that was deleted for them.
+    pub removed: Vec<(Version, RemovalReport)>,
+    /// The versions that were kept, in ascending order.
+    pub kept: Vec<Version>,
+    /// The disk space freed by removing the versions, in bytes.
+    pub reclaimed: u64,
+}
+
+/// Removes all installed versions except the global version and those `keep` returns `true` for.
+pub fn prune(keep: impl Fn(&Version) -> bool) -> Result<PruneReport, SvmError> {
+    let LocalState { installed, global } = LocalState::read()?;
+    let mut report = PruneReport::default();
+    for version in installed {
+        if global.as_ref() == Some(&version) || keep(&version) {
+            report.kept.push(version);
+            continue;
+        }
+        let removal = remove_version(&version)?;
+        report.reclaimed += removal.reclaimed;
+        report.removed.push((version, removal));
+    }
+    Ok(report)
+}
+
+/// What [`clean_download_cache`] removed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheCleanReport {
+    /// The removed entries of the download cache.
+    pub removed: Vec<PathBuf>,
+    /// The disk space freed, in bytes.
+    pub reclaimed: u64,
+}
+
+/// Removes the entries of the [download cache](download_cache_dir) that no installed version
+/// uses anymore.
+pub fn clean_download_cache() -> Result<CacheCleanReport, SvmError> {
+    let mut report = CacheCleanReport::default();
+    for (entry, size) in download_cache::clean()? {
+        report.reclaimed += size;
+        report.removed.push(entry);
+    }
+    Ok(report)
+}
+
+fn setup_version(version: &str) -> Result<(), SvmError> {
+    let v = version_path(version);
+    if !v.exists() {
+        fs::create_dir_all(v)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_installed_versions_tolerates_missing_data_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join(".zksvm");
+        assert!(read_installed_versions(&data_dir).unwrap().is_empty());
+
+        fs::create_dir_all(data_dir.join("1.3.17")).unwrap();
+        fs::create_dir_all(data_dir.join("1.3.9")).unwrap();
+        fs::write(data_dir.join(".global-version"), "1.3.17").unwrap();
+        fs::write(data_dir.join(".lock-install-zksolc-linux-amd64-1.3.18"), "").unwrap();
+        fs::create_dir_all(data_dir.join("bin")).unwrap();
+        fs::create_dir_all(data_dir.join("platforms/linux-amd64/1.3.16")).unwrap();
+        fs::create_dir_all(data_dir.join("tools/zkvyper/1.3.11")).unwrap();
+        fs::create_dir_all(data_dir.join("1.3.18")).unwrap();
+        fs::write(
+            data_dir.join("1.3.18").join(paths::INCOMPLETE_MARKER_NAME),
+            "",
+        )
+        .unwrap();
+        assert_eq!(
+            read_installed_versions(&data_dir).unwrap(),
+            vec![Version::new(1, 3, 9), Version::new(1, 3, 17)]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn global_version_states() {
+        unset_global_version().unwrap();
+        assert_eq!(global_version_state().unwrap(), GlobalVersionState::Unset);
+
+        setup_data_dir().unwrap();
+        let broken = |version: &str, reason| GlobalVersionState::Broken {
+            version: version.to_string(),
+            reason,
+        };
+        fs::write(global_version_path(), "not-a-version").unwrap();
+        assert_eq!(
+            global_version_state().unwrap(),
+            broken("not-a-version", BrokenGlobalVersion::InvalidVersion)
+        );
+
+        let version = Version::new(0, 0, 46);
+        set_global_version(&version).unwrap();
+        assert_eq!(
+            global_version_state().unwrap(),
+            broken("0.0.46", BrokenGlobalVersion::NotInstalled)
+        );
+        assert_eq!(get_global_version().unwrap(), Some(version.clone()));
+        assert_eq!(get_global_version_verified().unwrap(), None);
+
+        setup_version("0.0.46").unwrap();
+        assert_eq!(
+            global_version_state().unwrap(),
+            broken("0.0.46", BrokenGlobalVersion::MissingBinary)
+        );
+
+        let binary = version_binary("0.0.46");
+        fs::write(&binary, "zksolc").unwrap();
+        assert_eq!(
+            global_version_state().unwrap(),
+            GlobalVersionState::Active(version.clone(), binary.clone())
+        );
+        assert!(!current_binary().exists());
+        assert_eq!(
+            get_global_version_verified().unwrap(),
+            Some(version.clone())
+        );
+        set_global_version(&version).unwrap();
+        assert_eq!(fs::read(current_binary()).unwrap(), b"zksolc");
+        set_global_version_with(&version, GlobalBinary::Cmd).unwrap();
+        assert!(!current_binary().exists());
+        assert!(fs::read_to_string(current_script())
+            .unwrap()
+            .contains(&binary.display().to_string()));
+        set_global_version_with(&version, GlobalBinary::Copy).unwrap();
+        assert!(!current_script().exists());
+        assert!(!fs::symlink_metadata(current_binary()).unwrap().is_symlink());
+        assert!(installed_versions().unwrap().contains(&version));
+
+        remove_version(&version).unwrap();
+        unset_global_version().unwrap();
+        assert!(!current_binary().exists());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn remove_version_cleans_ancillary_state() {
+        let version = Version::new(0, 0, 45);
+        assert!(matches!(
+            remove_version(&version),
+            Err(SvmError::VersionNotInstalled(_))
+        ));
+
+        setup_data_dir().unwrap();
+        let dir = version_path(&version.to_string());
+        fs::create_dir_all(&dir).unwrap();
+        let binary = version_binary(&version.to_string());
+        fs::write(&binary, "zksolc").unwrap();
+        fs::write(dir.join("manifest.json"), "{}").unwrap();
+        let lock = data_dir().join(format!(
+            ".lock-install-zksolc-{}-{version}",
+            artifact_platform().unwrap()
+        ));
+        fs::write(&lock, "").unwrap();
+
+        let mut report = remove_version(&version).unwrap();
+        report.removed.sort();
+        let mut expected = vec![dir.join("manifest.json"), binary, dir.clone(), lock];
+        expected.sort();
+        assert_eq!(report.removed, expected);
+        assert!(report.kept_locks.is_empty());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn restores_current_unless_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, script) = (dir.path().join("zksolc"), dir.path().join("zksolc.cmd"));
+        let switch_to = |contents: &str| {
+            fs::write(&binary, "old").unwrap();
+            fs::write(&script, "old script").unwrap();
+            let mut switch = CurrentSwitch::default();
+            let staged = fsutil::stage_write(&binary, contents).unwrap();
+            switch.replace(staged, &binary).unwrap();
+            switch.remove(&script).unwrap();
+            assert_eq!(fs::read(&binary).unwrap(), contents.as_bytes());
+            assert!(!script.exists());
+            switch
+        };
+
+        drop(switch_to("new"));
+        assert_eq!(fs::read(&binary).unwrap(), b"old");
+        assert_eq!(fs::read(&script).unwrap(), b"old script");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        switch_to("new").commit();
+        assert_eq!(fs::read(&binary).unwrap(), b"new");
+        assert!(!script.exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn remove_version_clears_current() {
+        let version = Version::new(0, 0, 46);
+        setup_data_dir().unwrap();
+        fs::create_dir_all(version_path(&version.to_string())).unwrap();
+        fs::write(version_binary(&version.to_string()), "zksolc").unwrap();
+        set_global_version(&version).unwrap();
+        assert!(current_binary().exists());
+
+        let report = remove_version(&version).unwrap();
+        assert!(report.removed.contains(&current_binary()));
+        assert!(fs::symlink_metadata(current_binary()).is_err());
+        unset_global_version().unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn prune_keeps_global_and_matching_versions() {
+        let ours = [
+            Version::new(0, 0, 50),
+            Version::new(0, 0, 51),
+            Version::new(0, 0, 52),
+        ];
+        for version in &ours {
+            setup_version(&version.to_string()).unwrap();
+            fs::write(version_binary(&version.to_string()), "zksolc").unwrap();
+        }
+        set_global_version(&ours[0]).unwrap();
+
+        // other tests' versions are kept as well
+        let report = prune(|v| !ours.contains(v) || *v == ours[1]).unwrap();
+        let removed = report.removed.iter().map(|(v, _)| v).collect::<Vec<_>>();
+        assert_eq!(removed, [&ours[2]]);
+        assert!(report.kept.contains(&ours[0]) && report.kept.contains(&ours[1]));
+        assert_eq!(report.reclaimed, 6);
+        assert!(!version_path(&ours[2].to_string()).exists());
+
+        remove_version(&ours[0]).unwrap();
+        remove_version(&ours[1]).unwrap();
+        unset_global_version().unwrap();
+    }
+}
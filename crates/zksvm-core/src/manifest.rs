@@ -0,0 +1,183 @@
+use crate::{fsutil, version_binary, version_path, SvmError};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf, process::Command};
+
+/// Metadata about an installed zksolc version, stored next to its binary.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    /// The parsed output of `zksolc --version`, cached on first use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) compiler: Option<CompilerInfo>,
+    /// Where the binary was imported from, if it was imported instead of downloaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) imported_from: Option<PathBuf>,
+    /// The hex encoded SHA-256 checksum of a binary imported or installed from a bundle, which
+    /// is checked instead of the published one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sha256: Option<String>,
+}
+
+/// Version information reported by an installed zksolc binary.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompilerInfo {
+    /// The semantic version the binary reports.
+    pub version: Version,
+    /// The LLVM revision the binary was built with, if reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llvm_revision: Option<String>,
+    /// The commit the binary was built from, if reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// The unparsed output of `zksolc --version`.
+    pub raw: String,
+}
+
+impl CompilerInfo {
+    /// Parses the output of `zksolc --version`, e.g.
+    /// `zksolc, the ZKsync Solidity compiler v1.5.7 (LLVM build abc123, commit def456)`.
+    ///
+    /// Returns `None` if the output does not contain a version.
+    pub fn parse(output: &str) -> Option<Self> {
+        let version = output
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')'))
+            .filter_map(|word| word.strip_prefix('v'))
+            .find_map(|word| Version::parse(word).ok())?;
+        Some(Self {
+            version,
+            llvm_revision: word_after(output, &["LLVM build", "LLVM revision", "LLVM"]),
+            commit: word_after(output, &["commit"]),
+            raw: output.trim().to_string(),
+        })
+    }
+}
+
+/// Returns the word following the first of `labels` found in `output`.
+fn word_after(output: &str, labels: &[&str]) -> Option<String> {
+    labels.iter().find_map(|label| {
+        let (_, rest) = output.split_once(label)?;
+        let word = rest
+            .trim_start_matches(|c: char| c.is_whitespace() || c == ':')
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | ')'))
+            .next()?;
+        (!word.is_empty()).then(|| word.to_string())
+    })
+}
+
+fn manifest_path(version: &Version) -> PathBuf {
+    version_path(&version.to_string()).join("manifest.json")
+}
+
+/// Reads the manifest of an installed version, returning an empty one if there is none yet or it
+/// cannot be parsed.
+pub(crate) fn read_manifest(version: &Version) -> Manifest {
+    fs::read(manifest_path(version))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn write_manifest(version: &Version, manifest: &Manifest) -> Result<(), SvmError> {
+    let json = serde_json::to_vec_pretty(manifest).expect("manifest is serializable");
+    fsutil::write_atomic(&manifest_path(version), json)
+}
+
+/// Returns whether the binary was replaced after the manifest was written, e.g. by a reinstall.
+fn binary_newer_than_manifest(version: &Version) -> bool {
+    let modified = |path: PathBuf| fs::metadata(path).and_then(|m| m.modified()).ok();
+    match (
+        modified(version_binary(&version.to_string())),
+        modified(manifest_path(version)),
+    ) {
+        (Some(binary), Some(manifest)) => binary > manifest,
+        _ => true,
+    }
+}
+
+/// Returns the version information reported by an installed zksolc binary.
+///
+/// The output of `zksolc --version` is cached in the version's manifest on first use, so the
+/// binary is only spawned again after it has been reinstalled.
+pub fn compiler_info(version: &Version) -> Result<CompilerInfo, SvmError> {
+    let manifest = read_manifest(version);
+    if let Some(info) = manifest.compiler.clone() {
+        if !binary_newer_than_manifest(version) {
+            return Ok(info);
+        }
+    }
+    refresh_compiler_info(version, manifest)
+}
+
+/// Runs `zksolc --version` on the installed binary of `version` and caches its output in
+/// `manifest`, which is written back.
+pub(crate) fn refresh_compiler_info(
+    version: &Version,
+    mut manifest: Manifest,
+) -> Result<CompilerInfo, SvmError> {
+    let binary = version_binary(&version.to_string());
+    let output = match Command::new(&binary).arg("--version").output() {
+        Ok(output) => output,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(SvmError::VersionNotInstalled(version.to_string()))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let info = CompilerInfo::parse(&stdout)
+        .ok_or_else(|| SvmError::InvalidVersionOutput(binary, stdout.trim().to_string()))?;
+
+    manifest.compiler = Some(info.clone());
+    write_manifest(version, &manifest)?;
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup_version;
+
+    #[test]
+    fn parse_version_output() {
+        let info = CompilerInfo::parse("zksolc, the zkEVM Solidity compiler v1.3.17\n").unwrap();
+        assert_eq!(info.version, Version::new(1, 3, 17));
+        assert_eq!(info.llvm_revision, None);
+        assert_eq!(info.raw, "zksolc, the zkEVM Solidity compiler v1.3.17");
+
+        let info = CompilerInfo::parse(
+            "zksolc, the ZKsync Solidity compiler v1.5.7 (LLVM build 1a2b3c, commit 4d5e6f)",
+        )
+        .unwrap();
+        assert_eq!(info.version, Version::new(1, 5, 7));
+        assert_eq!(info.llvm_revision.as_deref(), Some("1a2b3c"));
+        assert_eq!(info.commit.as_deref(), Some("4d5e6f"));
+
+        assert!(CompilerInfo::parse("error: unknown option").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn caches_version_output() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let version = Version::new(0, 0, 7);
+        setup_version(&version.to_string()).unwrap();
+        let binary = version_binary(&version.to_string());
+        fs::write(
+            &binary,
+            "#!/bin/sh\necho 'zksolc v0.0.7 (LLVM build abc)'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let info = compiler_info(&version).unwrap();
+        assert_eq!(info.version, version);
+        assert_eq!(read_manifest(&version).compiler, Some(info.clone()));
+
+        // served from the manifest without spawning the binary again
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(compiler_info(&version).unwrap(), info);
+
+        crate::remove_version(&version).unwrap();
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{
+    http, installed_versions, platform,
+    releases::{download_releases, ReleaseSource, Releases},
+    Platform, SvmError,
+};
+use reqwest::{header, Method, StatusCode};
+use semver::Version;
+use serde::Serialize;
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::Semaphore, task::JoinSet};
+use url::Url;
+
+/// How many artifacts are checked at the same time.
+const MONITOR_CONCURRENCY: usize = 16;
+
+/// The timeout of each request made by [`check_availability`].
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The availability of everything advertised by the release lists of some platforms.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    /// Whether every release list could be fetched and every artifact is available and has a
+    /// checksum.
+    pub healthy: bool,
+    pub platforms: Vec<PlatformHealth>,
+}
+
+/// The availability of the release list of a platform and the artifacts it advertises.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PlatformHealth {
+    pub platform: String,
+    pub healthy: bool,
+    /// Why the release list could not be fetched, if it could not.
+    pub error: Option<String>,
+    pub artifacts: Vec<ArtifactHealth>,
+}
+
+/// The availability of a single advertised artifact.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ArtifactHealth {
+    pub version: Version,
+    pub url: String,
+    pub healthy: bool,
+    /// The status of the response, if there was one.
+    pub status: Option<u16>,
+    /// Why the artifact is unavailable, if it is.
+    pub error: Option<String>,
+    /// Whether the release list has a checksum for the artifact.
+    pub checksum: bool,
+}
+
+/// What is known about a release of the release list of a platform, see [`release_details`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseDetails {
+    pub version: Version,
+    /// The file name of the artifact.
+    pub artifact: String,
+    /// The published checksum of the artifact, if the release list has one.
+    pub sha256: Option<Vec<u8>>,
+    /// The size of the artifact in bytes, from the release list or else the server, if either
+    /// tells.
+    pub size: Option<u64>,
+    /// Whether the version is installed.
+    pub installed: bool,
+}
+
+/// Describes the artifacts of those of `versions` listed in `releases`, the release list of
+/// this machine, in the same order.
+///
+/// Sizes missing from the release list are requested from the server with `HEAD`, so only pass
+/// the versions of interest.
+pub async fn release_details(
+    releases: &Releases,
+    versions: &[Version],
+) -> Result<Vec<ReleaseDetails>, SvmError> {
+    let source = ReleaseSource::from_env()?;
+    release_details_from(&source, platform::artifact_platform()?, releases, versions).await
+}
+
+/// Describes the artifacts of `versions` in `releases`, the release list of `platform` fetched
+/// from `source`.
+pub async fn release_details_from(
+    source: &ReleaseSource,
+    platform: Platform,
+    releases: &Releases,
+    versions: &[Version],
+) -> Result<Vec<ReleaseDetails>, SvmError> {
+    let installed = installed_versions()?;
+    let client = http::client()?.timeout(REQUEST_TIMEOUT).build()?;
+    let semaphore = Arc::new(Semaphore::new(MONITOR_CONCURRENCY));
+
+    let mut tasks = JoinSet::new();
+    for (i, version) in versions.iter().enumerate() {
+        let Some(artifact) = releases.get_artifact(version) else {
+            continue;
+        };
+        let mut details = ReleaseDetails {
+            version: version.clone(),
+            artifact: artifact.clone(),
+            sha256: releases.get_checksum(version),
+            size: releases
+                .builds
+                .iter()
+                .find(|build| build.version == *version)
+                .and_then(|build| build.size),
+            installed: installed.contains(version),
+        };
+        let url = source.version_url(platform, version, releases).ok();
+        let (client, semaphore) = (client.clone(), semaphore.clone());
+        tasks.spawn(async move {
+            if let (None, Some(url)) = (details.size, url) {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                details.size = artifact_size(&client, &url).await;
+            }
+            (i, details)
+        });
+    }
+    let mut details = Vec::with_capacity(versions.len());
+    while let Some(joined) = tasks.join_next().await {
+        details.push(joined.expect("size request panicked"));
+    }
+    details.sort_by_key(|(i, _)| *i);
+    Ok(details.into_iter().map(|(_, details)| details).collect())
+}
+
+/// Checks that every artifact advertised for `platforms` responds and has a checksum.
+///
+/// Release lists are always fetched fresh, and artifacts are only requested with `HEAD`, so this
+/// is cheap enough to run periodically against the hosted binaries.
+pub async fn check_availability(platforms: &[Platform]) -> Result<HealthReport, SvmError> {
+    Ok(check_availability_from(&ReleaseSource::from_env()?, platforms).await)
+}
+
+/// Checks the availability of the artifacts of `platforms` hosted by a specific source.
+pub async fn check_availability_from(
+    source: &ReleaseSource,
+    platforms: &[Platform],
+) -> HealthReport {
+    let client =
+        match http::client().and_then(|client| Ok(client.timeout(REQUEST_TIMEOUT).build()?)) {
+            Ok(client) => client,
+            Err(err) => {
+                let platforms = platforms
+                    .iter()
+                    .map(|platform| PlatformHealth::failed(*platform, err.to_string()))
+                    .collect();
+                return HealthReport {
+                    healthy: false,
+                    platforms,
+                };
+            }
+        };
+    let semaphore = Arc::new(Semaphore::new(MONITOR_CONCURRENCY));
+
+    let mut reports = Vec::with_capacity(platforms.len());
+    for &platform in platforms {
+        let releases = match download_releases(source, platform, Some(REQUEST_TIMEOUT)).await {
+            Ok(releases) => releases,
+            Err(err) => {
+                reports.push(PlatformHealth::failed(platform, err.to_string()));
+                continue;
+            }
+        };
+
+        let mut tasks = JoinSet::new();
+        for (i, (version, artifact)) in releases.releases.iter().enumerate() {
+            let checksum = releases.get_checksum(version).is_some();
+            let url = source.version_url(platform, version, &releases);
+            let (version, client, semaphore) = (version.clone(), client.clone(), semaphore.clone());
+            let artifact = artifact.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                let (url, status, error) = match url {
+                    Ok(url) => {
+                        let (status, error) = probe(&client, &url).await;
+                        (url.to_string(), status, error)
+                    }
+                    Err(err) => (artifact, None, Some(err.to_string())),
+                };
+                let healthy = checksum && error.is_none();
+                let error = error.or_else(|| (!checksum).then(|| "no checksum".to_string()));
+                (
+                    i,
+                    ArtifactHealth {
+                        version,
+                        url,
+                        healthy,
+                        status,
+                        error,
+                        checksum,
+                    },
+                )
+            });
+        }
+        let mut artifacts = Vec::with_capacity(releases.releases.len());
+        while let Some(joined) = tasks.join_next().await {
+            artifacts.push(joined.expect("availability check panicked"));
+        }
+        artifacts.sort_by_key(|(i, _)| *i);
+        let artifacts: Vec<_> = artifacts
+            .into_iter()
+            .map(|(_, artifact)| artifact)
+            .collect();
+
+        reports.push(PlatformHealth {
+            platform: platform.to_string(),
+            healthy: artifacts.iter().all(|artifact| artifact.healthy),
+            error: None,
+            artifacts,
+        });
+    }
+
+    HealthReport {
+        healthy: reports.iter().all(|platform| platform.healthy),
+        platforms: reports,
+    }
+}
+
+impl PlatformHealth {
+    fn failed(platform: Platform, error: String) -> Self {
+        Self {
+            platform: platform.to_string(),
+            healthy: false,
+            error: Some(error),
+            artifacts: vec![],
+        }
+    }
+}
+
+/// Requests `url` with `HEAD`, returning the status and why it is not available, if it is not.
+///
+/// Hosts that do not allow `HEAD` are asked for the first byte instead.
+async fn probe(client: &reqwest::Client, url: &Url) -> (Option<u16>, Option<String>) {
+    let mut res = client.head(url.clone()).send().await;
+    if matches!(&res, Ok(res) if res.status() == StatusCode::METHOD_NOT_ALLOWED) {
+        res = client
+            .request(Method::GET, url.clone())
+            .header(header::RANGE, "bytes=0-0")
+            .send()
+            .await;
+    }
+    match res {
+        Ok(res) if res.status().is_success() => (Some(res.status().as_u16()), None),
+        Ok(res) => (
+            Some(res.status().as_u16()),
+            Some(format!("unexpected status {}", res.status())),
+        ),
+        Err(err) => (None, Some(SvmError::from(err).to_string())),
+    }
+}
+
+/// Requests the size of the artifact at `url` with `HEAD`.
+///
+/// Hosts that do not allow `HEAD` are asked for the first byte instead, whose `Content-Range`
+/// has the size.
+async fn artifact_size(client: &reqwest::Client, url: &Url) -> Option<u64> {
+    let res = client.head(url.clone()).send().await.ok()?;
+    // the body of a response to `HEAD` is empty, so `Response::content_length` would be zero
+    let (header, res) = if res.status() == StatusCode::METHOD_NOT_ALLOWED {
+        let res = client
+            .get(url.clone())
+            .header(header::RANGE, "bytes=0-0")
+            .send()
+            .await
+            .ok()?;
+        (header::CONTENT_RANGE, res)
+    } else {
+        (header::CONTENT_LENGTH, res)
+    };
+    if !res.status().is_success() {
+        return None;
+    }
+    let value = res.headers().get(header)?.to_str().ok()?;
+    value.rsplit('/').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    /// Serves a release list with two artifacts, of which only the first exists.
+    fn serve() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let list = serde_json::json!({
+            "builds": [{ "version": "1.3.17", "sha256": format!("0x{}", "ab".repeat(32)) }],
+            "releases": { "1.3.17": "zksolc-v1.3.17", "1.3.16": "zksolc-v1.3.16" },
+        })
+        .to_string();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let response = if request.contains("list.json") {
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{list}",
+                        list.len()
+                    )
+                } else if request.starts_with("HEAD") && request.contains("v1.3.17") {
+                    "HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n".to_string()
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn reports_broken_artifacts() {
+        let source = ReleaseSource::new(serve()).unwrap();
+        let report = check_availability_from(&source, &[Platform::LinuxAmd64]).await;
+        assert!(!report.healthy);
+        let [platform] = &report.platforms[..] else {
+            panic!("unexpected report {report:?}");
+        };
+        assert_eq!(platform.error, None);
+        let [missing, available] = &platform.artifacts[..] else {
+            panic!("unexpected report {report:?}");
+        };
+
+        assert_eq!(available.version, Version::new(1, 3, 17));
+        assert!(available.healthy);
+        assert_eq!(available.status, Some(200));
+
+        assert_eq!(missing.version, Version::new(1, 3, 16));
+        assert!(!missing.healthy);
+        assert!(!missing.checksum);
+        assert_eq!(missing.status, Some(404));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn describes_releases() {
+        let source = ReleaseSource::new(serve()).unwrap();
+        let platform = Platform::LinuxAmd64;
+        let releases = download_releases(&source, platform, Some(REQUEST_TIMEOUT))
+            .await
+            .unwrap();
+        let versions = [
+            Version::new(1, 3, 17),
+            Version::new(1, 3, 15),
+            Version::new(1, 3, 16),
+        ];
+        let details = release_details_from(&source, platform, &releases, &versions)
+            .await
+            .unwrap();
+        let [available, missing] = &details[..] else {
+            panic!("unexpected details {details:?}");
+        };
+
+        assert_eq!(available.version, Version::new(1, 3, 17));
+        assert_eq!(available.artifact, "zksolc-v1.3.17");
+        assert_eq!(available.sha256, Some(vec![0xab; 32]));
+        assert_eq!(available.size, Some(6));
+
+        assert_eq!(missing.version, Version::new(1, 3, 16));
+        assert_eq!(missing.sha256, None);
+        assert_eq!(missing.size, None);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_release_lists() {
+        let source = ReleaseSource::new(Url::parse("http://127.0.0.1:9/").unwrap()).unwrap();
+        let report =
+            check_availability_from(&source, &[Platform::LinuxAmd64, Platform::MacOsAarch64]).await;
+        assert!(!report.healthy);
+        assert_eq!(report.platforms.len(), 2);
+        assert!(report
+            .platforms
+            .iter()
+            .all(|platform| platform.error.is_some()));
+    }
+}
@@ -0,0 +1,3 @@
+//! Network helpers for fetching tool binaries, as used for zksolc itself.
+
+pub use crate::retry::{blocking_retry, is_transient, retry, RetryPolicy};
@@ -0,0 +1,571 @@
+use crate::{platform::Platform, SvmError, Tool};
+use std::{
+    cell::RefCell,
+    ffi::OsString,
+    fs,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    sync::{OnceLock, RwLock},
+};
+
+/// Setup ZKSVM home directory.
+pub fn setup_data_dir() -> Result<(), SvmError> {
+    // create $XDG_DATA_HOME or ~/.local/share/zksvm, or fallback to ~/.zksvm
+    let data_dir = data_dir();
+
+    // Create the directory, continuing if the directory came into existence after the check
+    // for this if statement. This may happen if two copies of zksvm run simultaneously (e.g CI).
+    fs::create_dir_all(&data_dir).or_else(|err| match err.kind() {
+        io::ErrorKind::AlreadyExists => Ok(()),
+        _ => Err(err),
+    })?;
+
+    // Check that the zksvm directory is indeed a directory, and not e.g. a file.
+    if !data_dir.is_dir() {
+        return Err(SvmError::IoError(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("zksvm data dir '{}' is not a directory", data_dir.display()),
+        )));
+    }
+
+    // Create `$ZKSVM/.global-version`.
+    let global_version = global_version_path();
+    if !global_version.exists() {
+        fs::File::create(&global_version)?;
+    }
+
+    Ok(())
+}
+
+/// Environment variable used to override the data directory.
+pub const ZKSVM_HOME: &str = "ZKSVM_HOME";
+
+/// The data directory set with [`set_data_dir`], if any.
+static DATA_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+thread_local! {
+    /// The data directory of the [`Svm`](crate::Svm) running a blocking operation on this thread.
+    static THREAD_DATA_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+tokio::task_local! {
+    /// The data directory of the [`Svm`](crate::Svm) running an async operation in this task.
+    static TASK_DATA_DIR: PathBuf;
+}
+
+/// A zksvm data directory, which holds the installed versions, the global version and caches.
+///
+/// Separate data directories are fully isolated from each other, e.g. to give each CI job its
+/// own installs or to sandbox tests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataDir {
+    path: PathBuf,
+}
+
+impl DataDir {
+    /// Creates a handle to the data directory at `path`, which is created once it is used.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the data directory used unless [`set_data_dir`] was called: `$ZKSVM_HOME` if set,
+    /// otherwise the `data-dir` of the [`Config`](crate::Config) if set, otherwise `~/.zksvm` if
+    /// it exists, otherwise `$XDG_DATA_HOME/zksvm`.
+    pub fn from_env() -> Self {
+        static ONCE: OnceLock<PathBuf> = OnceLock::new();
+        Self::new(ONCE.get_or_init(|| {
+            #[cfg(test)]
+            {
+                let dir = tempfile::tempdir().expect("could not create temp directory");
+                dir.path().join(".zksvm")
+            }
+            #[cfg(not(test))]
+            {
+                resolve_data_dir(
+                    std::env::var_os(ZKSVM_HOME)
+                        .filter(|home| !home.is_empty())
+                        .or_else(|| crate::config::file_config().data_dir.map(Into::into)),
+                )
+            }
+        }))
+    }
+
+    /// Returns the data directory operations currently use: the one of the [`Svm`](crate::Svm)
+    /// running them, otherwise the one set with [`set_data_dir`], otherwise
+    /// [`DataDir::from_env`].
+    pub fn current() -> Self {
+        if let Ok(path) = TASK_DATA_DIR.try_with(PathBuf::clone) {
+            return Self::new(path);
+        }
+        if let Some(path) = THREAD_DATA_DIR.with(|dir| dir.borrow().clone()) {
+            return Self::new(path);
+        }
+        match &*DATA_DIR.read().unwrap_or_else(|err| err.into_inner()) {
+            Some(path) => Self::new(path),
+            None => Self::from_env(),
+        }
+    }
+
+    /// Runs `f` with this as the data directory of all operations on the current thread.
+    pub(crate) fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        /// Restores the previous data directory of the thread, even if `f` panics.
+        struct Restore(Option<PathBuf>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                THREAD_DATA_DIR.with(|dir| *dir.borrow_mut() = self.0.take());
+            }
+        }
+
+        let previous = THREAD_DATA_DIR.with(|dir| dir.replace(Some(self.path.clone())));
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Runs `fut` with this as the data directory of all operations it performs, including
+    /// those of tasks spawned with [`spawn_scoped`].
+    pub(crate) async fn scope_async<F: Future>(&self, fut: F) -> F::Output {
+        TASK_DATA_DIR.scope(self.path.clone(), fut).await
+    }
+
+    /// Returns the path to the data directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Spawns `fut` onto `tasks`, running it with the data directory and the HTTP client
+/// configuration of the spawning task.
+pub(crate) fn spawn_scoped<T: Send + 'static>(
+    tasks: &mut tokio::task::JoinSet<T>,
+    fut: impl Future<Output = T> + Send + 'static,
+) {
+    let dir = DataDir::current();
+    let client = crate::http::task_config();
+    tasks.spawn(async move {
+        match client {
+            Some(client) => dir.scope_async(crate::http::scope_async(client, fut)).await,
+            None => dir.scope_async(fut).await,
+        }
+    });
+}
+
+/// Makes `dir` the data directory of all operations from now on, instead of the one
+/// [`DataDir::from_env`] returns.
+///
+/// This applies to the whole process. Use an [`Svm`](crate::Svm) to run only some operations in
+/// another data directory.
+pub fn set_data_dir(dir: impl Into<DataDir>) {
+    *DATA_DIR.write().unwrap_or_else(|err| err.into_inner()) = Some(dir.into().path);
+}
+
+impl From<PathBuf> for DataDir {
+    fn from(path: PathBuf) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&Path> for DataDir {
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+
+/// Returns the path to the data directory, see [`DataDir::current`].
+pub fn data_dir() -> PathBuf {
+    DataDir::current().path
+}
+
+/// Resolves the default data directory, which is `home` if set.
+fn resolve_data_dir(home: Option<OsString>) -> PathBuf {
+    if let Some(home) = home.filter(|home| !home.is_empty()) {
+        return PathBuf::from(home);
+    }
+    let home_dir = dirs::home_dir()
+        .expect("could not detect user home directory")
+        .join(".zksvm");
+
+    let data_dir = dirs::data_dir().expect("could not detect user data directory");
+    if !home_dir.exists() && data_dir.exists() {
+        data_dir.join("zksvm")
+    } else {
+        home_dir
+    }
+}
+
+/// Environment variable used to override the directory downloads are staged in.
+const ZKSVM_TMPDIR: &str = "ZKSVM_TMPDIR";
+
+/// Returns the directory downloads are staged in before being moved into the data directory.
+///
+/// This is `$ZKSVM_TMPDIR` if set, otherwise the system temporary directory. Overriding it is
+/// useful when `/tmp` is a size-limited tmpfs, e.g. in containers.
+pub fn tmp_dir() -> PathBuf {
+    match std::env::var_os(ZKSVM_TMPDIR) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => std::env::temp_dir(),
+    }
+}
+
+/// Environment variable used to override the directory downloaded zksolc binaries are cached in.
+const ZKSVM_DOWNLOAD_CACHE: &str = "ZKSVM_DOWNLOAD_CACHE";
+
+/// Returns the directory downloaded zksolc binaries are cached in, keyed by their checksum.
+///
+/// This is `$ZKSVM_DOWNLOAD_CACHE` if set, otherwise `cache_dir() / downloads`. Pointing several
+/// users at the same directory shares downloads between them. On the same filesystem as the data
+/// directory, installs hard link or reflink from the cache instead of copying.
+pub fn download_cache_dir() -> PathBuf {
+    match std::env::var_os(ZKSVM_DOWNLOAD_CACHE) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => cache_dir().join("downloads"),
+    }
+}
+
+/// Recursively removes `path`, which must live inside the data directory.
+///
+/// See [`remove_dir_within`] for how symlinks are handled.
+pub(crate) fn remove_dir_within_data_dir(path: &Path) -> Result<(), SvmError> {
+    remove_dir_within(&data_dir(), path)
+}
+
+/// Recursively removes `path`, refusing to delete anything outside of `root`.
+///
+/// `root` itself may be a symlink, e.g. when managed by a dotfile manager. If `path` is a symlink,
+/// only the link is removed and its target is left untouched.
+fn remove_dir_within(root: &Path, path: &Path) -> Result<(), SvmError> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        return fs::remove_file(path).map_err(Into::into);
+    }
+
+    let root = fs::canonicalize(root)?;
+    let canonical = fs::canonicalize(path)?;
+    if canonical == root || !canonical.starts_with(&root) {
+        return Err(SvmError::PathOutsideDataDir(path.to_path_buf()));
+    }
+    // `remove_dir_all` does not follow symlinks nested inside of the directory.
+    fs::remove_dir_all(canonical).map_err(Into::into)
+}
+
+/// Returns the path to the global version file.
+pub fn global_version_path() -> PathBuf {
+    data_dir().join(".global-version")
+}
+
+/// Returns the path to the file aliases of versions are kept in, see
+/// [`set_alias`](crate::set_alias).
+pub fn aliases_path() -> PathBuf {
+    data_dir().join(".aliases.toml")
+}
+
+/// Returns the path to the file usage stats are kept in, see
+/// [`usage_stats`](crate::usage_stats).
+pub fn usage_path() -> PathBuf {
+    data_dir().join(".usage.toml")
+}
+
+/// Returns the directory cached downloads such as release lists are kept in.
+///
+/// This is currently `data_dir() / .cache`.
+pub fn cache_dir() -> PathBuf {
+    data_dir().join(".cache")
+}
+
+/// Name of the directory in the data directory that `zksvm init` puts the `zksolc` shim in.
+pub(crate) const BIN_DIR_NAME: &str = "bin";
+
+/// Returns the directory the `zksolc` shim is installed in by default, to be added to `PATH`.
+///
+/// This is currently `data_dir() / bin`.
+pub fn bin_dir() -> PathBuf {
+    data_dir().join(BIN_DIR_NAME)
+}
+
+/// Name of the directory in the data directory holding a link to the binary of the global
+/// version.
+pub(crate) const CURRENT_DIR_NAME: &str = "current";
+
+/// Returns the path that points at the binary of the global version, to be added to `PATH` by
+/// users who do not need the shim to select versions per project.
+///
+/// This is currently `data_dir() / current / zksolc`, with `.exe` appended on Windows. It is
+/// updated by [`set_global_version`](crate::set_global_version), unless that provides the
+/// binary with [`current_script`] instead.
+pub fn current_binary() -> PathBuf {
+    data_dir()
+        .join(CURRENT_DIR_NAME)
+        .join(format!("zksolc{}", std::env::consts::EXE_SUFFIX))
+}
+
+/// Returns the path of the script running the binary of the global version, which replaces
+/// [`current_binary`] with [`GlobalBinary::Cmd`](crate::GlobalBinary::Cmd).
+///
+/// This is currently `data_dir() / current / zksolc.cmd`.
+pub fn current_script() -> PathBuf {
+    data_dir().join(CURRENT_DIR_NAME).join("zksolc.cmd")
+}
+
+/// Returns the path to a specific zksolc version's directory.
+///
+/// Note that this is not the path to the actual Solc binary file;
+/// use [`version_binary`] for that instead.
+///
+/// This is currently `data_dir() / {version}`.
+pub fn version_path(version: &str) -> PathBuf {
+    data_dir().join(version)
+}
+
+/// Name of the marker an install leaves in a version's directory until its binary is in place.
+pub(crate) const INCOMPLETE_MARKER_NAME: &str = ".incomplete";
+
+/// Returns the path to the marker of an install of `version` that has not completed.
+///
+/// Version directories holding it are not reported as installed, as the install was interrupted
+/// before the binary was moved into place.
+pub(crate) fn incomplete_marker(version: &str) -> PathBuf {
+    version_path(version).join(INCOMPLETE_MARKER_NAME)
+}
+
+/// Name of the directory in the data directory that binaries of other platforms are installed in.
+pub(crate) const PLATFORMS_DIR_NAME: &str = "platforms";
+
+/// Returns the path to the directory of a zksolc version installed for another platform with
+/// [`install_for_platform`](crate::install_for_platform).
+///
+/// This is currently `data_dir() / platforms / {platform} / {version}`.
+pub fn platform_version_path(platform: Platform, version: &str) -> PathBuf {
+    versions_dir(Tool::Zksolc, Some(platform)).join(version)
+}
+
+/// Name of the directory in the data directory that tools other than zksolc are installed in.
+pub(crate) const TOOLS_DIR_NAME: &str = "tools";
+
+/// Returns the directory the versions of `tool` built for `platform`, or this machine if `None`,
+/// are installed in.
+///
+/// zksolc versions live directly in the data directory, those of other tools in
+/// `data_dir() / tools / {tool}`. Binaries of other platforms are kept below `platforms /
+/// {platform}` of that.
+pub(crate) fn versions_dir(tool: Tool, platform: Option<Platform>) -> PathBuf {
+    let mut dir = data_dir();
+    if tool != Tool::Zksolc {
+        dir = dir.join(TOOLS_DIR_NAME).join(tool.name());
+    }
+    if let Some(platform) = platform {
+        dir = dir.join(PLATFORMS_DIR_NAME).join(platform.to_string());
+    }
+    dir
+}
+
+/// Returns the path to the directory of a version of `tool`.
+///
+/// This is [`version_path`] for zksolc, and `data_dir() / tools / {tool} / {version}` otherwise.
+pub fn tool_version_path(tool: Tool, version: &str) -> PathBuf {
+    versions_dir(tool, None).join(version)
+}
+
+/// Derive path to the binary file of a version of `tool`.
+///
+/// This is [`version_binary`] for zksolc, and `tool_version_path(tool, version) /
+/// {tool}-{version}` otherwise.
+pub fn tool_version_binary(tool: Tool, version: &str) -> PathBuf {
+    match tool {
+        Tool::Zksolc => version_binary(version),
+        tool => tool_version_path(tool, version).join(format!("{tool}-{version}")),
+    }
+}
+
+/// The names zksolc binaries are installed under, oldest first. Newer releases are moving from
+/// `zksolc-*` to `era-solc-*` artifact names.
+pub(crate) const BINARY_NAMES: &[&str] = &["zksolc", "era-solc"];
+
+/// Environment variable used to override the name new installs are stored under.
+const ZKSVM_BINARY_NAME: &str = "ZKSVM_BINARY_NAME";
+
+/// Returns the name a version with the given artifact is installed under: `$ZKSVM_BINARY_NAME`
+/// if set, otherwise the [known name](BINARY_NAMES) the artifact starts with, or `zksolc`.
+pub(crate) fn binary_name(artifact: &str) -> String {
+    if let Some(name) = configured_binary_name() {
+        return name;
+    }
+    BINARY_NAMES
+        .iter()
+        .find(|name| {
+            artifact
+                .strip_prefix(*name)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['-', '.']))
+        })
+        .unwrap_or(&BINARY_NAMES[0])
+        .to_string()
+}
+
+/// Returns `$ZKSVM_BINARY_NAME`, ignoring values which are not plain file names.
+fn configured_binary_name() -> Option<String> {
+    std::env::var(ZKSVM_BINARY_NAME).ok().filter(|name| {
+        !matches!(name.as_str(), "" | "." | "..")
+            && !name.contains(|c: char| std::path::is_separator(c) || c.is_control())
+    })
+}
+
+/// Derive path to a specific zksolc version's binary file.
+///
+/// This is `data_dir() / {version} / {name}-{version}`, where `name` is whichever of
+/// `$ZKSVM_BINARY_NAME`, `zksolc` and `era-solc` the installed binary uses, or `zksolc` if the
+/// version is not installed.
+pub fn version_binary(version: &str) -> PathBuf {
+    let configured = configured_binary_name();
+    let names = configured
+        .as_deref()
+        .into_iter()
+        .chain(BINARY_NAMES.iter().copied());
+    let mut default = None;
+    for name in names {
+        let binary = version_binary_named(version, name);
+        if binary.exists() {
+            return binary;
+        }
+        default.get_or_insert(binary);
+    }
+    default.expect("there are known binary names")
+}
+
+/// Derive path to a specific zksolc version's binary file, installed under `name`.
+///
+/// This is `data_dir() / {version} / {name}-{version}`.
+pub(crate) fn version_binary_named(version: &str, name: &str) -> PathBuf {
+    let data_dir = data_dir();
+    let sep = std::path::MAIN_SEPARATOR_STR;
+    let cap = data_dir.as_os_str().len()
+        + sep.len()
+        + version.len()
+        + sep.len()
+        + name.len()
+        + 1
+        + version.len();
+    let mut binary = OsString::with_capacity(cap);
+    binary.push(&data_dir);
+    debug_assert!(!data_dir.ends_with(sep));
+    binary.push(sep);
+
+    binary.push(version);
+    binary.push(sep);
+
+    binary.push(name);
+    binary.push("-");
+    binary.push(version);
+    PathBuf::from(binary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_dir_resolution() {
+        let home_dir = dirs::home_dir().unwrap().join(".zksvm");
+        let data_dir = dirs::data_dir();
+        let resolved_dir = resolve_data_dir(None);
+        match data_dir {
+            Some(data_dir) if !home_dir.exists() && data_dir.exists() => {
+                assert_eq!(resolved_dir, data_dir.join("zksvm"));
+            }
+            _ => assert_eq!(resolved_dir, home_dir),
+        }
+
+        assert_eq!(
+            resolve_data_dir(Some("/ci/job-1/zksvm".into())),
+            Path::new("/ci/job-1/zksvm")
+        );
+        assert_eq!(resolve_data_dir(Some("".into())), resolved_dir);
+        assert_eq!(DataDir::current(), DataDir::from_env());
+    }
+
+    #[test]
+    fn remove_dir_within_root() {
+        let root = tempfile::tempdir().unwrap();
+        let version = root.path().join("1.3.17");
+        fs::create_dir_all(&version).unwrap();
+        fs::write(version.join("zksolc-1.3.17"), "").unwrap();
+
+        remove_dir_within(root.path(), &version).unwrap();
+        assert!(!version.exists());
+        assert!(root.path().exists());
+
+        assert!(matches!(
+            remove_dir_within(root.path(), root.path()),
+            Err(SvmError::PathOutsideDataDir(_))
+        ));
+        assert!(matches!(
+            remove_dir_within(root.path(), &root.path().join("..")),
+            Err(SvmError::PathOutsideDataDir(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn remove_dir_within_symlinked_layouts() {
+        use std::os::unix::fs::symlink;
+
+        let outside = tempfile::tempdir().unwrap();
+        fs::write(outside.path().join("precious"), "").unwrap();
+
+        // a symlinked data dir is resolved and can be cleaned as usual
+        let real_root = tempfile::tempdir().unwrap();
+        let links = tempfile::tempdir().unwrap();
+        let root = links.path().join(".zksvm");
+        symlink(real_root.path(), &root).unwrap();
+        fs::create_dir_all(root.join("1.3.17")).unwrap();
+        remove_dir_within(&root, &root.join("1.3.17")).unwrap();
+        assert!(!real_root.path().join("1.3.17").exists());
+
+        // a version dir linking out of the root is unlinked, its target is kept
+        symlink(outside.path(), root.join("1.3.18")).unwrap();
+        remove_dir_within(&root, &root.join("1.3.18")).unwrap();
+        assert!(fs::symlink_metadata(root.join("1.3.18")).is_err());
+        assert!(outside.path().join("precious").exists());
+
+        // links nested inside of a version dir are not followed either
+        fs::create_dir_all(root.join("1.3.19")).unwrap();
+        symlink(outside.path(), root.join("1.3.19").join("escape")).unwrap();
+        remove_dir_within(&root, &root.join("1.3.19")).unwrap();
+        assert!(!root.join("1.3.19").exists());
+        assert!(outside.path().join("precious").exists());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_tmp_dir_override() {
+        std::env::set_var(ZKSVM_TMPDIR, "/some/tmp");
+        assert_eq!(tmp_dir(), PathBuf::from("/some/tmp"));
+        std::env::set_var(ZKSVM_TMPDIR, "");
+        assert_eq!(tmp_dir(), std::env::temp_dir());
+        std::env::remove_var(ZKSVM_TMPDIR);
+        assert_eq!(tmp_dir(), std::env::temp_dir());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn resolves_binary_names() {
+        assert_eq!(binary_name("zksolc-linux-amd64-musl-v1.3.17"), "zksolc");
+        assert_eq!(binary_name("era-solc-linux-amd64-gnu-v1.6.0"), "era-solc");
+        assert_eq!(binary_name("zksolc-1.3.17"), "zksolc");
+        assert_eq!(binary_name("zksolcx-v1.3.17"), "zksolc");
+        assert_eq!(binary_name(""), "zksolc");
+
+        let version = "0.0.3";
+        assert!(version_binary(version).ends_with("zksolc-0.0.3"));
+        fs::create_dir_all(version_path(version)).unwrap();
+        fs::write(version_path(version).join("era-solc-0.0.3"), "").unwrap();
+        assert!(version_binary(version).ends_with("era-solc-0.0.3"));
+
+        std::env::set_var(ZKSVM_BINARY_NAME, "zksolc-custom");
+        assert_eq!(binary_name("era-solc-v1.6.0"), "zksolc-custom");
+        // installed binaries are still found under their own name
+        assert!(version_binary(version).ends_with("era-solc-0.0.3"));
+        std::env::set_var(ZKSVM_BINARY_NAME, "../zksolc");
+        assert_eq!(binary_name("era-solc-v1.6.0"), "era-solc");
+        std::env::remove_var(ZKSVM_BINARY_NAME);
+        fs::remove_dir_all(version_path(version)).unwrap();
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{SvmError, Warning};
+use serde::{Deserialize, Serialize};
+use std::fmt::Formatter;
+use std::str::FromStr;
+use std::{env, fmt};
+
+/// Environment variable that disables falling back to artifacts running under emulation.
+const ZKSVM_NO_EMULATION: &str = "ZKSVM_NO_EMULATION";
+
+/// Types of supported platforms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Platform {
+    LinuxAmd64,
+    LinuxAarch64,
+    MacOsAmd64,
+    MacOsAarch64,
+    WindowsAmd64,
+    WindowsAarch64,
+    Unsupported,
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Platform::LinuxAmd64 => "linux-amd64",
+            Platform::LinuxAarch64 => "linux-aarch64",
+            Platform::MacOsAmd64 => "macosx-amd64",
+            Platform::MacOsAarch64 => "macosx-aarch64",
+            Platform::WindowsAmd64 => "windows-amd64",
+            Platform::WindowsAarch64 => "windows-aarch64",
+            Platform::Unsupported => "Unsupported-platform",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for Platform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "linux-amd64" => Ok(Platform::LinuxAmd64),
+            "linux-aarch64" => Ok(Platform::LinuxAarch64),
+            "macosx-amd64" => Ok(Platform::MacOsAmd64),
+            "macosx-aarch64" => Ok(Platform::MacOsAarch64),
+            "windows-amd64" => Ok(Platform::WindowsAmd64),
+            "windows-aarch64" => Ok(Platform::WindowsAarch64),
+            s => Err(format!("unsupported platform {s}")),
+        }
+    }
+}
+
+/// The C library a Linux build of zksolc is linked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Libc {
+    /// The GNU C library, which most distributions use.
+    #[serde(alias = "gnu")]
+    Glibc,
+    /// musl, used by e.g. Alpine Linux. Its builds are statically linked.
+    Musl,
+}
+
+impl fmt::Display for Libc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Libc::Glibc => "glibc",
+            Libc::Musl => "musl",
+        })
+    }
+}
+
+impl FromStr for Libc {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "glibc" | "gnu" => Ok(Libc::Glibc),
+            "musl" => Ok(Libc::Musl),
+            s => Err(format!("unsupported libc {s}, expected glibc or musl")),
+        }
+    }
+}
+
+/// Returns the C library of the current machine, or `None` if it does not run Linux.
+///
+/// Machines are taken to use musl if its dynamic loader, `/lib/ld-musl-<arch>.so.1`, exists.
+pub fn libc() -> Option<Libc> {
+    if env::consts::OS != "linux" {
+        return None;
+    }
+    let loaders = std::fs::read_dir("/lib")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok());
+    Some(libc_of_loaders(loaders))
+}
+
+/// Returns the C library whose dynamic loader is among the files in `/lib`.
+fn libc_of_loaders(mut names: impl Iterator<Item = String>) -> Libc {
+    if names.any(|name| name.starts_with("ld-musl-")) {
+        Libc::Musl
+    } else {
+        Libc::Glibc
+    }
+}
+
+/// Read the current machine's platform.
+pub fn platform() -> Platform {
+    match (env::consts::OS, env::consts::ARCH) {
+        ("linux", "x86_64") => Platform::LinuxAmd64,
+        ("linux", "aarch64") => Platform::LinuxAarch64,
+        ("macos", "x86_64") => Platform::MacOsAmd64,
+        ("macos", "aarch64") => Platform::MacOsAarch64,
+        ("windows", "x86_64") => Platform::WindowsAmd64,
+        ("windows", "aarch64") => Platform::WindowsAarch64,
+        _ => Platform::Unsupported,
+    }
+}
+
+impl Platform {
+    /// Returns whether this is a Linux platform, whose builds may exist for several C libraries.
+    pub fn is_linux(self) -> bool {
+        matches!(self, Platform::LinuxAmd64 | Platform::LinuxAarch64)
+    }
+
+    /// All platforms zksolc artifacts are published for.
+    pub const WITH_ARTIFACTS: [Platform; 5] = [
+        Platform::LinuxAmd64,
+        Platform::LinuxAarch64,
+        Platform::MacOsAmd64,
+        Platform::MacOsAarch64,
+        Platform::WindowsAmd64,
+    ];
+
+    /// Returns whether zksolc artifacts are published for this platform.
+    pub fn has_native_artifacts(self) -> bool {
+        !matches!(self, Platform::WindowsAarch64 | Platform::Unsupported)
+    }
+
+    /// Returns the platform whose artifacts this platform can run under emulation, if any.
+    ///
+    /// Apple Silicon runs x64 builds under Rosetta, which is only used for versions without an
+    /// arm64 build.
+    pub fn emulated(self) -> Option<Platform> {
+        match self {
+            Platform::WindowsAarch64 => Some(Platform::WindowsAmd64),
+            Platform::MacOsAarch64 => Some(Platform::MacOsAmd64),
+            _ => None,
+        }
+    }
+
+    /// Returns the platform whose builds to use for versions without a build for this one, which
+    /// does have builds of its own.
+    pub(crate) fn fallback(self) -> Option<Platform> {
+        self.emulated()
+            .filter(|_| self.has_native_artifacts() && emulation_allowed())
+    }
+
+    /// Returns the platform to download zksolc artifacts for to run them on this platform, like
+    /// [`artifact_platform`] does for the current machine.
+    pub fn artifacts(self) -> Result<Platform, SvmError> {
+        resolve_artifact_platform(self, emulation_allowed())
+    }
+}
+
+/// Returns the platform to download zksolc artifacts for on the current machine.
+///
+/// This is the machine's [`platform`], unless no artifacts are published for it and it can run
+/// those of another platform under emulation, e.g. x64 artifacts on Windows ARM. Setting
+/// `$ZKSVM_NO_EMULATION` disables this fallback and returns an error instead.
+pub fn artifact_platform() -> Result<Platform, SvmError> {
+    platform().artifacts()
+}
+
+/// Returns whether artifacts may be run under emulation, unless disabled by
+/// `$ZKSVM_NO_EMULATION`.
+pub(crate) fn emulation_allowed() -> bool {
+    env::var_os(ZKSVM_NO_EMULATION).map_or(true, |v| v.is_empty())
+}
+
+/// Returns a warning if `artifacts`, as returned by [`artifact_platform`], differs from the
+/// machine's platform.
+pub fn emulation_warning(artifacts: Platform) -> Option<Warning> {
+    let platform = platform();
+    (platform != artifacts).then_some(Warning::Emulated {
+        platform,
+        artifacts,
+    })
+}
+
+fn resolve_artifact_platform(
+    platform: Platform,
+    emulation_allowed: bool,
+) -> Result<Platform, SvmError> {
+    if platform.has_native_artifacts() {
+        return Ok(platform);
+    }
+    match platform.emulated() {
+        Some(emulated) if emulation_allowed => Ok(emulated),
+        Some(_) => Err(SvmError::UnsupportedPlatform(format!(
+            "{platform} (emulation fallback disabled by ${ZKSVM_NO_EMULATION})"
+        ))),
+        None => Err(SvmError::UnsupportedPlatform(platform.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn get_platform() {
+        assert_eq!(platform(), Platform::LinuxAmd64);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+    fn get_platform() {
+        assert_eq!(platform(), Platform::LinuxAarch64);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+    fn get_platform() {
+        assert_eq!(platform(), Platform::MacOsAmd64);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    fn get_platform() {
+        assert_eq!(platform(), Platform::MacOsAarch64);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    fn get_platform() {
+        assert_eq!(platform(), Platform::WindowsAmd64);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "windows", target_arch = "aarch64"))]
+    fn get_platform() {
+        assert_eq!(platform(), Platform::WindowsAarch64);
+    }
+
+    #[test]
+    fn detects_libc() {
+        let names = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            libc_of_loaders(names(&["ld-musl-x86_64.so.1", "libc.musl-x86_64.so.1"]).into_iter()),
+            Libc::Musl
+        );
+        assert_eq!(
+            libc_of_loaders(names(&["ld-linux-x86-64.so.2", "x86_64-linux-gnu"]).into_iter()),
+            Libc::Glibc
+        );
+        assert_eq!("gnu".parse(), Ok(Libc::Glibc));
+        assert_eq!(Libc::Musl.to_string().parse(), Ok(Libc::Musl));
+        assert!("uclibc".parse::<Libc>().is_err());
+        #[cfg(not(target_os = "linux"))]
+        assert_eq!(libc(), None);
+    }
+
+    #[test]
+    fn platforms_with_artifacts() {
+        for platform in Platform::WITH_ARTIFACTS {
+            assert!(platform.has_native_artifacts());
+            assert_eq!(platform.to_string().parse::<Platform>().unwrap(), platform);
+        }
+    }
+
+    #[test]
+    fn platform_roundtrip() {
+        for platform in [
+            Platform::LinuxAmd64,
+            Platform::LinuxAarch64,
+            Platform::MacOsAmd64,
+            Platform::MacOsAarch64,
+            Platform::WindowsAmd64,
+            Platform::WindowsAarch64,
+        ] {
+            assert_eq!(platform.to_string().parse::<Platform>(), Ok(platform));
+        }
+    }
+
+    #[test]
+    fn windows_aarch64_emulation_fallback() {
+        assert_eq!(
+            resolve_artifact_platform(Platform::WindowsAarch64, true).unwrap(),
+            Platform::WindowsAmd64
+        );
+        assert!(matches!(
+            resolve_artifact_platform(Platform::WindowsAarch64, false),
+            Err(SvmError::UnsupportedPlatform(_))
+        ));
+        assert_eq!(
+            resolve_artifact_platform(Platform::LinuxAmd64, false).unwrap(),
+            Platform::LinuxAmd64
+        );
+        assert!(matches!(
+            resolve_artifact_platform(Platform::Unsupported, true),
+            Err(SvmError::UnsupportedPlatform(_))
+        ));
+    }
+
+    #[test]
+    fn apple_silicon_only_emulates_missing_builds() {
+        assert_eq!(
+            Platform::MacOsAarch64.emulated(),
+            Some(Platform::MacOsAmd64)
+        );
+        assert_eq!(
+            resolve_artifact_platform(Platform::MacOsAarch64, true).unwrap(),
+            Platform::MacOsAarch64
+        );
+    }
+}
@@ -0,0 +1,1679 @@
+use crate::{
+    config,
+    error::SvmError,
+    github, http, paths,
+    platform::{self, Libc, Platform},
+    StalenessThresholds, Tool, Warning, WithWarnings,
+};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+use url::Url;
+
+/// Environment variable overriding the base URL of the default [`ReleaseSource`], or a comma
+/// separated list of base URLs to try in order.
+pub const ZKSVM_RELEASES_URL: &str = "ZKSVM_RELEASES_URL";
+
+/// The base URL release lists and artifacts are downloaded from by default.
+const DEFAULT_RELEASES_URL: &str =
+    "https://github.com/dutterbutter/zksolc-bin/raw/db/generate-list";
+
+/// Environment variable selecting where releases are tracked: `matter-labs` for the official
+/// GitHub Releases, `github:<owner>/<repo>` for those of another repository, or `default`.
+/// Takes precedence over [`ZKSVM_RELEASES_URL`].
+pub const ZKSVM_RELEASES_SOURCE: &str = "ZKSVM_RELEASES_SOURCE";
+
+/// The base URLs tried in order if [`DEFAULT_RELEASES_URL`] cannot serve a release list, e.g.
+/// because its development branch was merged and deleted upstream.
+const DEFAULT_FALLBACK_URLS: &[&str] = &["https://github.com/dutterbutter/zksolc-bin/raw/main"];
+
+/// A release channel of zksolc.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// Tagged releases.
+    #[default]
+    Stable,
+    /// Pre-release builds such as betas and nightlies, along with the stable releases.
+    Prerelease,
+}
+
+impl Channel {
+    /// Returns the channel `version` is published in.
+    pub fn of(version: &Version) -> Self {
+        if version.pre.is_empty() {
+            Self::Stable
+        } else {
+            Self::Prerelease
+        }
+    }
+
+    /// Returns whether `version` is available in this channel.
+    pub fn includes(self, version: &Version) -> bool {
+        self == Self::Prerelease || version.pre.is_empty()
+    }
+
+    /// Returns the name of the release list of this channel in each platform directory.
+    fn list_file(self) -> &'static str {
+        match self {
+            Self::Stable => "list.json",
+            Self::Prerelease => "list-prerelease.json",
+        }
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Stable => "stable",
+            Self::Prerelease => "prerelease",
+        })
+    }
+}
+
+impl FromStr for Channel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stable" => Ok(Self::Stable),
+            "prerelease" | "nightly" => Ok(Self::Prerelease),
+            s => Err(format!("unknown release channel {s}")),
+        }
+    }
+}
+
+/// How a [`ReleaseSource`] publishes its releases.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SourceLayout {
+    /// One directory per platform below the base URL, each containing a `list.json` release
+    /// list and the artifacts it references, e.g. `{base}/linux-amd64/list.json`. Pre-releases
+    /// are listed in `list-prerelease.json` next to it. Mirrors have to replicate this layout.
+    #[default]
+    Lists,
+    /// The GitHub Releases of the repository whose API URL is the base URL, e.g.
+    /// `https://api.github.com/repos/matter-labs/era-compiler-solidity/`. Each release tag is a
+    /// version, with the builds attached as assets named like `zksolc-linux-amd64-musl-v1.5.7`.
+    GithubReleases,
+}
+
+/// Where release lists and zksolc artifacts are downloaded from.
+///
+/// A source is a base URL publishing releases in a [`SourceLayout`], by default the
+/// [`Lists`](SourceLayout::Lists) of a static file host.
+///
+/// If the base URL cannot serve a release list, its fallbacks are tried in order. The fallback
+/// that served it is remembered in [`cache_dir`](crate::cache_dir), and artifacts are downloaded
+/// from there as well until [`clear_releases_cache`] is called.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseSource {
+    base_url: Url,
+    fallbacks: Vec<Url>,
+    channel: Channel,
+    layout: SourceLayout,
+    tool: Tool,
+}
+
+impl Default for ReleaseSource {
+    fn default() -> Self {
+        Self::new(Url::parse(DEFAULT_RELEASES_URL).expect("valid default URL"))
+            .and_then(|source| {
+                source.with_fallbacks(
+                    DEFAULT_FALLBACK_URLS
+                        .iter()
+                        .map(|url| Url::parse(url).expect("valid fallback URL")),
+                )
+            })
+            .expect("valid default URLs")
+    }
+}
+
+impl ReleaseSource {
+    /// Creates a source serving releases below `base_url`.
+    ///
+    /// Fails with [`SvmError::InvalidReleaseSource`] if `base_url` cannot have paths below it,
+    /// like `mailto:` URLs.
+    pub fn new(base_url: Url) -> Result<Self, SvmError> {
+        check_base_url(&base_url)?;
+        Ok(Self {
+            base_url,
+            fallbacks: vec![],
+            channel: Channel::Stable,
+            layout: SourceLayout::Lists,
+            tool: Tool::Zksolc,
+        })
+    }
+
+    /// Creates a source tracking the GitHub Releases of `repo`, e.g. `matter-labs/zksolc-bin`.
+    pub fn github(repo: &str) -> Result<Self, SvmError> {
+        Ok(Self::new(github::repo_url(repo)?)?.with_layout(SourceLayout::GithubReleases))
+    }
+
+    /// Creates a source tracking the official zksolc releases of
+    /// `matter-labs/era-compiler-solidity`.
+    pub fn matter_labs() -> Self {
+        Self::github(github::MATTER_LABS_REPO).expect("valid repository")
+    }
+
+    /// Sets how the base URL publishes releases.
+    pub fn with_layout(mut self, layout: SourceLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Returns how the base URL publishes releases.
+    pub fn layout(&self) -> SourceLayout {
+        self.layout
+    }
+
+    /// Sets the tool whose releases are listed, which picks the assets of
+    /// [GitHub Releases](SourceLayout::GithubReleases) by their name.
+    pub fn with_tool(mut self, tool: Tool) -> Self {
+        self.tool = tool;
+        self
+    }
+
+    /// Returns the tool whose releases are listed.
+    pub fn tool(&self) -> Tool {
+        self.tool
+    }
+
+    /// Sets the base URLs to try in order if `base_url` cannot serve a release list.
+    ///
+    /// Fails like [`new`](Self::new) if one of them cannot have paths below it.
+    pub fn with_fallbacks(
+        mut self,
+        fallbacks: impl IntoIterator<Item = Url>,
+    ) -> Result<Self, SvmError> {
+        self.fallbacks = fallbacks.into_iter().collect();
+        self.fallbacks.iter().try_for_each(check_base_url)?;
+        Ok(self)
+    }
+
+    /// Returns the base URLs tried in order if the base URL cannot serve a release list.
+    pub fn fallbacks(&self) -> &[Url] {
+        &self.fallbacks
+    }
+
+    /// Sets the channel whose releases are listed.
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Returns the channel whose releases are listed.
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    /// Returns the source configured by `$ZKSVM_RELEASES_SOURCE`, `$ZKSVM_RELEASES_URL` or the
+    /// `releases-source` or `releases-url` of the [`Config`](crate::Config), or the default
+    /// source.
+    ///
+    /// The first URL of `$ZKSVM_RELEASES_URL` is the base URL, any others are its fallbacks.
+    pub fn from_env() -> Result<Self, SvmError> {
+        let config = config::file_config();
+        let env_url = std::env::var(ZKSVM_RELEASES_URL).ok();
+        let name = match std::env::var(ZKSVM_RELEASES_SOURCE) {
+            Ok(name) if !name.trim().is_empty() => Some(name),
+            // a URL set in the environment still takes precedence over a source of the file
+            _ if env_url.is_some() => None,
+            _ => config.releases_source,
+        };
+        if let Some(source) = name.as_deref().map(Self::named).transpose()?.flatten() {
+            return Ok(source);
+        }
+        let Some(urls) = env_url.or(config.releases_url) else {
+            return Ok(Self::default());
+        };
+        let mut urls = urls
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(Url::parse);
+        let Some(base_url) = urls.next() else {
+            return Ok(Self::default());
+        };
+        Self::new(base_url?)?.with_fallbacks(urls.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Parses a source named like in `$ZKSVM_RELEASES_SOURCE`: `matter-labs`,
+    /// `github:<owner>/<repo>`, or `default`, for which `None` is returned.
+    pub(crate) fn named(name: &str) -> Result<Option<Self>, SvmError> {
+        match name.trim() {
+            "" | "default" => Ok(None),
+            "matter-labs" => Ok(Some(Self::matter_labs())),
+            name => match name.strip_prefix("github:") {
+                Some(repo) => Self::github(repo).map(Some),
+                None => Err(SvmError::InvalidReleaseSource(name.to_string())),
+            },
+        }
+    }
+
+    /// Returns the base URL of this source.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Returns the base URL release lists and artifacts are currently downloaded from: the
+    /// fallback that last served the release list, if any, or the base URL.
+    pub fn served_from(&self) -> &Url {
+        fs::read_to_string(self.served_from_path())
+            .ok()
+            .and_then(|url| {
+                self.fallbacks
+                    .iter()
+                    .find(|fallback| fallback.as_str() == url)
+            })
+            .unwrap_or(&self.base_url)
+    }
+
+    /// Returns a warning if the base URL could not serve the release list last time, so that a
+    /// fallback is used instead.
+    pub(crate) fn fallback_warning(&self) -> Option<Warning> {
+        let served_from = self.served_from();
+        (served_from != &self.base_url).then(|| Warning::FallbackSource {
+            base_url: self.base_url.clone(),
+            served_from: served_from.clone(),
+        })
+    }
+
+    /// Returns the base URLs to fetch the release list from in order, starting with the one that
+    /// served it last time.
+    fn candidates(&self) -> Vec<&Url> {
+        let served_from = self.served_from();
+        std::iter::once(served_from)
+            .chain(std::iter::once(&self.base_url))
+            .chain(&self.fallbacks)
+            .fold(vec![], |mut candidates, url| {
+                if !candidates.contains(&url) {
+                    candidates.push(url);
+                }
+                candidates
+            })
+    }
+
+    /// Remembers that `base_url` served the release list, best effort.
+    fn remember_served_from(&self, base_url: &Url) {
+        let path = self.served_from_path();
+        if *base_url == self.base_url {
+            let _ = fs::remove_file(path);
+        } else if fs::create_dir_all(paths::cache_dir()).is_ok() {
+            let _ = crate::fsutil::write_atomic(&path, base_url.as_str().as_bytes());
+        }
+    }
+
+    /// Returns the file remembering the fallback that served the release list.
+    fn served_from_path(&self) -> PathBuf {
+        let digest = sha2::Sha256::digest(self.base_url.as_str());
+        paths::cache_dir().join(format!("releases-source-{}.txt", hex::encode(&digest[..4])))
+    }
+
+    /// Returns the URL of the release list of the channel of this source for `platform`.
+    ///
+    /// GitHub Releases list all platforms at once.
+    pub fn releases_url(&self, platform: Platform) -> Url {
+        match self.layout {
+            SourceLayout::Lists => self.platform_url(platform, self.channel.list_file()),
+            SourceLayout::GithubReleases => github::releases_url(self.served_from()),
+        }
+    }
+
+    /// Returns the URL of an artifact listed in the release list of `platform`, for sources with
+    /// the [`Lists`](SourceLayout::Lists) layout. Use [`version_url`](Self::version_url) for any
+    /// source.
+    ///
+    /// Fails with [`SvmError::InvalidArtifactName`] unless `artifact` is a plain file name, so
+    /// that a malformed release list cannot point outside of the directory of `platform`.
+    pub fn artifact_url(&self, platform: Platform, artifact: &str) -> Result<Url, SvmError> {
+        if !is_valid_artifact_name(artifact) {
+            return Err(SvmError::InvalidArtifactName(artifact.to_string()));
+        }
+        Ok(self.platform_url(platform, artifact))
+    }
+
+    /// Returns the URL of the artifact of `version` in `releases`, the release list of `platform`
+    /// fetched from this source.
+    ///
+    /// Fails with [`SvmError::UnknownVersion`] unless `releases` list the version.
+    pub fn version_url(
+        &self,
+        platform: Platform,
+        version: &Version,
+        releases: &Releases,
+    ) -> Result<Url, SvmError> {
+        let artifact = releases
+            .get_artifact(version)
+            .ok_or(SvmError::UnknownVersion)?;
+        match self.layout {
+            SourceLayout::Lists => self.artifact_url(platform, artifact),
+            // GitHub serves assets below the tag of their release, which is not listed
+            SourceLayout::GithubReleases => releases
+                .builds
+                .iter()
+                .filter(|build| build.version == *version)
+                .filter_map(|build| Url::parse(build.url.as_deref()?).ok())
+                .find(|url| {
+                    url.scheme() == "https" && url.path().ends_with(&format!("/{artifact}"))
+                })
+                .ok_or_else(|| SvmError::InvalidArtifactName(artifact.to_string())),
+        }
+    }
+
+    fn platform_url(&self, platform: Platform, file: &str) -> Url {
+        platform_url(self.served_from(), self.tool.platform_dir(platform), file)
+    }
+}
+
+/// Returns the name of the directory of `platform`, which its artifacts are named after as well.
+pub(crate) fn platform_dir(platform: Platform) -> &'static str {
+    match platform {
+        Platform::LinuxAmd64 => "linux-amd64",
+        Platform::LinuxAarch64 => "linux-arm64",
+        Platform::MacOsAmd64 => "macosx-amd64",
+        Platform::MacOsAarch64 => "macosx-arm64",
+        Platform::WindowsAmd64 => "windows-amd64",
+        Platform::WindowsAarch64 => "windows-arm64",
+        Platform::Unsupported => "unsupported",
+    }
+}
+
+/// Checks that `url` can be the base URL of a [`ReleaseSource`], i.e. have paths below it.
+pub(crate) fn check_base_url(url: &Url) -> Result<(), SvmError> {
+    if url.cannot_be_a_base() {
+        return Err(SvmError::InvalidReleaseSource(url.to_string()));
+    }
+    Ok(())
+}
+
+/// Returns the URL of `file` in the platform directory `dir` below `base_url`.
+fn platform_url(base_url: &Url, dir: &str, file: &str) -> Url {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .expect("checked by ReleaseSource::new")
+        .pop_if_empty()
+        .push(dir)
+        .push(file);
+    url
+}
+
+/// Returns whether `artifact` is a plain file name, which URL resolution leaves untouched.
+///
+/// Besides separators, control characters are rejected as URL parsing drops some of them, and
+/// `%` as `%2e` resolves like a dot.
+pub(crate) fn is_valid_artifact_name(artifact: &str) -> bool {
+    !matches!(artifact, "" | "." | "..")
+        && !artifact.contains(|c: char| matches!(c, '/' | '\\' | '%') || c.is_control())
+}
+
+/// Defines the struct that the JSON-formatted release list can be deserialized into.
+///
+/// Both the key and value are deserialized into [`semver::Version`].
+///
+/// ```json
+/// {
+///     "builds": [
+///         {
+///             "version": "1.3.17",
+///             "sha256": "0xcc5c663d1fe17d4eb4aca09253787ac86b8785235fca71d9200569e662677990"
+///         }
+///     ]
+///     "releases": {
+///         "1.3.17": "zksolc-macosx-amd64-v0.8.7+commit.e28d00a7",
+///         "1.3.16": "zksolc-macosx-amd64-v0.8.6+commit.11564f7e",
+///         ...
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Releases {
+    pub builds: Vec<BuildInfo>,
+    pub releases: BTreeMap<Version, String>,
+    /// When this list was fetched from the source, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339_opt")]
+    pub fetched_at: Option<SystemTime>,
+    /// The oldest version that can be installed on this platform, if the list declares one, e.g.
+    /// because older builds did not run on it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<Version>,
+}
+
+impl Releases {
+    /// Returns the range of stable versions that can be installed from this list, which starts at
+    /// its [`min_version`](Self::min_version) if it declares one.
+    pub fn supported_versions(&self) -> VersionReq {
+        match &self.min_version {
+            Some(min) => VersionReq::parse(&format!(">={min}")).expect("valid requirement"),
+            None => VersionReq::STAR,
+        }
+    }
+
+    /// Returns whether `version` can be installed from this list if it lists it, which is the case
+    /// unless it is older than the list's [`min_version`](Self::min_version).
+    ///
+    /// Unlike [`supported_versions`](Self::supported_versions), this includes pre-releases.
+    pub fn is_supported(&self, version: &Version) -> bool {
+        self.min_version.as_ref().map_or(true, |min| version >= min)
+    }
+
+    /// Get the checksum of a solc version's binary if it exists.
+    pub fn get_checksum(&self, v: &Version) -> Option<Vec<u8>> {
+        for build in self.builds.iter() {
+            if build.version.eq(v) {
+                return Some(build.sha256.clone());
+            }
+        }
+        None
+    }
+
+    /// Returns the checksums of those of `versions` that are listed.
+    pub fn checksums(&self, versions: &[Version]) -> BTreeMap<Version, [u8; 32]> {
+        self.builds
+            .iter()
+            .filter(|build| versions.contains(&build.version))
+            .filter_map(|build| {
+                Some((
+                    build.version.clone(),
+                    build.sha256.as_slice().try_into().ok()?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the signature of the version if any
+    pub fn get_signature(&self, version: &Version) -> Option<&str> {
+        self.builds
+            .iter()
+            .find(|build| build.version == *version)
+            .and_then(|build| build.signature.as_deref())
+    }
+
+    /// Returns the oldest zksvm version able to install the version, if the release list
+    /// declares one.
+    pub fn get_min_manager_version(&self, version: &Version) -> Option<&Version> {
+        self.builds
+            .iter()
+            .find(|build| build.version == *version)
+            .and_then(|build| build.min_manager_version.as_ref())
+    }
+
+    /// Adds the releases of `other` that are not listed yet.
+    fn merge(&mut self, other: Releases) {
+        for build in other.builds {
+            if !self.builds.iter().any(|b| b.version == build.version) {
+                self.builds.push(build);
+            }
+        }
+        for (version, artifact) in other.releases {
+            self.releases.entry(version).or_insert(artifact);
+        }
+    }
+
+    /// Selects the builds linked against `libc` of versions that have builds for several C
+    /// libraries, making their artifacts and checksums the ones returned for those versions.
+    ///
+    /// Versions with a single build keep it, whichever C library it is linked against.
+    pub fn for_libc(mut self, libc: Libc) -> Self {
+        let selected = self
+            .builds
+            .iter()
+            .filter(|build| build.libc == Some(libc))
+            .map(|build| (build.version.clone(), build.path.clone()))
+            .collect::<Vec<_>>();
+        for (version, path) in selected {
+            self.builds
+                .retain(|build| build.version != version || build.libc == Some(libc));
+            if let Some(path) = path {
+                self.releases.insert(version, path);
+            }
+        }
+        self
+    }
+
+    /// Keeps only the releases available in `channel`.
+    fn in_channel(mut self, channel: Channel) -> Self {
+        self.builds.retain(|build| channel.includes(&build.version));
+        self.releases.retain(|version, _| channel.includes(version));
+        self
+    }
+
+    /// Returns the artifact of the version if any
+    pub fn get_artifact(&self, version: &Version) -> Option<&String> {
+        self.releases.get(version)
+    }
+
+    /// Returns the build info of the newest version, if any.
+    pub fn latest_build(&self) -> Option<&BuildInfo> {
+        self.builds.iter().max_by(|a, b| a.version.cmp(&b.version))
+    }
+
+    /// Returns the newest version that can be installed, if any.
+    pub fn latest_version(&self) -> Option<&Version> {
+        self.releases.keys().next_back()
+    }
+
+    /// Marks the list as fetched at the current time, truncated to whole seconds.
+    fn fetched_now(mut self) -> Self {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        self.fetched_at = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(now.as_secs()));
+        self
+    }
+
+    /// Returns a sorted list of all versions
+    pub fn into_versions(self) -> Vec<Version> {
+        let mut versions = self.releases.into_keys().collect::<Vec<_>>();
+        versions.sort_unstable();
+        versions
+    }
+}
+
+/// Build info contains the SHA256 checksum of a solc binary.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BuildInfo {
+    pub version: Version,
+    #[serde(with = "hex_string")]
+    pub sha256: Vec<u8>,
+    /// When this build was published, if the source provides it.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339_opt")]
+    pub published_at: Option<SystemTime>,
+    /// The detached signature of this build, e.g. the contents of a `.minisig` file, if the
+    /// source provides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// The oldest zksvm version able to install this build, e.g. after a change of the artifact
+    /// format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_manager_version: Option<Version>,
+    /// The C library this Linux build is linked against, if the release list lists builds for
+    /// several of them. See [`Releases::for_libc`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub libc: Option<Libc>,
+    /// The artifact of this build, if it differs from the one listed in
+    /// [`Releases::releases`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Where the artifact of this build is downloaded from, for sources that do not serve it at
+    /// a predictable URL, see [`SourceLayout::GithubReleases`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The size of the artifact of this build in bytes, if the source provides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+impl BuildInfo {
+    /// Creates the build info of `version` with the checksum `sha256`, and none of the optional
+    /// fields set.
+    pub fn new(version: Version, sha256: Vec<u8>) -> Self {
+        Self {
+            version,
+            sha256,
+            published_at: None,
+            signature: None,
+            min_manager_version: None,
+            libc: None,
+            path: None,
+            url: None,
+            size: None,
+        }
+    }
+}
+
+/// Helper serde module to serialize and deserialize bytes as hex.
+mod hex_string {
+    use super::*;
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = hex::decode(String::deserialize(deserializer)?).map_err(de::Error::custom)?;
+        if bytes.len() != 32 {
+            return Err(de::Error::invalid_length(
+                bytes.len(),
+                &"a 32 byte SHA-256 checksum",
+            ));
+        }
+        Ok(bytes)
+    }
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]>,
+    {
+        serializer.serialize_str(&hex::encode_prefixed(value))
+    }
+}
+
+/// Helper serde module to serialize and deserialize optional timestamps as RFC 3339 strings.
+mod rfc3339_opt {
+    use super::*;
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<SystemTime>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| humantime::parse_rfc3339_weak(&s).map_err(de::Error::custom))
+            .transpose()
+    }
+
+    pub fn serialize<S>(value: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(time) => serializer.serialize_str(&humantime::format_rfc3339(*time).to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Blocking version of [`all_releases`].
+#[cfg(feature = "blocking")]
+pub fn blocking_all_releases(platform: Platform) -> Result<Releases, SvmError> {
+    let source = ReleaseSource::from_env()?;
+    let mut releases = blocking_fetch_releases(&source, platform, None)?;
+    if let Some(fallback) = platform.fallback() {
+        if let Ok(emulated) = blocking_fetch_releases(&source, fallback, None) {
+            releases.merge(emulated);
+        }
+    }
+    Ok(releases)
+}
+
+/// Blocking version of [`fetch_releases`].
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_fetch_releases(
+    source: &ReleaseSource,
+    platform: Platform,
+    timeout: Option<Duration>,
+) -> Result<Releases, SvmError> {
+    let mut releases = blocking_fetch_list(source, platform, timeout)?;
+    // GitHub Releases list pre-releases along with the stable releases
+    if source.channel == Channel::Prerelease && source.layout == SourceLayout::Lists {
+        let stable = source.clone().with_channel(Channel::Stable);
+        releases.merge(blocking_fetch_list(&stable, platform, timeout)?);
+    }
+    Ok(releases.in_channel(source.channel))
+}
+
+/// Blocking version of [`fetch_list`].
+#[cfg(feature = "blocking")]
+fn blocking_fetch_list(
+    source: &ReleaseSource,
+    platform: Platform,
+    timeout: Option<Duration>,
+) -> Result<Releases, SvmError> {
+    if let Some(releases) = read_cache(source, platform, releases_cache_ttl()) {
+        return Ok(releases);
+    }
+    let fetched = blocking_download_releases(source, platform, timeout);
+    cached_or_fetched(source, platform, fetched)
+}
+
+/// Blocking version of [`download_releases`].
+#[cfg(feature = "blocking")]
+fn blocking_download_releases(
+    source: &ReleaseSource,
+    platform: Platform,
+    timeout: Option<Duration>,
+) -> Result<Releases, SvmError> {
+    let mut client = http::blocking_client()?;
+    if let Some(timeout) = timeout {
+        client = client.timeout(timeout);
+    }
+    let client = client.build()?;
+    let mut last_err = None;
+    for base_url in source.candidates() {
+        let res = match source.layout {
+            SourceLayout::Lists => {
+                let url = platform_url(
+                    base_url,
+                    source.tool.platform_dir(platform),
+                    source.channel.list_file(),
+                );
+                match client.get(url.clone()).send() {
+                    Ok(res) if res.status().is_success() => {
+                        res.json::<Releases>().map_err(Into::into)
+                    }
+                    Ok(res) => Err(SvmError::UnsuccessfulResponse(url, res.status())),
+                    Err(err) => Err(err.into()),
+                }
+            }
+            SourceLayout::GithubReleases => github::blocking_download_releases(
+                &client,
+                base_url,
+                platform,
+                source.tool,
+                source.channel,
+            ),
+        };
+        match res {
+            Ok(releases) => {
+                tracing::debug!(%base_url, versions = releases.releases.len(), "fetched the release list");
+                source.remember_served_from(base_url);
+                return Ok(releases);
+            }
+            Err(err) => {
+                tracing::warn!(%base_url, %err, "could not fetch the release list");
+                last_err = Some(err)
+            }
+        }
+    }
+    Err(last_err.expect("a source has a base URL"))
+}
+
+/// Fetch all releases available for the provided platform.
+///
+/// The list is cached in [`cache_dir`](crate::cache_dir) for [`releases_cache_ttl`]. On platforms
+/// that can run the builds of another one under emulation, e.g. Apple Silicon, the versions only
+/// published for that one are included as well.
+pub async fn all_releases(platform: Platform) -> Result<Releases, SvmError> {
+    with_emulated_releases(&ReleaseSource::from_env()?, platform).await
+}
+
+/// Fetch all releases of `channel` available for the provided platform, like [`all_releases`].
+pub async fn all_releases_in(platform: Platform, channel: Channel) -> Result<Releases, SvmError> {
+    with_emulated_releases(&ReleaseSource::from_env()?.with_channel(channel), platform).await
+}
+
+/// Fetches the releases of `platform` and adds those only published for its
+/// [`fallback`](Platform::fallback), if that list can be fetched.
+async fn with_emulated_releases(
+    source: &ReleaseSource,
+    platform: Platform,
+) -> Result<Releases, SvmError> {
+    let mut releases = fetch_releases(source, platform, None).await?;
+    if let Some(fallback) = platform.fallback() {
+        if let Ok(emulated) = fetch_releases(source, fallback, None).await {
+            releases.merge(emulated);
+        }
+    }
+    Ok(releases)
+}
+
+/// Fetch all releases available for the provided platform from a specific source.
+pub async fn all_releases_from(
+    source: &ReleaseSource,
+    platform: Platform,
+) -> Result<Releases, SvmError> {
+    fetch_releases(source, platform, None).await
+}
+
+/// The release lists of all platforms with artifacts, see [`all_platform_artifacts`].
+#[derive(Debug, Default)]
+pub struct PlatformArtifacts {
+    /// The release lists that were fetched, in the order of [`Platform::WITH_ARTIFACTS`].
+    pub releases: Vec<(Platform, Releases)>,
+    /// The platforms whose release list could not be fetched, along with the error.
+    pub failed: Vec<(Platform, SvmError)>,
+}
+
+impl PlatformArtifacts {
+    /// Returns whether the release lists of all platforms were fetched.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Fetches the release lists of all platforms with artifacts concurrently.
+///
+/// A platform whose list cannot be fetched does not fail the others; it is reported in
+/// [`PlatformArtifacts::failed`] instead.
+pub async fn all_platform_artifacts() -> Result<PlatformArtifacts, SvmError> {
+    Ok(all_platform_artifacts_from(&ReleaseSource::from_env()?).await)
+}
+
+/// Fetches the release lists of all platforms with artifacts concurrently from a specific source.
+pub async fn all_platform_artifacts_from(source: &ReleaseSource) -> PlatformArtifacts {
+    let mut tasks = tokio::task::JoinSet::new();
+    for (i, platform) in Platform::WITH_ARTIFACTS.into_iter().enumerate() {
+        let source = source.clone();
+        paths::spawn_scoped(&mut tasks, async move {
+            (i, platform, fetch_releases(&source, platform, None).await)
+        });
+    }
+
+    let mut results = Vec::with_capacity(Platform::WITH_ARTIFACTS.len());
+    while let Some(joined) = tasks.join_next().await {
+        results.push(joined.expect("release list task panicked"));
+    }
+    results.sort_by_key(|(i, ..)| *i);
+
+    let mut artifacts = PlatformArtifacts::default();
+    for (_, platform, res) in results {
+        match res {
+            Ok(releases) => artifacts.releases.push((platform, releases)),
+            Err(err) => artifacts.failed.push((platform, err)),
+        }
+    }
+    artifacts
+}
+
+/// Fetch all releases of the channel of `source` available for the provided platform, giving up
+/// after `timeout` if set.
+///
+/// The pre-release channel combines the pre-release list with the stable one.
+pub(crate) async fn fetch_releases(
+    source: &ReleaseSource,
+    platform: Platform,
+    timeout: Option<Duration>,
+) -> Result<Releases, SvmError> {
+    let mut releases = fetch_list(source, platform, timeout).await?;
+    // GitHub Releases list pre-releases along with the stable releases
+    if source.channel == Channel::Prerelease && source.layout == SourceLayout::Lists {
+        let stable = source.clone().with_channel(Channel::Stable);
+        releases.merge(fetch_list(&stable, platform, timeout).await?);
+    }
+    Ok(releases.in_channel(source.channel))
+}
+
+/// Fetch the release list of the channel of `source`, using the cache if it is recent enough.
+async fn fetch_list(
+    source: &ReleaseSource,
+    platform: Platform,
+    timeout: Option<Duration>,
+) -> Result<Releases, SvmError> {
+    if let Some(releases) = read_cache(source, platform, releases_cache_ttl()) {
+        tracing::debug!(%platform, channel = ?source.channel, "using the cached release list");
+        return Ok(releases);
+    }
+    let fetched = download_releases(source, platform, timeout).await;
+    cached_or_fetched(source, platform, fetched)
+}
+
+/// Downloads the release list of `platform`, bypassing the cache.
+///
+/// The base URL and fallbacks of `source` are tried in order until one serves the list, which
+/// is remembered for the next time.
+#[tracing::instrument(level = "debug", skip_all, fields(%platform, tool = %source.tool))]
+pub(crate) async fn download_releases(
+    source: &ReleaseSource,
+    platform: Platform,
+    timeout: Option<Duration>,
+) -> Result<Releases, SvmError> {
+    let mut client = http::client()?;
+    if let Some(timeout) = timeout {
+        client = client.timeout(timeout);
+    }
+    let client = client.build()?;
+    let mut last_err = None;
+    for base_url in source.candidates() {
+        let res = match source.layout {
+            SourceLayout::Lists => {
+                let url = platform_url(
+                    base_url,
+                    source.tool.platform_dir(platform),
+                    source.channel.list_file(),
+                );
+                match client.get(url.clone()).send().await {
+                    Ok(res) if res.status().is_success() => {
+                        res.json::<Releases>().await.map_err(Into::into)
+                    }
+                    Ok(res) => Err(SvmError::UnsuccessfulResponse(url, res.status())),
+                    Err(err) => Err(err.into()),
+                }
+            }
+            SourceLayout::GithubReleases => {
+                github::download_releases(&client, base_url, platform, source.tool, source.channel)
+                    .await
+            }
+        };
+        match res {
+            Ok(releases) => {
+                tracing::debug!(%base_url, versions = releases.releases.len(), "fetched the release list");
+                source.remember_served_from(base_url);
+                return Ok(releases);
+            }
+            Err(err) => {
+                tracing::warn!(%base_url, %err, "could not fetch the release list");
+                last_err = Some(err)
+            }
+        }
+    }
+    Err(last_err.expect("a source has a base URL"))
+}
+
+/// Environment variable overriding [`releases_cache_ttl`], e.g. `10m`, or `0` to disable caching.
+const ZKSVM_RELEASES_TTL: &str = "ZKSVM_RELEASES_TTL";
+
+/// How long a fetched release list is reused before fetching it again.
+const RELEASES_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Returns how long fetched release lists are cached, one hour unless overridden by
+/// `$ZKSVM_RELEASES_TTL` or the `releases-ttl` of the [`Config`](crate::Config).
+pub fn releases_cache_ttl() -> Duration {
+    std::env::var(ZKSVM_RELEASES_TTL)
+        .ok()
+        .and_then(|ttl| config::parse_duration(&ttl).ok())
+        .or_else(|| config::file_config().releases_ttl)
+        .unwrap_or(RELEASES_CACHE_TTL)
+}
+
+/// Removes all cached release lists, so that the next lookup fetches them again.
+pub fn clear_releases_cache() -> Result<(), SvmError> {
+    let entries = match fs::read_dir(paths::cache_dir()) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with("releases-") {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the cache file of the release list of `platform` from `source`.
+///
+/// Lists from different sources are cached separately, so switching to a mirror takes effect
+/// immediately.
+fn cache_path(source: &ReleaseSource, platform: Platform) -> PathBuf {
+    let digest = sha2::Sha256::digest(source.base_url().as_str());
+    let channel = match source.channel {
+        Channel::Stable => "",
+        Channel::Prerelease => "prerelease-",
+    };
+    // zksolc lists keep the names they had before other tools could be installed
+    let tool = match source.tool {
+        Tool::Zksolc => String::new(),
+        tool => format!("{tool}-"),
+    };
+    paths::cache_dir().join(format!(
+        "releases-{tool}{platform}-{channel}{}.json",
+        hex::encode(&digest[..4])
+    ))
+}
+
+/// Returns the cached release list if it was fetched no longer than `ttl` ago.
+fn read_cache(source: &ReleaseSource, platform: Platform, ttl: Duration) -> Option<Releases> {
+    let releases = read_cache_any_age(source, platform)?;
+    let age = SystemTime::now()
+        .duration_since(releases.fetched_at?)
+        .ok()?;
+    (age < ttl).then_some(releases)
+}
+
+fn read_cache_any_age(source: &ReleaseSource, platform: Platform) -> Option<Releases> {
+    let bytes = fs::read(cache_path(source, platform)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Caches a successfully fetched list, or falls back to a stale cached list if fetching failed.
+///
+/// Falling back keeps zksvm usable offline, outdated lists are reported by
+/// [`Releases::staleness`].
+fn cached_or_fetched(
+    source: &ReleaseSource,
+    platform: Platform,
+    fetched: Result<Releases, SvmError>,
+) -> Result<Releases, SvmError> {
+    let releases = match fetched {
+        Ok(releases) => unified_releases(releases.fetched_now(), platform),
+        Err(err) => {
+            let cached = read_cache_any_age(source, platform).ok_or(err)?;
+            tracing::warn!(%platform, "using the cached release list, as fetching it failed");
+            return Ok(cached);
+        }
+    };
+    // caching is best effort, e.g. the data directory may be read-only
+    let _ = write_cache(source, platform, &releases);
+    Ok(releases)
+}
+
+fn write_cache(
+    source: &ReleaseSource,
+    platform: Platform,
+    releases: &Releases,
+) -> Result<(), SvmError> {
+    let path = cache_path(source, platform);
+    fs::create_dir_all(paths::cache_dir())?;
+    let mut staged = tempfile::NamedTempFile::new_in(paths::cache_dir())?;
+    serde_json::to_writer(&mut staged, releases).map_err(io::Error::from)?;
+    staged.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Fetch all releases available for the artifact platform of this machine, along with warnings
+/// about emulation, fallback sources and outdated release information.
+pub async fn current_releases(
+    thresholds: &StalenessThresholds,
+) -> Result<WithWarnings<Releases>, SvmError> {
+    let platform = platform::artifact_platform()?;
+    let source = ReleaseSource::from_env()?;
+    let mut releases = WithWarnings::new(fetch_releases(&source, platform, None).await?);
+    if let Some(warning) = platform::emulation_warning(platform) {
+        releases.push(warning);
+    }
+    if let Some(warning) = source.fallback_warning() {
+        releases.push(warning);
+    }
+    for staleness in releases.value.staleness(thresholds) {
+        releases.push(staleness);
+    }
+    Ok(releases)
+}
+
+/// unifies the releases with old releases if on linux
+// TODO: remove this function once all platforms have been updated
+fn unified_releases(releases: Releases, _platform: Platform) -> Releases {
+    releases
+}
+
+/// Construct the URL to the zksolc binary of `version` listed in `releases` of `platform`.
+///
+/// Fails with [`SvmError::UnknownVersion`] unless `releases` list the version, and with
+/// [`SvmError::UnsupportedVersionRange`] if it is older than their
+/// [`min_version`](Releases::min_version).
+pub(crate) fn artifact_url(
+    source: &ReleaseSource,
+    platform: Platform,
+    version: &Version,
+    releases: &Releases,
+) -> Result<Url, SvmError> {
+    if releases.get_artifact(version).is_some() && !releases.is_supported(version) {
+        return Err(SvmError::UnsupportedVersionRange {
+            version: version.to_string(),
+            platform: platform.to_string(),
+            supported: releases.supported_versions().to_string(),
+        });
+    }
+    source.version_url(platform, version, releases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[serial_test::serial]
+    fn releases_cache() {
+        let platform = Platform::MacOsAmd64;
+        let source = ReleaseSource::default();
+        let releases = Releases {
+            builds: vec![],
+            releases: BTreeMap::from([(Version::new(1, 3, 17), "zksolc-v1.3.17".to_string())]),
+            fetched_at: None,
+            min_version: None,
+        }
+        .fetched_now();
+        assert!(read_cache(&source, platform, RELEASES_CACHE_TTL).is_none());
+
+        write_cache(&source, platform, &releases).unwrap();
+        assert_eq!(
+            read_cache(&source, platform, RELEASES_CACHE_TTL),
+            Some(releases.clone())
+        );
+        assert_eq!(read_cache(&source, platform, Duration::ZERO), None);
+
+        clear_releases_cache().unwrap();
+        assert!(read_cache_any_age(&source, platform).is_none());
+    }
+
+    #[test]
+    fn channels() {
+        let beta = Version::parse("1.4.2-beta.1").unwrap();
+        assert_eq!(Channel::of(&beta), Channel::Prerelease);
+        assert_eq!(Channel::of(&Version::new(1, 4, 1)), Channel::Stable);
+        assert_eq!("nightly".parse(), Ok(Channel::Prerelease));
+        assert_eq!(Channel::Stable.to_string().parse(), Ok(Channel::Stable));
+
+        let source = ReleaseSource::default();
+        let prerelease = source.clone().with_channel(Channel::Prerelease);
+        assert!(prerelease
+            .releases_url(Platform::LinuxAmd64)
+            .as_str()
+            .ends_with("/linux-amd64/list-prerelease.json"));
+        assert_ne!(
+            cache_path(&source, Platform::LinuxAmd64),
+            cache_path(&prerelease, Platform::LinuxAmd64)
+        );
+
+        let list = |versions: &[&Version]| Releases {
+            releases: versions
+                .iter()
+                .map(|v| ((*v).clone(), format!("zksolc-v{v}")))
+                .collect(),
+            ..Default::default()
+        };
+        let stable = Version::new(1, 4, 1);
+        let mut releases = list(&[&beta]);
+        releases.merge(list(&[&stable, &beta]));
+        assert_eq!(
+            releases
+                .clone()
+                .in_channel(Channel::Prerelease)
+                .into_versions(),
+            [stable.clone(), beta]
+        );
+        assert_eq!(
+            releases.in_channel(Channel::Stable).into_versions(),
+            [stable]
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn all_platform_artifacts_returns_partial_results() {
+        // nothing listens on the discard port, so only cached lists are available
+        let source = ReleaseSource::new(Url::parse("http://127.0.0.1:9/").unwrap()).unwrap();
+        let releases = Releases::default().fetched_now();
+        write_cache(&source, Platform::LinuxAmd64, &releases).unwrap();
+        write_cache(&source, Platform::WindowsAmd64, &releases).unwrap();
+
+        let artifacts = all_platform_artifacts_from(&source).await;
+        clear_releases_cache().unwrap();
+        assert!(!artifacts.is_complete());
+        assert_eq!(
+            artifacts
+                .releases
+                .iter()
+                .map(|(platform, _)| *platform)
+                .collect::<Vec<_>>(),
+            [Platform::LinuxAmd64, Platform::WindowsAmd64]
+        );
+        assert_eq!(
+            artifacts
+                .failed
+                .iter()
+                .map(|(platform, _)| *platform)
+                .collect::<Vec<_>>(),
+            [
+                Platform::LinuxAarch64,
+                Platform::MacOsAmd64,
+                Platform::MacOsAarch64
+            ]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn releases_cache_ttl_from_env() {
+        assert_eq!(releases_cache_ttl(), RELEASES_CACHE_TTL);
+        std::env::set_var(ZKSVM_RELEASES_TTL, "10m");
+        assert_eq!(releases_cache_ttl(), Duration::from_secs(600));
+        std::env::set_var(ZKSVM_RELEASES_TTL, "0");
+        assert_eq!(releases_cache_ttl(), Duration::ZERO);
+        std::env::remove_var(ZKSVM_RELEASES_TTL);
+    }
+
+    #[test]
+    fn test_artifact_url() {
+        let version = Version::new(1, 3, 17);
+        let artifact = "zksolc-linux-arm64-musl-v1.3.17";
+        let releases = Releases {
+            releases: BTreeMap::from([(version.clone(), artifact.to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            artifact_url(
+                &ReleaseSource::default(),
+                Platform::LinuxAarch64,
+                &version,
+                &releases
+            )
+            .unwrap(),
+            Url::parse(&format!(
+                "https://github.com/dutterbutter/zksolc-bin/raw/db/generate-list/linux-arm64/{artifact}"
+            ))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn artifact_url_outside_supported_range() {
+        let source = ReleaseSource::default();
+        let listed = |versions: &[Version]| Releases {
+            releases: versions
+                .iter()
+                .map(|version| {
+                    (
+                        version.clone(),
+                        format!("zksolc-linux-amd64-musl-v{version}"),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let (old, listed_min, new) = (
+            Version::new(1, 3, 5),
+            Version::new(1, 3, 20),
+            Version::new(9, 0, 0),
+        );
+        let earlier = Version::new(1, 3, 17);
+        let mut releases = listed(&[
+            old.clone(),
+            earlier.clone(),
+            listed_min.clone(),
+            new.clone(),
+        ]);
+        // without a minimum, every listed version can be installed
+        assert_eq!(releases.supported_versions(), VersionReq::STAR);
+        for version in [&old, &earlier, &new] {
+            assert!(artifact_url(&source, Platform::LinuxAmd64, version, &releases).is_ok());
+        }
+        assert!(matches!(
+            artifact_url(
+                &source,
+                Platform::LinuxAmd64,
+                &Version::new(9, 0, 1),
+                &releases
+            ),
+            Err(SvmError::UnknownVersion)
+        ));
+
+        // release lists may declare a minimum for their platform
+        releases.min_version = Some(listed_min.clone());
+        assert!(artifact_url(&source, Platform::LinuxAmd64, &listed_min, &releases).is_ok());
+        assert!(releases.is_supported(&Version::parse("9.0.1-beta.1").unwrap()));
+        assert!(!releases.is_supported(&old));
+        match artifact_url(&source, Platform::LinuxAmd64, &earlier, &releases).unwrap_err() {
+            SvmError::UnsupportedVersionRange { supported, .. } => {
+                assert_eq!(supported, format!(">={listed_min}"))
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn artifact_url_rejects_path_names() {
+        let source = ReleaseSource::default();
+        for artifact in [
+            "",
+            ".",
+            "..",
+            "../list.json",
+            "a/b",
+            "a\\b",
+            "%2e%2e",
+            "\n..",
+        ] {
+            assert!(
+                matches!(
+                    source.artifact_url(Platform::LinuxAmd64, artifact),
+                    Err(SvmError::InvalidArtifactName(_))
+                ),
+                "{artifact:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn checksums_of_listed_versions() {
+        let build = |version: Version, byte: u8| BuildInfo::new(version, vec![byte; 32]);
+        let releases = Releases {
+            builds: vec![
+                build(Version::new(1, 3, 16), 1),
+                build(Version::new(1, 3, 17), 2),
+                build(Version::new(1, 4, 0), 3),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            releases.checksums(&[
+                Version::new(1, 4, 0),
+                Version::new(1, 3, 16),
+                Version::new(9, 9, 9)
+            ]),
+            BTreeMap::from([
+                (Version::new(1, 3, 16), [1; 32]),
+                (Version::new(1, 4, 0), [3; 32])
+            ])
+        );
+        assert!(releases.checksums(&[]).is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_checksums() {
+        let json = |sha256: &str| {
+            format!(r#"{{"builds":[{{"version":"1.3.17","sha256":"{sha256}"}}],"releases":{{}}}}"#)
+        };
+        assert!(serde_json::from_str::<Releases>(&json(&"ab".repeat(32))).is_ok());
+        assert!(serde_json::from_str::<Releases>(&json(&format!("0x{}", "ab".repeat(32)))).is_ok());
+        assert!(serde_json::from_str::<Releases>(&json(&"ab".repeat(31))).is_err());
+        assert!(serde_json::from_str::<Releases>(&json("0x")).is_err());
+        assert!(serde_json::from_str::<Releases>(&json("0x0xab")).is_err());
+    }
+
+    #[test]
+    fn selects_builds_for_libc() {
+        let (glibc, musl) = ("ab".repeat(32), "cd".repeat(32));
+        let json = format!(
+            r#"{{
+                "builds": [
+                    {{"version": "1.5.0", "sha256": "{glibc}", "libc": "gnu"}},
+                    {{"version": "1.5.0", "sha256": "{musl}", "libc": "musl", "path": "zksolc-linux-amd64-musl-v1.5.0"}},
+                    {{"version": "1.4.1", "sha256": "{glibc}"}}
+                ],
+                "releases": {{
+                    "1.5.0": "zksolc-linux-amd64-gnu-v1.5.0",
+                    "1.4.1": "zksolc-linux-amd64-musl-v1.4.1"
+                }}
+            }}"#
+        );
+        let releases: Releases = serde_json::from_str(&json).unwrap();
+        let (v150, v141) = (Version::new(1, 5, 0), Version::new(1, 4, 1));
+
+        let selected = releases.clone().for_libc(Libc::Musl);
+        assert_eq!(
+            selected.get_artifact(&v150).unwrap(),
+            "zksolc-linux-amd64-musl-v1.5.0"
+        );
+        assert_eq!(
+            selected.get_checksum(&v150),
+            Some(hex::decode(&musl).unwrap())
+        );
+        assert_eq!(
+            selected.get_artifact(&v141).unwrap(),
+            "zksolc-linux-amd64-musl-v1.4.1"
+        );
+
+        let selected = releases.clone().for_libc(Libc::Glibc);
+        assert_eq!(
+            selected.get_artifact(&v150).unwrap(),
+            "zksolc-linux-amd64-gnu-v1.5.0"
+        );
+        assert_eq!(
+            selected.get_checksum(&v150),
+            Some(hex::decode(&glibc).unwrap())
+        );
+        assert_eq!(selected.builds.len(), 2);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn artifact_urls_stay_in_platform_dir(artifact in "\\PC{0,24}|[./%2eE\t\n]{0,6}") {
+            let source = ReleaseSource::default();
+            let list = source.releases_url(Platform::LinuxAmd64);
+            let dir = list.as_str().strip_suffix("list.json").unwrap();
+            if let Ok(url) = source.artifact_url(Platform::LinuxAmd64, &artifact) {
+                proptest::prop_assert!(url.as_str().starts_with(dir), "{} escapes {}", url, dir);
+            }
+        }
+
+        #[test]
+        fn releases_roundtrip_arbitrary(
+            builds in proptest::collection::vec(
+                (
+                    (0u64..5, 0u64..50, 0u64..50),
+                    proptest::array::uniform32(0u8..),
+                    proptest::option::of(0u64..4_000_000_000),
+                ),
+                0..8,
+            ),
+            artifact in "[a-z0-9.+-]{1,40}",
+        ) {
+            let releases = Releases {
+                builds: builds
+                    .into_iter()
+                    .map(|((major, minor, patch), sha256, published_at)| BuildInfo {
+                        published_at: published_at
+                            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+                        ..BuildInfo::new(Version::new(major, minor, patch), sha256.to_vec())
+                    })
+                    .collect(),
+                releases: BTreeMap::from([(Version::new(1, 3, 17), artifact)]),
+                fetched_at: None,
+                min_version: None,
+            };
+            let json = serde_json::to_string(&releases).unwrap();
+            proptest::prop_assert_eq!(serde_json::from_str::<Releases>(&json).unwrap(), releases);
+        }
+
+        #[test]
+        fn releases_parse_arbitrary_json(json in "\\PC*") {
+            let _ = serde_json::from_str::<Releases>(&json);
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn release_source_from_env() {
+        std::env::set_var(ZKSVM_RELEASES_URL, "https://mirror.example.com/zksolc");
+        let source = ReleaseSource::from_env().unwrap();
+        std::env::remove_var(ZKSVM_RELEASES_URL);
+        assert_eq!(
+            source.releases_url(Platform::MacOsAarch64).as_str(),
+            "https://mirror.example.com/zksolc/macosx-arm64/list.json"
+        );
+        assert_eq!(
+            source
+                .artifact_url(Platform::LinuxAmd64, "zksolc-linux-amd64-musl-v1.3.17")
+                .unwrap()
+                .as_str(),
+            "https://mirror.example.com/zksolc/linux-amd64/zksolc-linux-amd64-musl-v1.3.17"
+        );
+        assert_ne!(
+            cache_path(&source, Platform::LinuxAmd64),
+            cache_path(&ReleaseSource::default(), Platform::LinuxAmd64)
+        );
+        assert_eq!(ReleaseSource::from_env().unwrap(), ReleaseSource::default());
+
+        std::env::set_var(
+            ZKSVM_RELEASES_URL,
+            "https://mirror.example.com/zksolc, https://backup.example.com/zksolc",
+        );
+        let source = ReleaseSource::from_env().unwrap();
+        std::env::remove_var(ZKSVM_RELEASES_URL);
+        assert_eq!(
+            source.base_url().as_str(),
+            "https://mirror.example.com/zksolc"
+        );
+        assert_eq!(
+            source.fallbacks(),
+            [Url::parse("https://backup.example.com/zksolc").unwrap()]
+        );
+
+        // URLs that cannot have paths below them are rejected instead of panicking later
+        for urls in ["mailto:a@b", "https://mirror.example.com/zksolc,mailto:a@b"] {
+            std::env::set_var(ZKSVM_RELEASES_URL, urls);
+            assert!(matches!(
+                ReleaseSource::from_env(),
+                Err(SvmError::InvalidReleaseSource(_))
+            ));
+        }
+        std::env::remove_var(ZKSVM_RELEASES_URL);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn github_release_sources() {
+        std::env::set_var(ZKSVM_RELEASES_SOURCE, "matter-labs");
+        let source = ReleaseSource::from_env().unwrap();
+        assert_eq!(source, ReleaseSource::matter_labs());
+        assert_eq!(source.layout(), SourceLayout::GithubReleases);
+        assert_eq!(
+            source.releases_url(Platform::LinuxAmd64).as_str(),
+            "https://api.github.com/repos/matter-labs/era-compiler-solidity/releases"
+        );
+        std::env::set_var(ZKSVM_RELEASES_SOURCE, "github:matter-labs/zksolc-bin");
+        assert_eq!(
+            ReleaseSource::from_env().unwrap(),
+            ReleaseSource::github("matter-labs/zksolc-bin").unwrap()
+        );
+        std::env::set_var(ZKSVM_RELEASES_SOURCE, "gitlab:matter-labs/zksolc-bin");
+        assert!(matches!(
+            ReleaseSource::from_env(),
+            Err(SvmError::InvalidReleaseSource(_))
+        ));
+        std::env::set_var(ZKSVM_RELEASES_SOURCE, "default");
+        assert_eq!(ReleaseSource::from_env().unwrap(), ReleaseSource::default());
+
+        // the config file names a source like the environment, which takes precedence
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "releases-source = \"matter-labs\"").unwrap();
+        std::env::set_var(crate::ZKSVM_CONFIG, &path);
+        assert_eq!(ReleaseSource::from_env().unwrap(), ReleaseSource::default());
+        std::env::remove_var(ZKSVM_RELEASES_SOURCE);
+        assert_eq!(
+            ReleaseSource::from_env().unwrap(),
+            ReleaseSource::matter_labs()
+        );
+        std::env::remove_var(crate::ZKSVM_CONFIG);
+
+        // assets are downloaded from where the release lists them
+        let version = Version::new(1, 5, 7);
+        let artifact = "zksolc-linux-amd64-musl-v1.5.7";
+        let mut releases = Releases {
+            builds: vec![BuildInfo {
+                url: Some(format!(
+                    "https://github.com/matter-labs/era-compiler-solidity/releases/download/1.5.7/{artifact}"
+                )),
+                ..BuildInfo::new(version.clone(), vec![0; 32])
+            }],
+            releases: BTreeMap::from([(version.clone(), artifact.to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            source
+                .version_url(Platform::LinuxAmd64, &version, &releases)
+                .unwrap()
+                .as_str(),
+            releases.builds[0].url.as_deref().unwrap()
+        );
+        releases.builds[0].url = Some("http://example.com/zksolc".to_string());
+        assert!(matches!(
+            source.version_url(Platform::LinuxAmd64, &version, &releases),
+            Err(SvmError::InvalidArtifactName(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn falls_back_to_other_sources() {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let response = if request.contains("/main/") {
+                    let body = r#"{"builds":[],"releases":{"1.4.1":"zksolc-v1.4.1"}}"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        let base_url = Url::parse(&format!("http://{addr}/db/generate-list")).unwrap();
+        let fallback = Url::parse(&format!("http://{addr}/main")).unwrap();
+        let source = ReleaseSource::new(base_url.clone())
+            .unwrap()
+            .with_fallbacks([fallback.clone()])
+            .unwrap();
+        assert_eq!(source.served_from(), &base_url);
+        assert_eq!(source.fallback_warning(), None);
+
+        let releases = download_releases(&source, Platform::LinuxAmd64, None)
+            .await
+            .unwrap();
+        assert_eq!(releases.into_versions(), [Version::new(1, 4, 1)]);
+        assert_eq!(source.served_from(), &fallback);
+        assert!(source
+            .artifact_url(Platform::LinuxAmd64, "zksolc-v1.4.1")
+            .unwrap()
+            .as_str()
+            .starts_with(fallback.as_str()));
+        assert_eq!(
+            source.fallback_warning(),
+            Some(Warning::FallbackSource {
+                base_url: base_url.clone(),
+                served_from: fallback.clone(),
+            })
+        );
+
+        clear_releases_cache().unwrap();
+        assert_eq!(source.served_from(), &base_url);
+        assert!(download_releases(
+            &ReleaseSource::new(base_url).unwrap(),
+            Platform::LinuxAmd64,
+            None
+        )
+        .await
+        .is_err());
+    }
+
+    #[test]
+    fn releases_timestamps_roundtrip() {
+        let (a, b) = ("00".repeat(32), "01".repeat(32));
+        let json = format!(
+            r#"{{
+            "builds": [
+                {{ "version": "1.3.17", "sha256": "0x{a}", "published_at": "2023-10-04T12:00:00Z" }},
+                {{ "version": "1.3.16", "sha256": "0x{b}" }}
+            ],
+            "releases": {{ "1.3.17": "zksolc-v1.3.17", "1.3.16": "zksolc-v1.3.16" }}
+        }}"#
+        );
+        let releases: Releases = serde_json::from_str(&json).unwrap();
+        assert_eq!(releases.fetched_at, None);
+        let latest = releases.latest_build().unwrap();
+        assert_eq!(latest.version, Version::new(1, 3, 17));
+        assert_eq!(
+            latest.published_at,
+            Some(humantime::parse_rfc3339("2023-10-04T12:00:00Z").unwrap())
+        );
+
+        let releases = releases.fetched_now();
+        let s = serde_json::to_string(&releases).unwrap();
+        let de_releases: Releases = serde_json::from_str(&s).unwrap();
+        assert_eq!(releases.builds, de_releases.builds);
+        assert!(de_releases.fetched_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_all_releases_macos_amd64() {
+        assert!(all_releases(Platform::MacOsAmd64).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_all_releases_macos_aarch64() {
+        assert!(all_releases(Platform::MacOsAarch64).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_all_releases_linux_amd64() {
+        assert!(all_releases(Platform::LinuxAmd64).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_all_releases_linux_aarch64() {
+        assert!(all_releases(Platform::LinuxAarch64).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn releases_roundtrip() {
+        let releases = all_releases(Platform::LinuxAmd64).await.unwrap();
+        let s = serde_json::to_string(&releases).unwrap();
+        let de_releases: Releases = serde_json::from_str(&s).unwrap();
+        assert_eq!(releases, de_releases);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{platform, releases, SvmError};
+use semver::{Version, VersionReq};
+
+/// Returns the newest of `versions` matching `req`, if any.
+pub fn best_matching_version<'a>(
+    req: &VersionReq,
+    versions: impl IntoIterator<Item = &'a Version>,
+) -> Option<Version> {
+    versions
+        .into_iter()
+        .filter(|v| req.matches(v))
+        .max()
+        .cloned()
+}
+
+/// Narrows down a list of versions, e.g. to those matching `^1.4`, keeping only the newest ones.
+///
+/// ```
+/// use semver::Version;
+///
+/// let versions = [Version::new(1, 3, 22), Version::new(1, 4, 0), Version::new(1, 4, 1)];
+/// let filter = zksvm_core::VersionFilter::new()
+///     .req("^1.4".parse().unwrap())
+///     .limit(1);
+/// assert_eq!(filter.apply(&versions), [Version::new(1, 4, 1)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct VersionFilter {
+    /// Only keeps the versions matching this requirement.
+    pub req: Option<VersionReq>,
+    /// Only keeps this many of the newest versions.
+    pub limit: Option<usize>,
+}
+
+impl VersionFilter {
+    /// Returns a filter keeping all versions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keeps the versions matching `req`.
+    pub fn req(mut self, req: VersionReq) -> Self {
+        self.req = Some(req);
+        self
+    }
+
+    /// Only keeps the newest `limit` versions.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns whether `version` matches the requirement of the filter, regardless of the limit.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.req.as_ref().map_or(true, |req| req.matches(version))
+    }
+
+    /// Returns the kept `versions`, sorted from oldest to newest and without duplicates.
+    pub fn apply<'a>(&self, versions: impl IntoIterator<Item = &'a Version>) -> Vec<Version> {
+        let mut kept: Vec<_> = versions
+            .into_iter()
+            .filter(|v| self.matches(v))
+            .cloned()
+            .collect();
+        kept.sort();
+        kept.dedup();
+        if let Some(limit) = self.limit {
+            kept.drain(..kept.len().saturating_sub(limit));
+        }
+        kept
+    }
+}
+
+/// Blocking version of [`resolve_version`]
+#[cfg(feature = "blocking")]
+pub fn blocking_resolve_version(req: &VersionReq) -> Result<Version, SvmError> {
+    let releases = releases::blocking_all_releases(platform::artifact_platform()?)?;
+    best_matching_version(req, releases.releases.keys())
+        .ok_or_else(|| SvmError::NoMatchingVersion(req.to_string()))
+}
+
+/// Resolves a version requirement such as `^1.3` or `>=1.3.17, <1.4` to the newest matching
+/// release available for this machine.
+pub async fn resolve_version(req: &VersionReq) -> Result<Version, SvmError> {
+    let releases = releases::all_releases(platform::artifact_platform()?).await?;
+    best_matching_version(req, releases.releases.keys())
+        .ok_or_else(|| SvmError::NoMatchingVersion(req.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_newest_match() {
+        let versions = [
+            Version::new(1, 3, 9),
+            Version::new(1, 3, 17),
+            Version::new(1, 3, 22),
+            Version::new(1, 4, 1),
+            Version::parse("1.5.0-rc.1").unwrap(),
+        ];
+        let best = |req: &str| best_matching_version(&req.parse().unwrap(), &versions);
+        assert_eq!(best("^1.3"), Some(Version::new(1, 4, 1)));
+        assert_eq!(best("~1.3"), Some(Version::new(1, 3, 22)));
+        assert_eq!(best(">=1.3.17, <1.3.22"), Some(Version::new(1, 3, 17)));
+        assert_eq!(best("*"), Some(Version::new(1, 4, 1)));
+        assert_eq!(best("^2"), None);
+    }
+
+    #[test]
+    fn filters_versions() {
+        let versions = [
+            Version::new(1, 4, 1),
+            Version::new(1, 3, 22),
+            Version::new(1, 4, 0),
+            Version::new(1, 4, 1),
+            Version::new(1, 5, 0),
+        ];
+        assert_eq!(VersionFilter::new().apply(&versions).len(), 4);
+        assert_eq!(
+            VersionFilter::new()
+                .req("~1.4".parse().unwrap())
+                .apply(&versions),
+            [Version::new(1, 4, 0), Version::new(1, 4, 1)]
+        );
+        assert_eq!(
+            VersionFilter::new().limit(2).apply(&versions),
+            [Version::new(1, 4, 1), Version::new(1, 5, 0)]
+        );
+        assert!(VersionFilter::new().limit(0).apply(&versions).is_empty());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn best_match_is_newest_match(
+            versions in proptest::collection::vec((0u64..3, 0u64..6, 0u64..30), 0..20),
+            req in "[~^<>=]{0,2}[0-2](\\.[0-5](\\.[0-9]{1,2})?)?",
+        ) {
+            let versions: Vec<_> = versions
+                .into_iter()
+                .map(|(major, minor, patch)| Version::new(major, minor, patch))
+                .collect();
+            let Ok(req) = VersionReq::parse(&req) else {
+                return Ok(());
+            };
+            match best_matching_version(&req, &versions) {
+                Some(best) => {
+                    proptest::prop_assert!(req.matches(&best));
+                    proptest::prop_assert!(versions.iter().all(|v| !req.matches(v) || *v <= best));
+                }
+                None => proptest::prop_assert!(versions.iter().all(|v| !req.matches(v))),
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::SvmError;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often and how patiently downloads are retried after transient network errors.
+///
+/// The delay before retry `n` is `initial_backoff * 2^(n - 1)`, capped at `max_backoff`. With
+/// `jitter` the delay is randomly shortened by up to half, so that clients failing together do
+/// not retry together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The total number of attempts, including the first one.
+    pub attempts: u32,
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+    /// The upper bound of the delay between attempts.
+    pub max_backoff: Duration,
+    /// Whether to randomize delays.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+/// What to do after a failed attempt.
+#[derive(Debug)]
+pub(crate) enum Retry {
+    /// Try again after the delay.
+    After(Duration),
+    /// Fail with the error.
+    GiveUp(SvmError),
+}
+
+impl RetryPolicy {
+    /// A policy making a single attempt.
+    pub fn none() -> Self {
+        Self {
+            attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the delay before retry `retry`, starting at 1, without jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Decides whether to retry after `attempt`, starting at 1, failed with `err`.
+    pub(crate) fn on_error(&self, attempt: u32, err: SvmError) -> Retry {
+        if !is_transient(&err) || self.attempts <= 1 {
+            return Retry::GiveUp(err);
+        }
+        if attempt >= self.attempts {
+            return Retry::GiveUp(SvmError::MaxRetriesExceeded {
+                attempts: attempt,
+                last: Box::new(err),
+            });
+        }
+        let backoff = self.backoff(attempt);
+        if !self.jitter {
+            return Retry::After(backoff);
+        }
+        // a cheap source of randomness is enough to spread out retries
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        let fraction = f64::from(nanos % 1000) / 1000.0;
+        Retry::After(backoff.mul_f64(0.5 + fraction / 2.0))
+    }
+}
+
+/// Runs `operation` until it succeeds, fails with an error that is not [transient](is_transient)
+/// or `policy` runs out of attempts, sleeping between attempts without blocking the runtime.
+pub async fn retry<T, F, Fut>(policy: &RetryPolicy, mut operation: F) -> Result<T, SvmError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, SvmError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) => match policy.on_error(attempt, err) {
+                Retry::After(delay) => tokio::time::sleep(delay).await,
+                Retry::GiveUp(err) => return Err(err),
+            },
+        }
+        attempt += 1;
+    }
+}
+
+/// Blocking version of [`retry`], sleeping the current thread between attempts.
+pub fn blocking_retry<T>(
+    policy: &RetryPolicy,
+    mut operation: impl FnMut() -> Result<T, SvmError>,
+) -> Result<T, SvmError> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Ok(value) => return Ok(value),
+            Err(err) => match policy.on_error(attempt, err) {
+                Retry::After(delay) => std::thread::sleep(delay),
+                Retry::GiveUp(err) => return Err(err),
+            },
+        }
+        attempt += 1;
+    }
+}
+
+/// Returns whether `err` may go away by trying again: timeouts, connection and DNS failures,
+/// server errors, rate limiting and truncated downloads.
+pub fn is_transient(err: &SvmError) -> bool {
+    match err {
+        SvmError::ReqwestError(err) => {
+            err.is_timeout() || err.is_connect() || err.is_request() || err.is_body()
+        }
+        SvmError::UnsuccessfulResponse(_, status) => {
+            status.is_server_error() || status.as_u16() == 429
+        }
+        SvmError::DnsResolution { .. } | SvmError::CorruptDownload { .. } => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+    use url::Url;
+
+    fn server_error() -> SvmError {
+        SvmError::UnsuccessfulResponse(
+            Url::parse("https://example.com").unwrap(),
+            StatusCode::BAD_GATEWAY,
+        )
+    }
+
+    #[test]
+    fn backoff_grows_exponentially() {
+        let policy = RetryPolicy {
+            attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            jitter: false,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40), Duration::from_secs(1));
+        assert!(matches!(
+            policy.on_error(3, server_error()),
+            Retry::After(delay) if delay == Duration::from_millis(400)
+        ));
+
+        let jittered = RetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        match jittered.on_error(3, server_error()) {
+            Retry::After(delay) => {
+                assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400))
+            }
+            retry => panic!("unexpected {retry:?}"),
+        }
+    }
+
+    #[test]
+    fn gives_up() {
+        let policy = RetryPolicy::default();
+        assert!(matches!(
+            policy.on_error(1, SvmError::UnknownVersion),
+            Retry::GiveUp(SvmError::UnknownVersion)
+        ));
+        assert!(matches!(
+            RetryPolicy::none().on_error(1, server_error()),
+            Retry::GiveUp(SvmError::UnsuccessfulResponse(..))
+        ));
+        match policy.on_error(3, server_error()) {
+            Retry::GiveUp(SvmError::MaxRetriesExceeded { attempts, last }) => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*last, SvmError::UnsuccessfulResponse(..)));
+            }
+            retry => panic!("unexpected {retry:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let policy = RetryPolicy {
+            attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            jitter: false,
+        };
+        let mut calls = 0;
+        let value = retry(&policy, || {
+            calls += 1;
+            let res = if calls < 3 {
+                Err(server_error())
+            } else {
+                Ok(calls)
+            };
+            async move { res }
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, 3);
+
+        let mut calls = 0;
+        let res: Result<(), _> = blocking_retry(&policy, || {
+            calls += 1;
+            Err(SvmError::UnknownVersion)
+        });
+        assert!(matches!(res, Err(SvmError::UnknownVersion)));
+        assert_eq!(calls, 1);
+
+        let res: Result<(), _> = blocking_retry(&policy, || Err(server_error()));
+        assert!(matches!(
+            res,
+            Err(SvmError::MaxRetriesExceeded { attempts: 3, .. })
+        ));
+    }
+}
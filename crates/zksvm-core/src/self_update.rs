@@ -0,0 +1,244 @@
+//! Updating zksvm itself from the GitHub Releases of its repository.
+
+use crate::{
+    fsutil,
+    github::{self, GithubAsset, GithubRelease},
+    http, install, platform, InstallOptions, SvmError,
+};
+use semver::Version;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// The repository zksvm is released from.
+pub const ZKSVM_REPO: &str = "dutterbutter/zsvm-rs";
+
+/// A release of zksvm newer than the running one, see [`check_self_update`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfUpdate {
+    pub version: Version,
+    /// The name of the binary for this machine, e.g. `zksvm-linux-amd64`.
+    pub asset: String,
+    /// Where the binary is downloaded from.
+    pub url: Url,
+    /// The published checksum of the binary, from GitHub or a `.sha256` file next to it.
+    pub sha256: Vec<u8>,
+}
+
+/// Checks the latest release of zksvm for a version newer than `current` with a binary for this
+/// machine.
+///
+/// Fails with [`SvmError::NoSelfUpdateAsset`] if the release has no binary for this machine, and
+/// with [`SvmError::MissingSelfUpdateChecksum`] if it cannot be verified.
+pub async fn check_self_update(current: &Version) -> Result<Option<SelfUpdate>, SvmError> {
+    let client = http::client()?.timeout(http::request_timeout()).build()?;
+    let url = github::repo_url(ZKSVM_REPO)?.join("releases/latest")?;
+    let res = client
+        .get(url.clone())
+        .headers(github::request_headers(None))
+        .send()
+        .await?;
+    let body = match github::check_response(&url, res.status(), res.headers(), None)? {
+        Some(body) => body,
+        None => res.text().await?,
+    };
+    let release: GithubRelease = serde_json::from_str(&body).map_err(std::io::Error::from)?;
+    let Ok(version) = Version::parse(release.tag_name.trim_start_matches('v')) else {
+        return Ok(None);
+    };
+    if version <= *current {
+        return Ok(None);
+    }
+
+    let platform = platform::platform();
+    let Some(asset) = release
+        .assets
+        .iter()
+        .find(|asset| is_self_asset(&asset.name, &platform.to_string()))
+    else {
+        return Err(SvmError::NoSelfUpdateAsset {
+            version: version.to_string(),
+            platform: platform.to_string(),
+        });
+    };
+    let sha256 = match asset.digest.as_deref().and_then(github::parse_digest) {
+        Some(sha256) => sha256,
+        None => sibling_checksum(&client, &release.assets, &asset.name)
+            .await
+            .ok_or_else(|| SvmError::MissingSelfUpdateChecksum(asset.name.clone()))?,
+    };
+    Ok(Some(SelfUpdate {
+        version,
+        asset: asset.name.clone(),
+        url: Url::parse(&asset.browser_download_url)?,
+        sha256,
+    }))
+}
+
+/// Downloads `update`, verifies its checksum and replaces the binary at `exe` with it, which may
+/// be the running executable.
+///
+/// The download is retried and bounded like installs with `opts`, of which only
+/// [`retry`](InstallOptions::retry) and [`deadline`](InstallOptions::deadline) apply. On Windows,
+/// where running executables cannot be replaced, `exe` is moved aside first and removed by
+/// [`remove_replaced_exe`] once it no longer runs.
+pub async fn self_update(
+    update: &SelfUpdate,
+    exe: &Path,
+    opts: &InstallOptions,
+) -> Result<(), SvmError> {
+    // staged next to `exe`, so that it can be moved into place
+    let dir = exe.parent().unwrap_or(Path::new("."));
+    let name = format!("zksvm {}", update.version);
+    let staged = install::download_checked(&update.url, dir, &name, &update.sha256, opts).await?;
+    staged.as_file().sync_all()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        staged
+            .as_file()
+            .set_permissions(fs::Permissions::from_mode(0o755))?;
+    }
+    fsutil::remove_quarantine(staged.path())?;
+    replace_exe(staged, exe)
+}
+
+/// Returns where [`self_update`] moves the executable at `exe` aside on Windows.
+fn replaced_exe_path(exe: &Path) -> PathBuf {
+    let mut path = exe.as_os_str().to_owned();
+    path.push(".old");
+    path.into()
+}
+
+/// Removes the executable moved aside by a [`self_update`] of `exe`, if any. Errors are
+/// ignored, as it may still be running.
+pub fn remove_replaced_exe(exe: &Path) {
+    let _ = fs::remove_file(replaced_exe_path(exe));
+}
+
+/// Moves `staged` to `exe`.
+fn replace_exe(staged: tempfile::NamedTempFile, exe: &Path) -> Result<(), SvmError> {
+    if cfg!(windows) {
+        // running executables can be renamed but not replaced
+        let old = replaced_exe_path(exe);
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old).map_err(|err| fsutil::file_error(err, exe))?;
+        if let Err(err) = fsutil::persist(staged, exe) {
+            let _ = fs::rename(&old, exe);
+            return Err(fsutil::file_error(err.error, exe));
+        }
+        return Ok(());
+    }
+    fsutil::persist(staged, exe).map_err(|err| fsutil::file_error(err.error, exe))
+}
+
+/// Returns whether the release asset `name` is the zksvm binary for `platform`, e.g.
+/// `zksvm-linux-amd64` or `zksvm-windows-amd64.exe`.
+fn is_self_asset(name: &str, platform: &str) -> bool {
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    name == format!("zksvm-{platform}")
+}
+
+/// Downloads the checksum in the `.sha256` file accompanying the asset `name`, if there is one.
+async fn sibling_checksum(
+    client: &reqwest::Client,
+    assets: &[GithubAsset],
+    name: &str,
+) -> Option<Vec<u8>> {
+    let sibling = format!("{name}.sha256");
+    let asset = assets.iter().find(|asset| asset.name == sibling)?;
+    let res = client.get(&asset.browser_download_url).send().await.ok()?;
+    let text = res.error_for_status().ok()?.text().await.ok()?;
+    let sha256 = hex::decode(text.split_whitespace().next()?).ok()?;
+    (sha256.len() == 32).then_some(sha256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RetryPolicy;
+    use sha2::Digest;
+    use std::io::Write;
+
+    #[test]
+    fn recognizes_self_assets() {
+        assert!(is_self_asset("zksvm-linux-amd64", "linux-amd64"));
+        assert!(is_self_asset("zksvm-windows-amd64.exe", "windows-amd64"));
+        assert!(!is_self_asset("zksvm-linux-amd64.sha256", "linux-amd64"));
+        assert!(!is_self_asset("zksvm-linux-aarch64", "linux-amd64"));
+        assert!(!is_self_asset(
+            "zksolc-linux-amd64-musl-v1.5.7",
+            "linux-amd64"
+        ));
+    }
+
+    #[test]
+    fn replaces_exe() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("zksvm");
+        fs::write(&exe, "old").unwrap();
+        let mut staged = tempfile::NamedTempFile::new_in(dir.path()).unwrap();
+        staged.write_all(b"new").unwrap();
+        replace_exe(staged, &exe).unwrap();
+        assert_eq!(fs::read(&exe).unwrap(), b"new");
+        remove_replaced_exe(&exe);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn updates_from_url() {
+        use std::{
+            io::{BufRead, BufReader},
+            net::TcpListener,
+        };
+
+        let binbytes = b"zksvm from a release";
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!(
+            "http://{}/zksvm-linux-amd64",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                BufReader::new(&stream)
+                    .read_line(&mut String::new())
+                    .unwrap();
+                let mut response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                    binbytes.len()
+                )
+                .into_bytes();
+                response.extend_from_slice(binbytes);
+                let _ = stream.write_all(&response);
+            }
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("zksvm");
+        fs::write(&exe, "old").unwrap();
+        let mut update = SelfUpdate {
+            version: Version::new(9, 9, 9),
+            asset: "zksvm-linux-amd64".to_string(),
+            url,
+            sha256: sha2::Sha256::digest(b"other").to_vec(),
+        };
+        let opts = InstallOptions::default().retry(RetryPolicy {
+            attempts: 1,
+            ..Default::default()
+        });
+        assert!(matches!(
+            self_update(&update, &exe, &opts).await,
+            Err(SvmError::ChecksumMismatch { .. })
+        ));
+        assert_eq!(fs::read(&exe).unwrap(), b"old");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        update.sha256 = sha2::Sha256::digest(binbytes).to_vec();
+        self_update(&update, &exe, &opts).await.unwrap();
+        assert_eq!(fs::read(&exe).unwrap(), binbytes);
+    }
+}
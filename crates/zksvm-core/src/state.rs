@@ -0,0 +1,410 @@
+use crate::{
+    aliases_path, config_path, data_dir, fsutil, get_global_version, global_version_path,
+    installed_versions, Config, InstallOutcome, SvmError, Warning,
+};
+use semver::Version;
+use sha2::{Digest, Sha256};
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The integrity of a state file protected by a checksum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Integrity {
+    /// The file matches its checksum.
+    Valid,
+    /// The file exists but has no checksum, e.g. because it was written by an older zksvm.
+    Unverified,
+    /// The file does not exist.
+    Missing,
+    /// The file does not match its checksum, e.g. after a partial write.
+    Corrupted,
+}
+
+/// How a corrupted state file was recovered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    /// The file was restored from the backup written before its last change.
+    RestoredBackup,
+    /// No usable backup existed and the file was reset to its default contents.
+    ResetToDefault,
+}
+
+/// A consistent snapshot of the zksolc versions installed on this machine and the global
+/// default.
+///
+/// Reading the installed versions and the global version separately can observe a
+/// half-finished install, removal or switch by another process. [`LocalState::read`] reads both
+/// under a shared lock on the data directory, which all of those operations hold exclusively.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocalState {
+    /// The installed versions, in ascending order.
+    pub installed: Vec<Version>,
+    /// The global default version, if set.
+    pub global: Option<Version>,
+}
+
+impl LocalState {
+    /// Reads the local state while holding a shared lock on the data directory.
+    pub fn read() -> Result<Self, SvmError> {
+        let _lock = lock_data_dir(false)?;
+        Ok(Self {
+            installed: installed_versions()?,
+            global: get_global_version()?,
+        })
+    }
+
+    /// Returns whether `version` is installed.
+    pub fn is_installed(&self, version: &Version) -> bool {
+        self.installed.contains(version)
+    }
+
+    /// Returns whether `version` is installed and whether it is the global default, or `None`
+    /// if it is not installed.
+    pub fn install_status(&self, version: &Version) -> Option<InstallOutcome> {
+        if !self.is_installed(version) {
+            None
+        } else if self.global.as_ref() == Some(version) {
+            Some(InstallOutcome::AlreadyActive)
+        } else {
+            Some(InstallOutcome::AlreadyInstalled)
+        }
+    }
+}
+
+/// A lock on the data directory, released once dropped.
+pub(crate) struct DataDirLock {
+    _file: fs::File,
+}
+
+/// Locks the data directory, blocking until the lock is available.
+///
+/// Inside of a multi-threaded tokio runtime, the other tasks keep running while it waits.
+/// Operations changing the installed versions or the global version take the lock
+/// exclusively, readers of [`LocalState`] take it shared. Returns `None` if the data directory
+/// does not exist yet, as there is nothing to protect.
+pub(crate) fn lock_data_dir(exclusive: bool) -> Result<Option<DataDirLock>, SvmError> {
+    use fs4::FileExt;
+    let file = match fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(data_dir().join(".lock-state"))
+    {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let locked = if exclusive {
+        file.try_lock_exclusive()
+    } else {
+        FileExt::try_lock_shared(&file)
+    };
+    match locked {
+        Ok(()) => {}
+        Err(err) if err.kind() == fs4::lock_contended_error().kind() => {
+            tracing::debug!(exclusive, "waiting for the lock of the data directory");
+            let started = std::time::Instant::now();
+            block_in_place(|| {
+                if exclusive {
+                    file.lock_exclusive()
+                } else {
+                    FileExt::lock_shared(&file)
+                }
+            })?;
+            tracing::debug!(waited = ?started.elapsed(), "locked the data directory");
+        }
+        Err(err) => return Err(err.into()),
+    }
+    Ok(Some(DataDirLock { _file: file }))
+}
+
+/// A state file zksvm protects with a checksum.
+struct StateFile {
+    path: PathBuf,
+    /// The contents the file is reset to if it has no usable backup.
+    default: &'static str,
+    /// Whether users edit the file by hand, so that a change that still parses is an edit rather
+    /// than corruption.
+    editable: bool,
+}
+
+/// Runs the blocking `wait`, handing the worker thread of a multi-threaded tokio runtime over to
+/// the other tasks meanwhile, so that they keep running, e.g. to handle signals.
+fn block_in_place<T>(wait: impl FnOnce() -> T) -> T {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(wait)
+        }
+        _ => wait(),
+    }
+}
+
+/// Returns the state files zksvm protects with checksums.
+fn state_files() -> Vec<StateFile> {
+    let mut files = vec![
+        StateFile {
+            path: global_version_path().to_path_buf(),
+            default: "",
+            editable: false,
+        },
+        StateFile {
+            path: aliases_path(),
+            default: "",
+            editable: false,
+        },
+    ];
+    files.extend(config_path().map(|path| StateFile {
+        path,
+        default: "",
+        editable: true,
+    }));
+    files
+}
+
+impl StateFile {
+    /// Checks the file against its checksum, taking a hand edit of an editable file as
+    /// [`Integrity::Unverified`].
+    fn verify(&self) -> Result<Integrity, SvmError> {
+        match verify_state_file(&self.path)? {
+            Integrity::Corrupted if self.is_edit() => Ok(Integrity::Unverified),
+            integrity => Ok(integrity),
+        }
+    }
+
+    /// Returns whether the editable file still parses, so that it was changed on purpose.
+    fn is_edit(&self) -> bool {
+        self.editable
+            && fs::read_to_string(&self.path)
+                .is_ok_and(|contents| toml::from_str::<Config>(&contents).is_ok())
+    }
+}
+
+/// Checks all state files zksvm protects, without recovering any.
+pub(crate) fn verify_state_files() -> Vec<(PathBuf, Result<Integrity, SvmError>)> {
+    state_files()
+        .into_iter()
+        .map(|file| {
+            let integrity = file.verify();
+            (file.path, integrity)
+        })
+        .collect()
+}
+
+/// Returns the path of the checksum file accompanying `path`.
+fn checksum_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".sha256")
+}
+
+/// Returns the path of the backup of `path`.
+fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix);
+    path.into()
+}
+
+fn checksum(contents: &[u8]) -> String {
+    hex::encode(Sha256::digest(contents))
+}
+
+/// Writes a state file along with its checksum, backing up the previous contents first.
+///
+/// Each file is replaced atomically, so readers never see partial contents. Writers hold the
+/// exclusive lock of the data directory, see [`lock_data_dir`].
+pub(crate) fn write_state_file(path: &Path, contents: &str) -> Result<(), SvmError> {
+    match verify_state_file(path)? {
+        Integrity::Valid | Integrity::Unverified => {
+            fsutil::copy_atomic(path, &backup_path(path))?;
+        }
+        Integrity::Missing | Integrity::Corrupted => {}
+    }
+    fsutil::write_atomic(path, contents)?;
+    fsutil::write_atomic(&checksum_path(path), checksum(contents.as_bytes()))
+}
+
+/// Checks a state file against its checksum.
+pub fn verify_state_file(path: &Path) -> Result<Integrity, SvmError> {
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Integrity::Missing),
+        Err(err) => return Err(err.into()),
+    };
+    let expected = match fs::read_to_string(checksum_path(path)) {
+        Ok(expected) => expected,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Integrity::Unverified),
+        Err(err) => return Err(err.into()),
+    };
+    if expected.trim() == checksum(&contents) {
+        Ok(Integrity::Valid)
+    } else {
+        Ok(Integrity::Corrupted)
+    }
+}
+
+/// Recovers a corrupted state file from its backup, or resets it to `default` if there is none.
+pub fn recover_state_file(path: &Path, default: &str) -> Result<Recovery, SvmError> {
+    let (contents, recovery) = match fs::read_to_string(backup_path(path)) {
+        Ok(backup) => (backup, Recovery::RestoredBackup),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            (default.to_string(), Recovery::ResetToDefault)
+        }
+        Err(err) => return Err(err.into()),
+    };
+    fsutil::write_atomic(path, &contents)?;
+    fsutil::write_atomic(&checksum_path(path), checksum(contents.as_bytes()))?;
+    Ok(recovery)
+}
+
+/// Checks all state files zksvm protects and recovers the corrupted ones.
+///
+/// A config file edited by hand is not corrupted as long as it parses, its new checksum is
+/// recorded instead. Returns a warning for each recovered file.
+pub fn recover_state_files() -> Result<Vec<Warning>, SvmError> {
+    let mut recovered = vec![];
+    for file in state_files() {
+        if verify_state_file(&file.path)? != Integrity::Corrupted {
+            continue;
+        }
+        // a file and its checksum are replaced one after the other, so check again once no other
+        // process is writing them
+        let _lock = lock_data_dir(true)?;
+        if verify_state_file(&file.path)? != Integrity::Corrupted {
+            continue;
+        }
+        if file.is_edit() {
+            let contents = fs::read(&file.path)?;
+            fsutil::write_atomic(&checksum_path(&file.path), checksum(&contents))?;
+        } else {
+            let recovery = recover_state_file(&file.path, file.default)?;
+            recovered.push(Warning::StateFileRecovered {
+                path: file.path,
+                recovery,
+            });
+        }
+    }
+    Ok(recovered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_and_recovers_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".global-version");
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Missing);
+
+        fs::write(&path, "1.3.16").unwrap();
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Unverified);
+
+        write_state_file(&path, "1.3.17").unwrap();
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Valid);
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "1.3.16");
+
+        // simulate a torn write
+        fs::write(&path, "1.3").unwrap();
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Corrupted);
+
+        assert_eq!(
+            recover_state_file(&path, "").unwrap(),
+            Recovery::RestoredBackup
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1.3.16");
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Valid);
+    }
+
+    #[test]
+    fn readers_never_see_partial_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".global-version");
+        write_state_file(&path, "1.3.17").unwrap();
+
+        let writer = {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                for i in 0..200 {
+                    let version = if i % 2 == 0 { "1.5.7" } else { "1.3.17" };
+                    write_state_file(&path, version).unwrap();
+                }
+            })
+        };
+        while !writer.is_finished() {
+            let contents = fs::read_to_string(&path).unwrap();
+            assert!(contents == "1.3.17" || contents == "1.5.7", "{contents:?}");
+        }
+        writer.join().unwrap();
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Valid);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn local_state_snapshot() {
+        let version = Version::new(0, 0, 44);
+        crate::setup_data_dir().unwrap();
+        let state = LocalState::read().unwrap();
+        assert_eq!(state.install_status(&version), None);
+
+        fs::create_dir_all(crate::version_path(&version.to_string())).unwrap();
+        let state = LocalState::read().unwrap();
+        crate::remove_version(&version).unwrap();
+        assert!(state.is_installed(&version));
+        assert_ne!(
+            state.install_status(&version),
+            Some(InstallOutcome::AlreadyActive)
+        );
+        assert!(!LocalState::read().unwrap().is_installed(&version));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn keeps_hand_edited_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::env::set_var(crate::ZKSVM_CONFIG, &path);
+        let mut config = Config::default();
+        config.set("install.attempts", Some("5")).unwrap();
+        config.write(&path).unwrap();
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Valid);
+
+        fs::write(&path, "[install]\nattempts = 7\n").unwrap();
+        assert!(verify_state_files()
+            .iter()
+            .any(|(p, integrity)| p == &path && matches!(integrity, Ok(Integrity::Unverified))));
+        assert!(recover_state_files().unwrap().is_empty());
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Valid);
+        assert_eq!(Config::load().unwrap().install.attempts, Some(7));
+
+        // a torn write is recovered from the backup
+        config.write(&path).unwrap();
+        fs::write(&path, "[install]\nattem").unwrap();
+        let recovered = recover_state_files().unwrap();
+        std::env::remove_var(crate::ZKSVM_CONFIG);
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(Config::read(&path).unwrap().install.attempts, Some(7));
+    }
+
+    #[test]
+    fn resets_without_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".global-version");
+        write_state_file(&path, "1.3.17").unwrap();
+        assert!(!backup_path(&path).exists());
+
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(
+            recover_state_file(&path, "").unwrap(),
+            Recovery::ResetToDefault
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        assert_eq!(verify_state_file(&path).unwrap(), Integrity::Valid);
+    }
+}
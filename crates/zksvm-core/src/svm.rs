@@ -0,0 +1,426 @@
+use crate::{
+    paths::DataDir, platform::Platform, CacheCleanReport, CompilerInfo, DiagnosticReport,
+    GlobalBinary, GlobalVersionState, ImportedBinary, InstallEvent, InstallOptions, InstallOutcome,
+    InstalledVersion, LocalState, PruneReport, RemovalReport, SvmError, Tool, UsageStats,
+    VerificationResult,
+};
+use semver::Version;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// zksvm operating on its own data directory, isolated from all others.
+///
+/// The free functions of this crate operate on `Svm::default()`, which uses
+/// [`DataDir::current`]. Separate instances can be used at the same time from different threads
+/// and tasks without affecting each other, e.g. to run installs of parallel tests in separate
+/// temporary directories.
+///
+/// Methods cover installing, verifying and removing versions, the global version, aliases,
+/// bundles, usage stats and [`diagnose`](crate::diagnose). The remaining free functions, e.g.
+/// [`all_releases`](crate::all_releases) with its release list cache,
+/// [`Workspace`](crate::Workspace) and the path functions other than
+/// [`version_path`](Self::version_path) and [`version_binary`](Self::version_binary), always use
+/// the current data directory.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), zksvm_core::SvmError> {
+/// let sandbox = tempfile::tempdir()?;
+/// let svm = zksvm_core::Svm::new(sandbox.path());
+/// svm.install(&semver::Version::new(1, 5, 7)).await?;
+/// assert_eq!(svm.installed_versions()?, [semver::Version::new(1, 5, 7)]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Svm {
+    pub(crate) data_dir: DataDir,
+}
+
+impl Default for Svm {
+    fn default() -> Self {
+        Self::new(DataDir::current())
+    }
+}
+
+impl Svm {
+    /// Creates an instance keeping its installs and state in `data_dir`.
+    pub fn new(data_dir: impl Into<DataDir>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+        }
+    }
+
+    /// Returns the path to the data directory.
+    pub fn data_dir(&self) -> &Path {
+        self.data_dir.path()
+    }
+
+    /// See [`version_path`](crate::version_path).
+    pub fn version_path(&self, version: &Version) -> PathBuf {
+        self.data_dir
+            .scope(|| crate::version_path(&version.to_string()))
+    }
+
+    /// See [`version_binary`](crate::version_binary).
+    pub fn version_binary(&self, version: &Version) -> PathBuf {
+        self.data_dir
+            .scope(|| crate::version_binary(&version.to_string()))
+    }
+
+    /// See [`install`](crate::install()).
+    pub async fn install(&self, version: &Version) -> Result<PathBuf, SvmError> {
+        self.data_dir.scope_async(crate::install(version)).await
+    }
+
+    /// See [`install_with_options`](crate::install_with_options).
+    pub async fn install_with_options(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+    ) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope_async(crate::install_with_options(version, opts))
+            .await
+    }
+
+    /// See [`install_with_events`](crate::install_with_events).
+    pub async fn install_with_events(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+        on_event: impl Fn(InstallEvent),
+    ) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope_async(crate::install_with_events(version, opts, on_event))
+            .await
+    }
+
+    /// See [`install_verified`](crate::install_verified).
+    pub async fn install_verified(&self, version: &Version) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope_async(crate::install_verified(version))
+            .await
+    }
+
+    /// See [`install_for_platform`](crate::install_for_platform).
+    pub async fn install_for_platform(
+        &self,
+        version: &Version,
+        platform: Platform,
+    ) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope_async(crate::install_for_platform(version, platform))
+            .await
+    }
+
+    /// See [`install_from_url_with_options`](crate::install_from_url_with_options).
+    pub async fn install_from_url(
+        &self,
+        version: &Version,
+        url: &Url,
+        expected_sha256: &[u8],
+        opts: &InstallOptions,
+    ) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope_async(crate::install_from_url_with_options(
+                version,
+                url,
+                expected_sha256,
+                opts,
+            ))
+            .await
+    }
+
+    /// See [`install_tool`](crate::install_tool).
+    pub async fn install_tool(&self, tool: Tool, version: &Version) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope_async(crate::install_tool(tool, version))
+            .await
+    }
+
+    /// See [`install_many`](crate::install_many).
+    pub async fn install_many(&self, versions: &[Version]) -> Vec<Result<PathBuf, SvmError>> {
+        self.data_dir
+            .scope_async(crate::install_many(versions))
+            .await
+    }
+
+    /// See [`install_many_with_events`](crate::install_many_with_events).
+    pub async fn install_many_with_events(
+        &self,
+        versions: &[Version],
+        opts: &InstallOptions,
+        concurrency: usize,
+        on_event: impl Fn(&Version, InstallEvent) + Send + Sync + 'static,
+    ) -> Vec<Result<PathBuf, SvmError>> {
+        self.data_dir
+            .scope_async(crate::install_many_with_events(
+                versions,
+                opts,
+                concurrency,
+                on_event,
+            ))
+            .await
+    }
+
+    /// See [`ensure_installed`](crate::ensure_installed).
+    pub async fn ensure_installed(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+    ) -> Result<InstallOutcome, SvmError> {
+        self.data_dir
+            .scope_async(crate::ensure_installed(version, opts))
+            .await
+    }
+
+    /// See [`ensure_version_exact_with_options`](crate::ensure_version_exact_with_options).
+    pub async fn ensure_version_exact(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+    ) -> Result<InstallOutcome, SvmError> {
+        self.data_dir
+            .scope_async(crate::ensure_version_exact_with_options(version, opts))
+            .await
+    }
+
+    /// See [`blocking_install`](crate::blocking_install).
+    #[cfg(feature = "blocking")]
+    pub fn blocking_install(&self, version: &Version) -> Result<PathBuf, SvmError> {
+        self.data_dir.scope(|| crate::blocking_install(version))
+    }
+
+    /// See [`blocking_install_with_options`](crate::blocking_install_with_options).
+    #[cfg(feature = "blocking")]
+    pub fn blocking_install_with_options(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+    ) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope(|| crate::blocking_install_with_options(version, opts))
+    }
+
+    /// See [`blocking_install_with_events`](crate::blocking_install_with_events).
+    #[cfg(feature = "blocking")]
+    pub fn blocking_install_with_events(
+        &self,
+        version: &Version,
+        opts: &InstallOptions,
+        on_event: impl Fn(InstallEvent),
+    ) -> Result<PathBuf, SvmError> {
+        self.data_dir
+            .scope(|| crate::blocking_install_with_events(version, opts, on_event))
+    }
+
+    /// See [`install_bundle`](crate::install_bundle).
+    pub fn install_bundle(&self, path: &Path) -> Result<Vec<(Version, InstallOutcome)>, SvmError> {
+        self.data_dir.scope(|| crate::install_bundle(path))
+    }
+
+    /// See [`export_bundle`](crate::export_bundle).
+    pub fn export_bundle(
+        &self,
+        versions: &[Version],
+        platform: Platform,
+        path: &Path,
+    ) -> Result<(), SvmError> {
+        self.data_dir
+            .scope(|| crate::export_bundle(versions, platform, path))
+    }
+
+    /// See [`import_binary`](crate::import_binary).
+    pub fn import_binary(
+        &self,
+        path: &Path,
+        version: Option<&Version>,
+    ) -> Result<ImportedBinary, SvmError> {
+        self.data_dir.scope(|| crate::import_binary(path, version))
+    }
+
+    /// See [`install_status`](crate::install_status).
+    pub fn install_status(&self, version: &Version) -> Result<Option<InstallOutcome>, SvmError> {
+        self.data_dir.scope(|| crate::install_status(version))
+    }
+
+    /// See [`installed_versions`](crate::installed_versions).
+    pub fn installed_versions(&self) -> Result<Vec<Version>, SvmError> {
+        self.data_dir.scope(crate::installed_versions)
+    }
+
+    /// See [`installed_tool_versions`](crate::installed_tool_versions).
+    pub fn installed_tool_versions(&self, tool: Tool) -> Result<Vec<Version>, SvmError> {
+        self.data_dir.scope(|| crate::installed_tool_versions(tool))
+    }
+
+    /// See [`list_installed`](crate::list_installed).
+    pub async fn list_installed(&self) -> Result<Vec<InstalledVersion>, SvmError> {
+        self.data_dir.scope_async(crate::list_installed()).await
+    }
+
+    /// See [`verify_installed`](crate::verify_installed).
+    pub async fn verify_installed(
+        &self,
+        version: &Version,
+    ) -> Result<VerificationResult, SvmError> {
+        self.data_dir
+            .scope_async(crate::verify_installed(version))
+            .await
+    }
+
+    /// See [`blocking_verify_installed`](crate::blocking_verify_installed).
+    #[cfg(feature = "blocking")]
+    pub fn blocking_verify_installed(
+        &self,
+        version: &Version,
+    ) -> Result<VerificationResult, SvmError> {
+        self.data_dir
+            .scope(|| crate::blocking_verify_installed(version))
+    }
+
+    /// See [`compiler_info`](crate::compiler_info).
+    pub fn compiler_info(&self, version: &Version) -> Result<CompilerInfo, SvmError> {
+        self.data_dir.scope(|| crate::compiler_info(version))
+    }
+
+    /// See [`LocalState::read`].
+    pub fn local_state(&self) -> Result<LocalState, SvmError> {
+        self.data_dir.scope(LocalState::read)
+    }
+
+    /// See [`get_global_version`](crate::get_global_version).
+    pub fn get_global_version(&self) -> Result<Option<Version>, SvmError> {
+        self.data_dir.scope(crate::get_global_version)
+    }
+
+    /// See [`get_global_version_verified`](crate::get_global_version_verified).
+    pub fn get_global_version_verified(&self) -> Result<Option<Version>, SvmError> {
+        self.data_dir.scope(crate::get_global_version_verified)
+    }
+
+    /// See [`global_version_state`](crate::global_version_state).
+    pub fn global_version_state(&self) -> Result<GlobalVersionState, SvmError> {
+        self.data_dir.scope(crate::global_version_state)
+    }
+
+    /// See [`set_global_version`](crate::set_global_version).
+    pub fn set_global_version(&self, version: &Version) -> Result<(), SvmError> {
+        self.data_dir.scope(|| crate::set_global_version(version))
+    }
+
+    /// See [`set_global_version_with`](crate::set_global_version_with).
+    pub fn set_global_version_with(
+        &self,
+        version: &Version,
+        binary: GlobalBinary,
+    ) -> Result<(), SvmError> {
+        self.data_dir
+            .scope(|| crate::set_global_version_with(version, binary))
+    }
+
+    /// See [`unset_global_version`](crate::unset_global_version).
+    pub fn unset_global_version(&self) -> Result<(), SvmError> {
+        self.data_dir.scope(crate::unset_global_version)
+    }
+
+    /// See [`remove_version`](crate::remove_version).
+    pub fn remove_version(&self, version: &Version) -> Result<RemovalReport, SvmError> {
+        self.data_dir.scope(|| crate::remove_version(version))
+    }
+
+    /// See [`prune`](crate::prune).
+    pub fn prune(&self, keep: impl Fn(&Version) -> bool) -> Result<PruneReport, SvmError> {
+        self.data_dir.scope(|| crate::prune(keep))
+    }
+
+    /// See [`clean_download_cache`](crate::clean_download_cache).
+    pub fn clean_download_cache(&self) -> Result<CacheCleanReport, SvmError> {
+        self.data_dir.scope(crate::clean_download_cache)
+    }
+
+    /// See [`diagnose`](crate::diagnose).
+    pub async fn diagnose(&self) -> DiagnosticReport {
+        self.data_dir.scope_async(crate::diagnose()).await
+    }
+
+    /// See [`list_aliases`](crate::list_aliases).
+    pub fn list_aliases(&self) -> Result<BTreeMap<String, Version>, SvmError> {
+        self.data_dir.scope(crate::list_aliases)
+    }
+
+    /// See [`resolve_alias`](crate::resolve_alias).
+    pub fn resolve_alias(&self, name: &str) -> Option<Version> {
+        self.data_dir.scope(|| crate::resolve_alias(name))
+    }
+
+    /// See [`set_alias`](crate::set_alias).
+    pub fn set_alias(&self, name: &str, version: &Version) -> Result<Option<Version>, SvmError> {
+        self.data_dir.scope(|| crate::set_alias(name, version))
+    }
+
+    /// See [`remove_alias`](crate::remove_alias).
+    pub fn remove_alias(&self, name: &str) -> Result<Option<Version>, SvmError> {
+        self.data_dir.scope(|| crate::remove_alias(name))
+    }
+
+    /// See [`usage_stats`](crate::usage_stats).
+    pub fn usage_stats(&self) -> Result<UsageStats, SvmError> {
+        self.data_dir.scope(crate::usage_stats)
+    }
+
+    /// See [`clear_usage_stats`](crate::clear_usage_stats).
+    pub fn clear_usage_stats(&self) -> Result<(), SvmError> {
+        self.data_dir.scope(crate::clear_usage_stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn instances_are_isolated() {
+        let (a, b) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (a, b) = (Svm::new(a.path()), Svm::new(b.path()));
+        let version = Version::new(0, 0, 60);
+
+        fs::create_dir_all(a.version_path(&version)).unwrap();
+        fs::write(a.version_binary(&version), "zksolc").unwrap();
+        a.set_global_version(&version).unwrap();
+        assert!(a.version_path(&version).starts_with(a.data_dir()));
+        assert_eq!(a.installed_versions().unwrap(), vec![version.clone()]);
+        assert_eq!(a.get_global_version().unwrap(), Some(version.clone()));
+        assert!(b.installed_versions().unwrap().is_empty());
+        assert_eq!(b.get_global_version().unwrap(), None);
+        assert!(!crate::installed_versions().unwrap().contains(&version));
+
+        // concurrent tasks each see their own data directory
+        let (in_a, in_b) = tokio::join!(
+            a.data_dir.scope_async(async { crate::data_dir() }),
+            b.data_dir.scope_async(async { crate::data_dir() }),
+        );
+        assert_eq!(
+            (in_a.as_path(), in_b.as_path()),
+            (a.data_dir(), b.data_dir())
+        );
+
+        a.set_alias("stable", &version).unwrap();
+        assert_eq!(a.resolve_alias("stable"), Some(version.clone()));
+        assert_eq!(b.resolve_alias("stable"), None);
+        assert_eq!(
+            b.global_version_state().unwrap(),
+            crate::GlobalVersionState::Unset
+        );
+
+        let report = a.prune(|_| false).unwrap();
+        assert_eq!(report.kept, vec![version.clone()]);
+        a.unset_global_version().unwrap();
+        a.remove_version(&version).unwrap();
+        assert!(a.installed_versions().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{github, paths, releases, Platform, ReleaseSource, SvmError};
+use semver::Version;
+use std::{fmt, path::PathBuf, str::FromStr};
+
+/// The compilers zksvm can install.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Tool {
+    /// The zkSync Solidity compiler, which the free functions of this crate manage.
+    #[default]
+    Zksolc,
+    /// The zkSync Vyper compiler.
+    Zkvyper,
+    /// The upstream Solidity compiler, which zksolc drives to compile Solidity.
+    #[cfg(feature = "solc")]
+    Solc,
+}
+
+/// The base URL the official solc builds are published below, in the
+/// [`Lists`](crate::SourceLayout::Lists) layout.
+#[cfg(feature = "solc")]
+pub const SOLC_RELEASES_URL: &str = "https://binaries.soliditylang.org/";
+
+impl Tool {
+    /// All tools zksvm can install.
+    pub const ALL: &'static [Tool] = &[
+        Tool::Zksolc,
+        Tool::Zkvyper,
+        #[cfg(feature = "solc")]
+        Tool::Solc,
+    ];
+
+    /// Returns the name of the tool, which its artifacts and installed binaries start with.
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::Zksolc => "zksolc",
+            Tool::Zkvyper => "zkvyper",
+            #[cfg(feature = "solc")]
+            Tool::Solc => "solc",
+        }
+    }
+
+    /// Returns where releases of the tool are downloaded from by default.
+    ///
+    /// For zksolc this is [`ReleaseSource::from_env`], zkvyper tracks the GitHub Releases of its
+    /// repository and solc the lists of [`SOLC_RELEASES_URL`].
+    pub fn source(self) -> Result<ReleaseSource, SvmError> {
+        match self {
+            Tool::Zksolc => ReleaseSource::from_env(),
+            Tool::Zkvyper => Ok(ReleaseSource::github(github::ZKVYPER_REPO)?.with_tool(self)),
+            #[cfg(feature = "solc")]
+            Tool::Solc => {
+                Ok(ReleaseSource::new(url::Url::parse(SOLC_RELEASES_URL)?)?.with_tool(self))
+            }
+        }
+    }
+
+    /// Returns the name of the directory the builds of the tool for `platform` are published in.
+    ///
+    /// solc publishes universal macOS builds in the directory of x64 macOS only.
+    pub(crate) fn platform_dir(self, platform: Platform) -> &'static str {
+        match (self, platform) {
+            #[cfg(feature = "solc")]
+            (Tool::Solc, Platform::MacOsAarch64) => "macosx-amd64",
+            _ => releases::platform_dir(platform),
+        }
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Tool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tool::ALL
+            .iter()
+            .copied()
+            .find(|tool| tool.name() == s)
+            .ok_or_else(|| {
+                let names = Tool::ALL.iter().map(|tool| tool.name()).collect::<Vec<_>>();
+                format!("unsupported tool {s}, expected one of {}", names.join(", "))
+            })
+    }
+}
+
+/// Returns the versions of `tool` installed for this machine, sorted in ascending order.
+///
+/// For zksolc this is [`installed_versions`](crate::installed_versions).
+pub fn installed_tool_versions(tool: Tool) -> Result<Vec<Version>, SvmError> {
+    crate::read_installed_versions(&paths::versions_dir(tool, None))
+}
+
+/// Installs the provided version of `tool` in the machine.
+///
+/// Returns the path to the installed binary. See
+/// [`InstallOptions::tool`](crate::InstallOptions::tool).
+pub async fn install_tool(tool: Tool, version: &Version) -> Result<PathBuf, SvmError> {
+    crate::install_with_options(version, &crate::InstallOptions::default().tool(tool)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SourceLayout;
+
+    #[test]
+    fn tool_names() {
+        for &tool in Tool::ALL {
+            assert_eq!(tool.to_string().parse(), Ok(tool));
+        }
+        assert!("zkllvm".parse::<Tool>().is_err());
+        assert_eq!(Tool::default(), Tool::Zksolc);
+
+        let source = Tool::Zkvyper.source().unwrap();
+        assert_eq!(source.tool(), Tool::Zkvyper);
+        assert_eq!(source.layout(), SourceLayout::GithubReleases);
+        assert_eq!(
+            source.base_url().as_str(),
+            "https://api.github.com/repos/matter-labs/era-compiler-vyper/"
+        );
+        assert_eq!(
+            Tool::Zkvyper.platform_dir(Platform::MacOsAarch64),
+            "macosx-arm64"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "solc")]
+    fn solc_lists() {
+        let source = Tool::Solc.source().unwrap();
+        assert_eq!(source.layout(), SourceLayout::Lists);
+        assert_eq!(
+            source.releases_url(Platform::MacOsAarch64).as_str(),
+            "https://binaries.soliditylang.org/macosx-amd64/list.json"
+        );
+        assert_eq!(
+            source
+                .artifact_url(Platform::LinuxAmd64, "solc-linux-amd64-v0.8.24+commit.e11b9ed9")
+                .unwrap()
+                .as_str(),
+            "https://binaries.soliditylang.org/linux-amd64/solc-linux-amd64-v0.8.24+commit.e11b9ed9"
+        );
+
+        // the official lists prefix checksums with 0x and carry fields zksvm does not use
+        let list: crate::Releases = serde_json::from_value(serde_json::json!({
+            "builds": [{
+                "path": "solc-linux-amd64-v0.8.24+commit.e11b9ed9",
+                "version": "0.8.24",
+                "build": "commit.e11b9ed9",
+                "longVersion": "0.8.24+commit.e11b9ed9",
+                "keccak256": format!("0x{}", "ab".repeat(32)),
+                "sha256": format!("0x{}", "cd".repeat(32)),
+                "urls": []
+            }],
+            "releases": {"0.8.24": "solc-linux-amd64-v0.8.24+commit.e11b9ed9"},
+            "latestRelease": "0.8.24"
+        }))
+        .unwrap();
+        let version = Version::new(0, 8, 24);
+        assert_eq!(list.get_checksum(&version), Some(vec![0xcd; 32]));
+        assert_eq!(
+            source
+                .version_url(Platform::LinuxAmd64, &version, &list)
+                .unwrap(),
+            source
+                .artifact_url(
+                    Platform::LinuxAmd64,
+                    "solc-linux-amd64-v0.8.24+commit.e11b9ed9"
+                )
+                .unwrap()
+        );
+    }
+}
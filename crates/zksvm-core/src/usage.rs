@@ -0,0 +1,172 @@
+//! Local usage stats, recorded only if the `telemetry` setting is enabled. Nothing is sent
+//! anywhere.
+
+use crate::{config, data_dir, fsutil, usage_path, version_path, SvmError};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, time::SystemTime};
+
+/// How often commands were invoked and versions were run, kept in [`usage_path`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    /// How often each subcommand was invoked, by name.
+    pub commands: BTreeMap<String, u64>,
+    /// How often each version was run, e.g. through the shim or `zksvm run`.
+    pub versions: BTreeMap<Version, VersionUsage>,
+}
+
+/// How often a version was run, see [`UsageStats`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionUsage {
+    pub runs: u64,
+    #[serde(with = "rfc3339")]
+    pub last_used: SystemTime,
+}
+
+impl UsageStats {
+    /// Returns whether `version` was run since `since`.
+    ///
+    /// Versions that were not run since stats are recorded count as used when they were
+    /// installed, so enabling the stats does not make every version look unused.
+    pub fn used_since(&self, version: &Version, since: SystemTime) -> bool {
+        let last_used = match self.versions.get(version) {
+            Some(usage) => Some(usage.last_used),
+            None => fs::metadata(version_path(&version.to_string()))
+                .and_then(|meta| meta.created().or_else(|_| meta.modified()))
+                .ok(),
+        };
+        last_used.is_some_and(|last_used| last_used >= since)
+    }
+}
+
+/// Returns whether usage stats are recorded, i.e. the `telemetry` setting is enabled.
+pub fn telemetry_enabled() -> bool {
+    config::file_config().telemetry.unwrap_or(false)
+}
+
+/// Returns the recorded usage stats, which are empty if none were recorded.
+pub fn usage_stats() -> Result<UsageStats, SvmError> {
+    let path = usage_path();
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(UsageStats::default()),
+        Err(err) => return Err(err.into()),
+    };
+    toml::from_str(&contents).map_err(|err| SvmError::InvalidUsageStats(path, err.to_string()))
+}
+
+/// Counts an invocation of the subcommand `name`, if [`telemetry_enabled`].
+pub fn record_command(name: &str) -> Result<(), SvmError> {
+    update_usage(|stats| *stats.commands.entry(name.to_string()).or_default() += 1)
+}
+
+/// Counts a run of `version`, if [`telemetry_enabled`].
+pub fn record_version_use(version: &Version) -> Result<(), SvmError> {
+    let now = SystemTime::now();
+    update_usage(|stats| {
+        let usage = stats
+            .versions
+            .entry(version.clone())
+            .or_insert(VersionUsage {
+                runs: 0,
+                last_used: now,
+            });
+        usage.runs += 1;
+        usage.last_used = now;
+    })
+}
+
+/// Removes the recorded usage stats.
+pub fn clear_usage_stats() -> Result<(), SvmError> {
+    match fs::remove_file(usage_path()) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Applies `update` to the recorded stats, starting over if they cannot be read.
+///
+/// Uses its own lock rather than the one of the data directory, so that running a version never
+/// waits for an install to finish.
+fn update_usage(update: impl FnOnce(&mut UsageStats)) -> Result<(), SvmError> {
+    use fs4::FileExt;
+    if !telemetry_enabled() || !data_dir().exists() {
+        return Ok(());
+    }
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(data_dir().join(".lock-usage"))?;
+    lock.lock_exclusive()?;
+    let mut stats = usage_stats().unwrap_or_default();
+    update(&mut stats);
+    let contents = toml::to_string(&stats)
+        .map_err(|err| SvmError::InvalidUsageStats(usage_path(), err.to_string()))?;
+    fsutil::write_atomic(&usage_path(), contents)
+}
+
+/// Helper serde module to serialize and deserialize times like `2024-05-01T12:00:00Z`.
+mod rfc3339 {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::time::SystemTime;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SystemTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        humantime::parse_rfc3339(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+
+    pub fn serialize<S>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&humantime::format_rfc3339_seconds(*value).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{setup_data_dir, setup_version, DataDir};
+    use std::time::Duration;
+
+    #[test]
+    #[serial_test::serial]
+    fn records_usage_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        std::env::set_var(config::ZKSVM_CONFIG, &config);
+        DataDir::new(dir.path().join("data")).scope(|| {
+            setup_data_dir().unwrap();
+            let version = Version::new(1, 5, 7);
+            record_command("use").unwrap();
+            assert_eq!(usage_stats().unwrap(), UsageStats::default());
+
+            fs::write(&config, "telemetry = true\n").unwrap();
+            record_command("use").unwrap();
+            record_command("use").unwrap();
+            record_version_use(&version).unwrap();
+            record_version_use(&version).unwrap();
+
+            let stats = usage_stats().unwrap();
+            assert_eq!(stats.commands["use"], 2);
+            assert_eq!(stats.versions[&version].runs, 2);
+            let hour_ago = SystemTime::now() - Duration::from_secs(3600);
+            assert!(stats.used_since(&version, hour_ago));
+            assert!(!stats.used_since(&version, SystemTime::now() + Duration::from_secs(3600)));
+
+            // versions that were never run count as used when they were installed
+            let unused = Version::new(1, 5, 6);
+            assert!(!stats.used_since(&unused, hour_ago));
+            setup_version(&unused.to_string()).unwrap();
+            assert!(stats.used_since(&unused, hour_ago));
+
+            clear_usage_stats().unwrap();
+            assert_eq!(usage_stats().unwrap(), UsageStats::default());
+        });
+        std::env::remove_var(config::ZKSVM_CONFIG);
+    }
+}
@@ -0,0 +1,498 @@
+use crate::{
+    http, manifest, platform, releases, state, version_binary, version_path, Channel, Releases,
+    SvmError,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::StatusCode;
+use semver::Version;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    ops::Deref,
+    process::Command,
+    sync::Arc,
+    time::Duration,
+};
+use url::Url;
+
+/// Verifies detached signatures of downloaded zksolc binaries.
+///
+/// Signatures are taken from the `signature` field of the release list if present, or else
+/// downloaded from next to the artifact, with [`SignatureVerifier::extension`] appended to its
+/// URL.
+pub trait SignatureVerifier: fmt::Debug + Send + Sync {
+    /// The extension of detached signature files, e.g. `.minisig`.
+    fn extension(&self) -> &str;
+
+    /// Verifies that `signature` is a valid signature of `artifact`.
+    fn verify(&self, artifact: &[u8], signature: &[u8]) -> Result<(), SvmError>;
+}
+
+/// A shared [`SignatureVerifier`], as used by [`InstallOptions`](crate::InstallOptions).
+#[derive(Clone, Debug)]
+pub struct Verifier(Arc<dyn SignatureVerifier>);
+
+impl Verifier {
+    pub fn new(verifier: impl SignatureVerifier + 'static) -> Self {
+        Self(Arc::new(verifier))
+    }
+}
+
+impl Deref for Verifier {
+    type Target = dyn SignatureVerifier;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl PartialEq for Verifier {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Verifier {}
+
+/// Verifies [minisign](https://jedisct1.github.io/minisign/) signatures.
+///
+/// Only signatures of the whole file (`minisign -S -l`) are supported natively; verify prehashed
+/// signatures with a [`CommandVerifier`] running `minisign` instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinisignVerifier {
+    key_id: [u8; 8],
+    public_key: [u8; 32],
+}
+
+impl MinisignVerifier {
+    /// Parses a minisign public key, either the base64 key itself or the contents of a `.pub`
+    /// file.
+    pub fn from_public_key(key: &str) -> Result<Self, SvmError> {
+        let invalid = |reason: &str| SvmError::InvalidSignatureKey(reason.to_string());
+        let line = key
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+            .ok_or_else(|| invalid("the key is empty"))?;
+        let bytes = STANDARD
+            .decode(line)
+            .map_err(|_| invalid("the key is not valid base64"))?;
+        if bytes.len() != 42 || &bytes[..2] != b"Ed" {
+            return Err(invalid("not an Ed25519 minisign public key"));
+        }
+        Ok(Self {
+            key_id: bytes[2..10].try_into().expect("8 bytes"),
+            public_key: bytes[10..].try_into().expect("32 bytes"),
+        })
+    }
+
+    fn verify_ed25519(&self, message: &[u8], signature: &[u8]) -> Result<(), SvmError> {
+        ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, self.public_key)
+            .verify(message, signature)
+            .map_err(|_| SvmError::InvalidSignature("the signature does not match".to_string()))
+    }
+}
+
+impl SignatureVerifier for MinisignVerifier {
+    fn extension(&self) -> &str {
+        ".minisig"
+    }
+
+    fn verify(&self, artifact: &[u8], signature: &[u8]) -> Result<(), SvmError> {
+        let invalid = |reason: &str| SvmError::InvalidSignature(reason.to_string());
+        let signature =
+            std::str::from_utf8(signature).map_err(|_| invalid("not a minisig file"))?;
+        let mut lines = signature
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("untrusted comment:"));
+        let sig = lines
+            .next()
+            .and_then(|line| STANDARD.decode(line).ok())
+            .filter(|sig| sig.len() == 74)
+            .ok_or_else(|| invalid("not a minisig file"))?;
+        match &sig[..2] {
+            b"Ed" => {}
+            b"ED" => return Err(invalid("prehashed minisign signatures are not supported")),
+            _ => return Err(invalid("unknown signature algorithm")),
+        }
+        if sig[2..10] != self.key_id {
+            return Err(invalid("signed with a different key"));
+        }
+        self.verify_ed25519(artifact, &sig[10..])?;
+
+        // the trusted comment is signed along with the signature
+        let comment = lines
+            .next()
+            .and_then(|line| line.strip_prefix("trusted comment: "))
+            .ok_or_else(|| invalid("missing trusted comment"))?;
+        let global = lines
+            .next()
+            .and_then(|line| STANDARD.decode(line).ok())
+            .ok_or_else(|| invalid("missing global signature"))?;
+        self.verify_ed25519(&[&sig[10..], comment.as_bytes()].concat(), &global)
+    }
+}
+
+/// Verifies signatures by running an external program, such as `gpg` or `minisign`.
+///
+/// The artifact and the signature are written to temporary files whose paths replace the
+/// `{artifact}` and `{signature}` placeholders in the arguments. A zero exit status means the
+/// signature is valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandVerifier {
+    pub program: String,
+    pub args: Vec<String>,
+    pub extension: String,
+}
+
+impl CommandVerifier {
+    /// Verifies ASCII-armored signatures with `gpg`, against the keys in the default keyring.
+    pub fn gpg() -> Self {
+        Self {
+            program: "gpg".to_string(),
+            args: ["--batch", "--verify", "{signature}", "{artifact}"]
+                .map(String::from)
+                .to_vec(),
+            extension: ".asc".to_string(),
+        }
+    }
+}
+
+impl SignatureVerifier for CommandVerifier {
+    fn extension(&self) -> &str {
+        &self.extension
+    }
+
+    fn verify(&self, artifact: &[u8], signature: &[u8]) -> Result<(), SvmError> {
+        let mut artifact_file = tempfile::NamedTempFile::new()?;
+        artifact_file.write_all(artifact)?;
+        let mut signature_file = tempfile::NamedTempFile::new()?;
+        signature_file.write_all(signature)?;
+
+        let args = self.args.iter().map(|arg| {
+            arg.replace("{artifact}", &artifact_file.path().to_string_lossy())
+                .replace("{signature}", &signature_file.path().to_string_lossy())
+        });
+        let output = Command::new(&self.program).args(args).output()?;
+        if !output.status.success() {
+            return Err(SvmError::InvalidSignature(format!(
+                "{} failed: {}",
+                self.program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the URL of the detached signature next to an artifact.
+pub(crate) fn signature_url(artifact_url: &Url, extension: &str) -> Url {
+    let mut url = artifact_url.clone();
+    url.set_path(&format!("{}{extension}", artifact_url.path()));
+    url
+}
+
+/// Fetches the detached signature next to the artifact at `artifact_url`.
+///
+/// Returns `None` if the mirror does not publish one.
+pub(crate) async fn fetch_signature(
+    artifact_url: &Url,
+    extension: &str,
+    timeout: Duration,
+) -> Result<Option<Vec<u8>>, SvmError> {
+    let url = signature_url(artifact_url, extension);
+    let res = http::client()?
+        .timeout(timeout)
+        .build()?
+        .get(url.clone())
+        .send()
+        .await?;
+    if res.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !res.status().is_success() {
+        return Err(SvmError::UnsuccessfulResponse(url, res.status()));
+    }
+    Ok(Some(res.bytes().await?.to_vec()))
+}
+
+/// Blocking version of [`fetch_signature`].
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_fetch_signature(
+    artifact_url: &Url,
+    extension: &str,
+    timeout: Duration,
+) -> Result<Option<Vec<u8>>, SvmError> {
+    let url = signature_url(artifact_url, extension);
+    let res = http::blocking_client()?
+        .timeout(timeout)
+        .build()?
+        .get(url.clone())
+        .send()?;
+    if res.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !res.status().is_success() {
+        return Err(SvmError::UnsuccessfulResponse(url, res.status()));
+    }
+    Ok(Some(res.bytes()?.to_vec()))
+}
+
+/// Verifies the downloaded `artifact` if there is both a verifier and a signature.
+///
+/// Without either, the artifact is only accepted if a signature is not `required`.
+pub(crate) fn check(
+    version: &Version,
+    verifier: Option<&Verifier>,
+    required: bool,
+    artifact: &[u8],
+    signature: Option<&[u8]>,
+) -> Result<(), SvmError> {
+    match (verifier, signature) {
+        (Some(verifier), Some(signature)) => verifier.verify(artifact, signature),
+        _ if required => Err(SvmError::SignatureRequired(version.to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// The outcome of checking an installed zksolc binary against its published checksum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationResult {
+    /// The binary matches the published checksum.
+    Verified,
+    /// The binary differs from the published build: it was corrupted or tampered with.
+    Mismatch { expected: String, actual: String },
+    /// The version is installed, but its binary is missing.
+    Missing,
+    /// There is nothing to compare the binary against, as the release list has no checksum for
+    /// the version or the binary was unpacked from an archive.
+    Unverifiable,
+}
+
+impl VerificationResult {
+    /// Returns whether the install is broken and should be downloaded again.
+    pub fn needs_repair(&self) -> bool {
+        matches!(self, Self::Mismatch { .. } | Self::Missing)
+    }
+}
+
+/// Blocking version of [`verify_installed`].
+#[cfg(feature = "blocking")]
+pub fn blocking_verify_installed(version: &Version) -> Result<VerificationResult, SvmError> {
+    let source = crate::ReleaseSource::from_env()?.with_channel(Channel::of(version));
+    let releases =
+        releases::blocking_fetch_releases(&source, platform::artifact_platform()?, None)?;
+    verify_binary(version, &releases)
+}
+
+/// Re-hashes the installed binary of `version` and compares it against the checksum in the
+/// release list.
+///
+/// Fails with [`SvmError::VersionNotInstalled`] if the version is not installed.
+pub async fn verify_installed(version: &Version) -> Result<VerificationResult, SvmError> {
+    let releases =
+        releases::all_releases_in(platform::artifact_platform()?, Channel::of(version)).await?;
+    verify_binary(version, &releases)
+}
+
+pub(crate) fn verify_binary(
+    version: &Version,
+    releases: &Releases,
+) -> Result<VerificationResult, SvmError> {
+    let _lock = state::lock_data_dir(false)?;
+    if !version_path(&version.to_string()).is_dir() {
+        return Err(SvmError::VersionNotInstalled(version.to_string()));
+    }
+    // imported binaries are checked against the checksum recorded when importing them
+    let imported = manifest::read_manifest(version)
+        .sha256
+        .and_then(|sha256| hex::decode(sha256).ok());
+    let expected = match imported.clone() {
+        Some(expected) => expected,
+        None => {
+            let Some(expected) = releases.get_checksum(version) else {
+                return Ok(VerificationResult::Unverifiable);
+            };
+            // the checksum is of the archive, not of the binary unpacked from it
+            if releases
+                .get_artifact(version)
+                .is_some_and(|artifact| artifact.ends_with(".zip"))
+            {
+                return Ok(VerificationResult::Unverifiable);
+            }
+            expected
+        }
+    };
+    let binary = match fs::read(version_binary(&version.to_string())) {
+        Ok(binary) => binary,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(VerificationResult::Missing)
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let actual = Sha256::digest(binary);
+    // the binary may be a build for another C library than the one listed first
+    let other_build = || {
+        imported.is_none()
+            && releases
+                .builds
+                .iter()
+                .any(|build| &build.version == version && build.sha256[..] == actual[..])
+    };
+    if actual[..] == expected[..] || other_build() {
+        Ok(VerificationResult::Verified)
+    } else {
+        Ok(VerificationResult::Mismatch {
+            expected: hex::encode(expected),
+            actual: hex::encode(actual),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    /// Signs `artifact` like `minisign -S -l`, returning the public key and the signature file.
+    fn minisign(artifact: &[u8]) -> (String, String) {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key_id = [7u8; 8];
+        let public_key = [b"Ed".as_slice(), &key_id, pair.public_key().as_ref()].concat();
+
+        let sig = pair.sign(artifact);
+        let comment = "timestamp:1700000000\tfile:zksolc";
+        let global = pair.sign(&[sig.as_ref(), comment.as_bytes()].concat());
+        let signature = format!(
+            "untrusted comment: signature from minisign secret key\n{}\ntrusted comment: {comment}\n{}\n",
+            STANDARD.encode([b"Ed".as_slice(), &key_id, sig.as_ref()].concat()),
+            STANDARD.encode(global.as_ref()),
+        );
+        (
+            format!(
+                "untrusted comment: minisign public key\n{}\n",
+                STANDARD.encode(public_key)
+            ),
+            signature,
+        )
+    }
+
+    #[test]
+    fn verifies_minisign_signatures() {
+        let (key, signature) = minisign(b"zksolc");
+        let verifier = MinisignVerifier::from_public_key(&key).unwrap();
+        verifier.verify(b"zksolc", signature.as_bytes()).unwrap();
+        assert!(matches!(
+            verifier.verify(b"tampered", signature.as_bytes()),
+            Err(SvmError::InvalidSignature(_))
+        ));
+
+        let tampered = signature.replace("file:zksolc", "file:other");
+        assert!(verifier.verify(b"zksolc", tampered.as_bytes()).is_err());
+
+        let (other_key, _) = minisign(b"zksolc");
+        let other = MinisignVerifier::from_public_key(&other_key).unwrap();
+        assert!(other.verify(b"zksolc", signature.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(MinisignVerifier::from_public_key("").is_err());
+        assert!(MinisignVerifier::from_public_key("not base64!").is_err());
+        assert!(MinisignVerifier::from_public_key(&STANDARD.encode([0u8; 42])).is_err());
+    }
+
+    #[test]
+    fn check_requires_signatures() {
+        let version = Version::new(1, 4, 1);
+        let (key, signature) = minisign(b"zksolc");
+        let verifier = Verifier::new(MinisignVerifier::from_public_key(&key).unwrap());
+
+        check(&version, None, false, b"zksolc", None).unwrap();
+        check(&version, Some(&verifier), false, b"zksolc", None).unwrap();
+        check(
+            &version,
+            Some(&verifier),
+            true,
+            b"zksolc",
+            Some(signature.as_bytes()),
+        )
+        .unwrap();
+        assert!(matches!(
+            check(&version, Some(&verifier), true, b"zksolc", None),
+            Err(SvmError::SignatureRequired(_))
+        ));
+        assert!(matches!(
+            check(&version, None, true, b"zksolc", Some(signature.as_bytes())),
+            Err(SvmError::SignatureRequired(_))
+        ));
+        assert!(check(
+            &version,
+            Some(&verifier),
+            false,
+            b"other",
+            Some(signature.as_bytes())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn signature_url_appends_extension() {
+        let url = Url::parse("https://example.com/linux-amd64/zksolc-v1.4.1?raw=true").unwrap();
+        assert_eq!(
+            signature_url(&url, ".minisig").as_str(),
+            "https://example.com/linux-amd64/zksolc-v1.4.1.minisig?raw=true"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn verifies_installed_binaries() {
+        let version = Version::new(0, 0, 46);
+        let releases = |artifact: &str| Releases {
+            builds: vec![crate::BuildInfo::new(
+                version.clone(),
+                Sha256::digest(b"zksolc").to_vec(),
+            )],
+            releases: [(version.clone(), artifact.to_string())].into(),
+            fetched_at: None,
+            min_version: None,
+        };
+        assert!(matches!(
+            verify_binary(&version, &releases("zksolc-v0.0.46")),
+            Err(SvmError::VersionNotInstalled(_))
+        ));
+
+        crate::setup_data_dir().unwrap();
+        let dir = version_path(&version.to_string());
+        fs::create_dir_all(&dir).unwrap();
+        let verify = |artifact: &str| verify_binary(&version, &releases(artifact)).unwrap();
+        assert_eq!(verify("zksolc-v0.0.46"), VerificationResult::Missing);
+
+        let binary = version_binary(&version.to_string());
+        fs::write(&binary, "zksolc").unwrap();
+        assert_eq!(verify("zksolc-v0.0.46"), VerificationResult::Verified);
+        assert_eq!(
+            verify("zksolc-v0.0.46.zip"),
+            VerificationResult::Unverifiable
+        );
+
+        fs::write(&binary, "tampered").unwrap();
+        let result = verify("zksolc-v0.0.46");
+        assert!(result.needs_repair());
+        assert!(matches!(result, VerificationResult::Mismatch { .. }));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn verifiers_compare_by_identity() {
+        let verifier = Verifier::new(CommandVerifier::gpg());
+        assert_eq!(verifier, verifier.clone());
+        assert_ne!(verifier, Verifier::new(CommandVerifier::gpg()));
+        assert_eq!(verifier.extension(), ".asc");
+    }
+}
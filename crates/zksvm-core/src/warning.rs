@@ -0,0 +1,152 @@
+use crate::{Platform, Recovery, Staleness};
+use semver::Version;
+use std::{fmt, path::PathBuf};
+use url::Url;
+
+/// A non-fatal condition encountered during an operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// Release information may be outdated.
+    Stale(Staleness),
+    /// No builds are published for `platform`, so builds of `artifacts` are used under emulation.
+    Emulated {
+        platform: Platform,
+        artifacts: Platform,
+    },
+    /// No `platform` build of zksolc `version` is published, so the build of `artifacts` is used
+    /// under emulation, e.g. Rosetta on Apple Silicon.
+    EmulatedBuild {
+        version: Version,
+        platform: Platform,
+        artifacts: Platform,
+    },
+    /// A corrupted state file was recovered.
+    StateFileRecovered { path: PathBuf, recovery: Recovery },
+    /// zksolc `version` was installed although the release list declares that it requires zksvm
+    /// `required` or newer.
+    OutdatedManager {
+        version: Version,
+        required: Version,
+        current: Version,
+    },
+    /// zksolc `version` was installed without verifying it, as no checksum is published for it.
+    Unchecked { version: Version },
+    /// The release list could not be fetched from `base_url`, so it and the artifacts are
+    /// downloaded from its fallback `served_from` instead.
+    FallbackSource { base_url: Url, served_from: Url },
+    /// The config file sets `key`, which this zksvm does not know and ignores, see
+    /// [`Config::unknown_keys`](crate::Config::unknown_keys).
+    UnknownConfigKey { key: String },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stale(staleness) => staleness.fmt(f),
+            Self::Emulated {
+                platform,
+                artifacts,
+            } => write!(
+                f,
+                "no zksolc builds are published for {platform}, using {artifacts} builds under emulation"
+            ),
+            Self::EmulatedBuild {
+                version,
+                platform,
+                artifacts,
+            } => write!(
+                f,
+                "no {platform} build of zksolc {version} is published, using the {artifacts} build under emulation"
+            ),
+            Self::StateFileRecovered { path, recovery } => {
+                let action = match recovery {
+                    Recovery::RestoredBackup => "restored it from its backup",
+                    Recovery::ResetToDefault => "reset it to its default",
+                };
+                write!(f, "{} was corrupted, {action}", path.display())
+            }
+            Self::OutdatedManager {
+                version,
+                required,
+                current,
+            } => write!(
+                f,
+                "zksolc {version} requires zksvm {required} or newer, but this is zksvm {current}; update zksvm"
+            ),
+            Self::Unchecked { version } => write!(
+                f,
+                "no checksum is published for zksolc {version}, it was installed without verifying it"
+            ),
+            Self::FallbackSource {
+                base_url,
+                served_from,
+            } => write!(
+                f,
+                "release lists are no longer served from {base_url}, falling back to {served_from}; update zksvm or set ZKSVM_RELEASES_URL"
+            ),
+            Self::UnknownConfigKey { key } => write!(
+                f,
+                "the config file sets the unknown key {key:?}, which is ignored; it may be meant for a newer zksvm"
+            ),
+        }
+    }
+}
+
+impl From<Staleness> for Warning {
+    fn from(staleness: Staleness) -> Self {
+        Self::Stale(staleness)
+    }
+}
+
+/// The result of an operation along with the warnings collected while performing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithWarnings<T> {
+    pub value: T,
+    pub warnings: Vec<Warning>,
+}
+
+impl<T> WithWarnings<T> {
+    /// Wraps a value without any warnings.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            warnings: vec![],
+        }
+    }
+
+    /// Adds a warning.
+    pub fn push(&mut self, warning: impl Into<Warning>) {
+        self.warnings.push(warning.into());
+    }
+
+    /// Maps the value, keeping the warnings.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> WithWarnings<U> {
+        WithWarnings {
+            value: f(self.value),
+            warnings: self.warnings,
+        }
+    }
+
+    /// Returns the value and the warnings.
+    pub fn into_parts(self) -> (T, Vec<Warning>) {
+        (self.value, self.warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn collects_warnings() {
+        let mut result = WithWarnings::new(1);
+        result.push(Staleness::IndexOutdated {
+            age: Duration::from_secs(2 * 24 * 60 * 60),
+        });
+        let (value, warnings) = result.map(|v| v + 1).into_parts();
+        assert_eq!(value, 2);
+        assert_eq!(warnings[0].to_string(), "release list was fetched 2d ago");
+    }
+}
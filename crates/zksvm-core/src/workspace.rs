@@ -0,0 +1,472 @@
+use crate::{
+    get_global_version, install, installed_versions, set_global_version, version_binary, SvmError,
+};
+use semver::Version;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file pinning the zksolc version of a project.
+pub const PROJECT_VERSION_FILE: &str = ".zksolc-version";
+
+/// Name of the project configuration file.
+pub const PROJECT_CONFIG_FILE: &str = "zksvm.toml";
+
+/// Environment variable selecting the zksolc version outside of projects pinning one.
+pub const ZKSVM_ZKSOLC_VERSION: &str = "ZKSVM_ZKSOLC_VERSION";
+
+/// Where the active zksolc version was selected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VersionSource {
+    /// Pinned by the project rooted at this directory.
+    Project(PathBuf),
+    /// Set by [`ZKSVM_ZKSOLC_VERSION`].
+    Env,
+    /// The global default.
+    Global,
+}
+
+/// The zksolc version to use, along with where it was selected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveVersion {
+    pub version: Version,
+    pub source: VersionSource,
+}
+
+/// How [`Workspace::ensure_ready`] may change the machine to satisfy a project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Policy {
+    /// Install the pinned version if it is missing.
+    pub auto_install: bool,
+    /// Make the pinned version the global default.
+    pub set_global: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            auto_install: true,
+            set_global: false,
+        }
+    }
+}
+
+/// Contents of a `zksvm.toml` project file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectConfig {
+    zksolc: Option<Version>,
+    solc: Option<Version>,
+    policy: Option<Policy>,
+}
+
+/// Everything zksvm knows about the compiler requirements of a project.
+///
+/// Settings are read from [`PROJECT_VERSION_FILE`]s and [`PROJECT_CONFIG_FILE`]s in the
+/// directory the search starts at and all of its ancestors. Each setting is taken from the
+/// nearest file defining it, so a pin at the root of a monorepo can be overridden per package.
+/// Within a directory, a version in [`PROJECT_VERSION_FILE`] takes precedence over the `zksolc`
+/// key of [`PROJECT_CONFIG_FILE`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Workspace {
+    /// The nearest directory containing project files, or the directory the search started at
+    /// if there are none.
+    pub root: PathBuf,
+    /// The zksolc version the project is pinned to.
+    pub zksolc: Option<Version>,
+    /// The solc version the project requires alongside zksolc.
+    ///
+    /// This is recorded for tooling that provides solc itself and is not installed by
+    /// [`Workspace::ensure_ready`].
+    pub solc: Option<Version>,
+    /// What [`Workspace::ensure_ready`] is allowed to do.
+    pub policy: Policy,
+    /// All project files the settings were resolved from, nearest first.
+    pub files: Vec<ProjectFile>,
+}
+
+/// A project file and the versions it pins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectFile {
+    pub path: PathBuf,
+    pub zksolc: Option<Version>,
+    pub solc: Option<Version>,
+}
+
+/// The result of [`Workspace::ensure_ready`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ready {
+    /// The zksolc binary to use for the project, if it pins a version.
+    pub zksolc: Option<PathBuf>,
+    /// Whether the pinned version had to be installed.
+    pub installed: bool,
+    /// Whether the pinned version was made the global default.
+    pub set_global: bool,
+}
+
+impl Workspace {
+    /// Loads the workspace containing `dir`, searching it and its ancestors for project files.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, SvmError> {
+        let dir = dir.as_ref();
+        let mut workspace = Self {
+            root: dir.to_path_buf(),
+            zksolc: None,
+            solc: None,
+            policy: Policy::default(),
+            files: vec![],
+        };
+        let mut policy = None;
+        for root in dir.ancestors() {
+            let found = read_project_files(root)?;
+            if found.is_empty() {
+                continue;
+            }
+            if workspace.files.is_empty() {
+                workspace.root = root.to_path_buf();
+            }
+            for (file, file_policy) in found {
+                workspace.zksolc = workspace.zksolc.or_else(|| file.zksolc.clone());
+                workspace.solc = workspace.solc.or_else(|| file.solc.clone());
+                policy = policy.or(file_policy);
+                workspace.files.push(file);
+            }
+        }
+        workspace.policy = policy.unwrap_or_default();
+        Ok(workspace)
+    }
+
+    /// Returns the zksolc version to use in this workspace: the project pin, then
+    /// [`ZKSVM_ZKSOLC_VERSION`], then the global version.
+    pub fn active_version(&self) -> Result<Option<ActiveVersion>, SvmError> {
+        if let Some(version) = &self.zksolc {
+            return Ok(Some(ActiveVersion {
+                version: version.clone(),
+                source: VersionSource::Project(self.root.clone()),
+            }));
+        }
+        match std::env::var(ZKSVM_ZKSOLC_VERSION) {
+            Ok(version) if !version.trim().is_empty() => {
+                let version = Version::parse(version.trim()).map_err(|err| {
+                    SvmError::InvalidEnvVersion(ZKSVM_ZKSOLC_VERSION, err.to_string())
+                })?;
+                return Ok(Some(ActiveVersion {
+                    version,
+                    source: VersionSource::Env,
+                }));
+            }
+            _ => {}
+        }
+        Ok(get_global_version()?.map(|version| ActiveVersion {
+            version,
+            source: VersionSource::Global,
+        }))
+    }
+
+    /// Performs all installs and switches needed to compile the project, as allowed by its
+    /// [`Policy`].
+    pub async fn ensure_ready(&self) -> Result<Ready, SvmError> {
+        let Some(version) = &self.zksolc else {
+            return Ok(Ready::default());
+        };
+
+        let mut ready = Ready::default();
+        let path = if installed_versions().unwrap_or_default().contains(version) {
+            version_binary(&version.to_string())
+        } else if self.policy.auto_install {
+            ready.installed = true;
+            install(version).await?
+        } else {
+            return Err(SvmError::VersionNotInstalled(version.to_string()));
+        };
+
+        if self.policy.set_global {
+            set_global_version(version)?;
+            ready.set_global = true;
+        }
+        ready.zksolc = Some(path);
+        Ok(ready)
+    }
+}
+
+/// Reads the project files in `dir`, the version file first, along with the policy of the config
+/// file.
+fn read_project_files(dir: &Path) -> Result<Vec<(ProjectFile, Option<Policy>)>, SvmError> {
+    let mut files = vec![];
+    let version_file = dir.join(PROJECT_VERSION_FILE);
+    if version_file.is_file() {
+        let zksolc = read_version_file(&version_file)?;
+        files.push((
+            ProjectFile {
+                path: version_file,
+                zksolc: Some(zksolc),
+                solc: None,
+            },
+            None,
+        ));
+    }
+    let config_file = dir.join(PROJECT_CONFIG_FILE);
+    if config_file.is_file() {
+        let content = fs::read_to_string(&config_file)?;
+        let config = toml::from_str::<ProjectConfig>(&content)
+            .map_err(|err| SvmError::InvalidProjectFile(config_file.clone(), err.to_string()))?;
+        files.push((
+            ProjectFile {
+                path: config_file,
+                zksolc: config.zksolc,
+                solc: config.solc,
+            },
+            config.policy,
+        ));
+    }
+    Ok(files)
+}
+
+/// Returns the zksolc version pinned for the project containing `dir`, along with the project
+/// file pinning it.
+///
+/// Like rbenv or nvm local versions, `dir` and its ancestors are searched for a
+/// [`PROJECT_VERSION_FILE`] or a [`PROJECT_CONFIG_FILE`] with a `zksolc` key, and the nearest pin
+/// wins. The binary of the pinned version is [`version_binary`], once installed.
+pub fn find_project_version(dir: impl AsRef<Path>) -> Result<Option<(Version, PathBuf)>, SvmError> {
+    for root in dir.as_ref().ancestors() {
+        for (file, _) in read_project_files(root)? {
+            if let Some(version) = file.zksolc {
+                return Ok(Some((version, file.path)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Pins the project rooted at `dir` to `version` by writing its [`PROJECT_VERSION_FILE`], and
+/// returns the path of that file.
+pub fn set_project_version(dir: impl AsRef<Path>, version: &Version) -> Result<PathBuf, SvmError> {
+    let path = dir.as_ref().join(PROJECT_VERSION_FILE);
+    fs::write(&path, format!("{version}\n"))?;
+    Ok(path)
+}
+
+/// Returns the root of the repository containing `dir`, or `dir` itself outside of one.
+///
+/// Git submodules are part of the repository of their superproject, so this is the outermost
+/// ancestor with a `.git` directory.
+pub fn repository_root(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .filter(|ancestor| ancestor.join(".git").is_dir())
+        .last()
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Returns all project files below `root`, sorted by path.
+///
+/// Hidden directories and common build and dependency directories are skipped.
+pub fn find_project_files(root: &Path) -> Result<Vec<ProjectFile>, SvmError> {
+    let mut files = vec![];
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        files.extend(read_project_files(&dir)?.into_iter().map(|(file, _)| file));
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let skipped = name.to_str().map_or(true, |name| {
+                name.starts_with('.') || matches!(name, "node_modules" | "target" | "out")
+            });
+            if !skipped && entry.file_type()?.is_dir() {
+                dirs.push(entry.path());
+            }
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Reads the version from a [`PROJECT_VERSION_FILE`].
+fn read_version_file(path: &Path) -> Result<Version, SvmError> {
+    let content = fs::read_to_string(path)?;
+    Version::parse(content.trim())
+        .map_err(|err| SvmError::InvalidProjectFile(path.to_path_buf(), err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_without_project_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = Workspace::load(dir.path()).unwrap();
+        assert_eq!(workspace.root, dir.path());
+        assert_eq!(workspace.zksolc, None);
+        assert_eq!(workspace.policy, Policy::default());
+    }
+
+    #[test]
+    fn load_from_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("contracts").join("src");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            dir.path().join(PROJECT_CONFIG_FILE),
+            "zksolc = \"1.3.16\"\nsolc = \"0.8.24\"\n\n[policy]\nauto-install = false\nset-global = true\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::load(&nested).unwrap();
+        assert_eq!(workspace.root, dir.path());
+        assert_eq!(workspace.zksolc, Some(Version::new(1, 3, 16)));
+        assert_eq!(workspace.solc, Some(Version::new(0, 8, 24)));
+        assert_eq!(
+            workspace.policy,
+            Policy {
+                auto_install: false,
+                set_global: true
+            }
+        );
+
+        // the version file takes precedence over the config file
+        fs::write(dir.path().join(PROJECT_VERSION_FILE), "1.3.17\n").unwrap();
+        let workspace = Workspace::load(&nested).unwrap();
+        assert_eq!(workspace.zksolc, Some(Version::new(1, 3, 17)));
+        assert_eq!(workspace.solc, Some(Version::new(0, 8, 24)));
+    }
+
+    #[test]
+    fn load_inherits_across_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("packages").join("token");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(dir.path().join(PROJECT_VERSION_FILE), "1.3.17").unwrap();
+        fs::write(
+            dir.path().join(PROJECT_CONFIG_FILE),
+            "solc = \"0.8.24\"\n[policy]\nset-global = true\n",
+        )
+        .unwrap();
+        fs::write(package.join(PROJECT_CONFIG_FILE), "zksolc = \"1.4.0\"\n").unwrap();
+
+        let workspace = Workspace::load(&package).unwrap();
+        assert_eq!(workspace.root, package);
+        assert_eq!(workspace.zksolc, Some(Version::new(1, 4, 0)));
+        assert_eq!(workspace.solc, Some(Version::new(0, 8, 24)));
+        assert!(workspace.policy.set_global);
+        assert_eq!(
+            workspace
+                .files
+                .iter()
+                .map(|f| f.path.clone())
+                .collect::<Vec<_>>(),
+            [
+                package.join(PROJECT_CONFIG_FILE),
+                dir.path().join(PROJECT_VERSION_FILE),
+                dir.path().join(PROJECT_CONFIG_FILE)
+            ]
+        );
+
+        let sibling = dir.path().join("packages").join("nft");
+        fs::create_dir_all(sibling.join("node_modules").join("dep")).unwrap();
+        fs::write(
+            sibling
+                .join("node_modules")
+                .join("dep")
+                .join(PROJECT_VERSION_FILE),
+            "1.3.9",
+        )
+        .unwrap();
+        assert_eq!(
+            Workspace::load(&sibling).unwrap().zksolc,
+            Some(Version::new(1, 3, 17))
+        );
+        assert_eq!(find_project_files(dir.path()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn find_and_set_project_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("contracts");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_project_version(&nested).unwrap(), None);
+
+        // a config file without a zksolc key does not stop the search
+        fs::write(nested.join(PROJECT_CONFIG_FILE), "solc = \"0.8.24\"\n").unwrap();
+        let path = set_project_version(dir.path(), &Version::new(1, 3, 17)).unwrap();
+        assert_eq!(path, dir.path().join(PROJECT_VERSION_FILE));
+        assert_eq!(
+            find_project_version(&nested).unwrap(),
+            Some((Version::new(1, 3, 17), path))
+        );
+
+        let path = set_project_version(&nested, &Version::new(1, 4, 0)).unwrap();
+        assert_eq!(
+            find_project_version(&nested).unwrap(),
+            Some((Version::new(1, 4, 0), path))
+        );
+    }
+
+    #[test]
+    fn repository_root_spans_submodules() {
+        let dir = tempfile::tempdir().unwrap();
+        let submodule = dir.path().join("lib").join("contracts");
+        fs::create_dir_all(dir.path().join(".git")).unwrap();
+        fs::create_dir_all(&submodule).unwrap();
+        fs::write(
+            submodule.join(".git"),
+            "gitdir: ../../.git/modules/contracts",
+        )
+        .unwrap();
+        assert_eq!(repository_root(&submodule), dir.path());
+    }
+
+    #[test]
+    fn load_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(PROJECT_VERSION_FILE), "latest").unwrap();
+        assert!(matches!(
+            Workspace::load(dir.path()),
+            Err(SvmError::InvalidProjectFile(..))
+        ));
+
+        fs::remove_file(dir.path().join(PROJECT_VERSION_FILE)).unwrap();
+        fs::write(dir.path().join(PROJECT_CONFIG_FILE), "zksolc = 1").unwrap();
+        assert!(matches!(
+            Workspace::load(dir.path()),
+            Err(SvmError::InvalidProjectFile(..))
+        ));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn active_version_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var(ZKSVM_ZKSOLC_VERSION, "1.3.9");
+        let active = Workspace::load(dir.path()).unwrap().active_version();
+        assert_eq!(
+            active.unwrap(),
+            Some(ActiveVersion {
+                version: Version::new(1, 3, 9),
+                source: VersionSource::Env
+            })
+        );
+
+        fs::write(dir.path().join(PROJECT_VERSION_FILE), "1.3.17").unwrap();
+        let active = Workspace::load(dir.path()).unwrap().active_version();
+        std::env::remove_var(ZKSVM_ZKSOLC_VERSION);
+        assert_eq!(
+            active.unwrap().unwrap().source,
+            VersionSource::Project(dir.path().to_path_buf())
+        );
+    }
+
+    #[tokio::test]
+    async fn ensure_ready_without_pin() {
+        let dir = tempfile::tempdir().unwrap();
+        let ready = Workspace::load(dir.path())
+            .unwrap()
+            .ensure_ready()
+            .await
+            .unwrap();
+        assert_eq!(ready, Ready::default());
+    }
+}
@@ -0,0 +1,20 @@
+//! Builds artifact URLs from arbitrary artifact names, which come from the release list.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use url::Url;
+use zksvm_core::{Platform, ReleaseSource};
+
+fuzz_target!(|artifact: &str| {
+    let source = ReleaseSource::new(Url::parse("https://example.com/releases/").unwrap()).unwrap();
+    let list = source.releases_url(Platform::LinuxAmd64);
+    let Ok(url) = source.artifact_url(Platform::LinuxAmd64, artifact) else {
+        return;
+    };
+
+    // an artifact name must never escape the directory of its platform
+    let dir = list.as_str().strip_suffix("list.json").unwrap();
+    assert!(url.as_str().starts_with(dir), "{url} escapes {dir}");
+    assert_eq!(url.host_str(), Some("example.com"));
+});
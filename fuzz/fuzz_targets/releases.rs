@@ -0,0 +1,24 @@
+//! Deserializes arbitrary release lists, including their hex checksums and timestamps.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zksvm_core::Releases;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(releases) = serde_json::from_slice::<Releases>(data) else {
+        return;
+    };
+    for build in &releases.builds {
+        assert_eq!(build.sha256.len(), 32);
+        let _ = releases.get_checksum(&build.version);
+        let _ = releases.get_signature(&build.version);
+    }
+    let _ = releases.latest_build();
+
+    // whatever was accepted must survive a round trip through the cache
+    let json = serde_json::to_vec(&releases).expect("serialize releases");
+    let reparsed: Releases = serde_json::from_slice(&json).expect("reparse releases");
+    assert_eq!(reparsed.builds.len(), releases.builds.len());
+    assert_eq!(reparsed.releases, releases.releases);
+});
@@ -0,0 +1,26 @@
+//! Resolves arbitrary version strings and requirements, and parses `zksolc --version` output.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use semver::{Version, VersionReq};
+use zksvm_core::{best_matching_version, CompilerInfo, Releases};
+
+fuzz_target!(|input: &str| {
+    let versions = [
+        Version::new(1, 3, 13),
+        Version::new(1, 3, 17),
+        Version::new(1, 4, 1),
+    ];
+    if let Ok(req) = VersionReq::parse(input) {
+        if let Some(best) = best_matching_version(&req, &versions) {
+            assert!(req.matches(&best));
+        }
+    }
+    if let Ok(version) = Version::parse(input.trim()) {
+        let _ = Releases::default().supported_versions().matches(&version);
+    }
+    if let Some(info) = CompilerInfo::parse(input) {
+        assert!(input.contains(&info.version.to_string()));
+    }
+});